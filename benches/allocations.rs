@@ -0,0 +1,102 @@
+//! Counts the allocations made while building a large instrument,
+//! with and without reserving capacity for its groups and regions.
+//!
+//! Run it with `cargo bench`.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use sofiza::{Instrument, Opcode, Region};
+
+/// An allocator that counts the number of allocations and reallocations.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static REALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        REALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const GROUPS: usize = 200;
+const REGIONS_PER_GROUP: usize = 64;
+
+/// Prints the number of allocations and reallocations made by `f`,
+/// and the time it took.
+fn measure<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let reallocations = REALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+    let reallocations = REALLOCATIONS.load(Ordering::Relaxed) - reallocations;
+    println!("{name:<24} {allocations:>10} allocs {reallocations:>6} reallocs {elapsed:>12.3?}");
+    result
+}
+
+/// Generates the SFZ code of a large instrument.
+fn large_sfz() -> String {
+    let mut sfz = String::from("<global> volume=-6 ampeg_release=0.5\n");
+    for group in 0..GROUPS {
+        sfz.push_str(&format!(
+            "<group> group_label=G{group} lovel={}\n",
+            group % 128
+        ));
+        for key in 0..REGIONS_PER_GROUP {
+            sfz.push_str(&format!(
+                "<region> sample=g{group}_k{key}.wav key={key} tune={}\n",
+                key % 100
+            ));
+        }
+    }
+    sfz
+}
+
+/// Pushes all the regions into the instrument, one by one.
+fn push_regions(mut instrument: Instrument) -> Instrument {
+    for g in 0..GROUPS {
+        instrument.new_group();
+        for key in 0..REGIONS_PER_GROUP {
+            let mut region = Region::with_group(g);
+            region.add_opcode(&Opcode::key(key as u8));
+            instrument.regions.push(region);
+        }
+    }
+    instrument
+}
+
+fn main() {
+    let sfz = large_sfz();
+    let regions = GROUPS * REGIONS_PER_GROUP;
+
+    println!("{GROUPS} groups, {regions} regions\n");
+
+    let a = measure("new + push", || push_regions(Instrument::new()));
+    let b = measure("with_capacity + push", || {
+        push_regions(Instrument::with_capacity(GROUPS, regions))
+    });
+    assert_eq!(a.regions, b.regions);
+
+    let i = measure("from_sfz", || {
+        Instrument::from_sfz(&sfz, Path::new("")).unwrap()
+    });
+    assert_eq!(i.regions(), regions);
+}
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use thiserror::Error;
 
 /// All the possible errors returned by this library.
@@ -10,6 +12,35 @@ pub enum Error {
     #[error("{0}")]
     OutOfBounds(String),
 
+    /// The value of an opcode is malformed.
+    #[error("`{value}` is not a valid value for `{opcode}`")]
+    ParseValue { opcode: String, value: String },
+
+    /// The SFZ code can't be parsed, at the given line and column.
+    ///
+    /// The column is the one of the code as written, before substituting
+    /// the variables. If the error is in an included file, `include` has
+    /// its path as written in the `#include` directive, and the line and
+    /// column are the ones in that file.
+    #[error(
+        "{}line {line}, column {col}: {message}",
+        include.as_ref().map(|path| format!("{}, ", path.display())).unwrap_or_default()
+    )]
+    Parse {
+        line: usize,
+        col: usize,
+        include: Option<PathBuf>,
+        message: String,
+    },
+
+    /// A variable is used without having been defined with `#define`.
+    #[error("undefined variable `${0}`")]
+    UndefinedVariable(String),
+
+    /// A sample file is not a WAV file that can be understood.
+    #[error("invalid WAV file: {0}")]
+    InvalidWav(String),
+
     /// Represents all other cases of `std::io::Error`.
     #[error(transparent)]
     IOError(#[from] std::io::Error),
@@ -29,5 +60,38 @@ mod tests_error {
     #[test]
     fn test_error() {
         assert("generic error", Error::Generic);
+        assert(
+            "`abc` is not a valid value for `cutoff`",
+            Error::ParseValue {
+                opcode: "cutoff".into(),
+                value: "abc".into(),
+            },
+        );
+        assert(
+            "line 3, column 9: unknown header `<foo>`",
+            Error::Parse {
+                line: 3,
+                col: 9,
+                include: None,
+                message: "unknown header `<foo>`".into(),
+            },
+        );
+        assert(
+            "regions.sfz, line 2, column 5: unknown header `<foo>`",
+            Error::Parse {
+                line: 2,
+                col: 5,
+                include: Some("regions.sfz".into()),
+                message: "unknown header `<foo>`".into(),
+            },
+        );
+        assert(
+            "undefined variable `$KEY`",
+            Error::UndefinedVariable("KEY".into()),
+        );
+        assert(
+            "invalid WAV file: missing the `fmt ` chunk",
+            Error::InvalidWav("missing the `fmt ` chunk".into()),
+        );
     }
 }
@@ -1,11 +1,18 @@
-//!
+//! An SFZ format parser.
 //!
 
 pub mod error;
 pub(crate) mod sfz;
 pub(crate) mod utils;
+#[cfg(feature = "wav")]
+pub mod wav;
 
 pub use sfz::{
-    types::{fil_type, loop_mode, trigger},
-    Group, Header, Instrument, Opcode, Region,
+    types::{
+        direction, effect_filter, effect_type, fil_type, loop_mode, loop_type, off_mode, on_off,
+        phase, sw_vel, trigger, waveform, xf_curve,
+    },
+    Curve, Diagnostic, DiagnosticKind, Effect, FormatOptions, Group, Header, Instrument, LfoConfig,
+    LoopSettings, Master, MidiContext, Opcode, OpcodeMap, OpcodeType, Region, RoutingMatrix,
+    SampleResolver, SfzWriteOptions, Warning, WarningKind,
 };
@@ -0,0 +1,51 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use crate::sfz::Opcode;
+
+/// Curves define the response to velocity and MIDI CCs.
+///
+/// A curve is defined with the <curve> header, and the opcodes
+/// following it last till the next header, or till the end of the file.
+///
+/// - info: [curve](https://sfzformat.com/headers/curve)
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Curve {
+    /// The index used to refer to this curve, if specified.
+    pub curve_index: Option<u8>,
+
+    /// The defined points of the curve, from the point index to its value.
+    pub points: BTreeMap<u8, f32>,
+}
+
+impl Curve {
+    /// New curve.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an opcode to the curve.
+    ///
+    /// Only the `curve_index` and `vN` opcodes belong to a curve,
+    /// any other opcode is ignored.
+    pub fn add_opcode(&mut self, o: &Opcode) {
+        match o {
+            Opcode::curve_index(index) => self.curve_index = Some(*index),
+            Opcode::vN(n, value) => {
+                self.points.insert(*n, *value);
+            }
+            _ => (),
+        }
+    }
+
+    /// Returns the opcodes defining this curve.
+    pub fn opcodes(&self) -> Vec<Opcode> {
+        self.curve_index
+            .map(Opcode::curve_index)
+            .into_iter()
+            .chain(self.points.iter().map(|(&n, &v)| Opcode::vN(n, v)))
+            .collect()
+    }
+}
@@ -0,0 +1,64 @@
+use std::fmt::Debug;
+
+use crate::sfz::{Opcode, OpcodeMap};
+
+/// Effects process the audio of the buses they are assigned to.
+///
+/// An effect is defined with the <effect> header, and the opcodes
+/// following it last till the next header, or till the end of the file.
+///
+/// - info: [effect](https://sfzformat.com/headers/effect)
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Effect {
+    /// The opcodes of this effect.
+    pub opcodes: OpcodeMap,
+
+    /// The processing order of this effect, if specified.
+    pub dsp_order: Option<u8>,
+
+    /// The bus this effect processes, if specified. Otherwise it's `main`.
+    pub bus: Option<String>,
+}
+
+impl Effect {
+    /// New effect.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an opcode to the effect.
+    pub fn add_opcode(&mut self, o: &Opcode) {
+        match o {
+            Opcode::dsp_order(order) => self.dsp_order = Some(*order),
+            Opcode::bus(bus) => self.bus = Some(bus.clone()),
+            _ => (),
+        }
+        self.opcodes.insert(o.full_name(), o.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests_effect {
+    use crate::sfz::{types::effect_type, Instrument, Opcode};
+    use std::path::Path;
+
+    #[test]
+    fn test_effect_bus() {
+        let i = Instrument::from_sfz(
+            "<effect> bus=fx1 type=mverb reverb_wet=40
+            <effect> type=lofi",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.effects.len(), 2);
+        assert_eq!(i.effects[0].bus.as_deref(), Some("fx1"));
+        assert_eq!(
+            i.effects[0].opcodes["type"],
+            Opcode::r#type(effect_type::from_name("mverb"))
+        );
+        assert_eq!(i.effects[0].opcodes["reverb_wet"], Opcode::reverb_wet(40.));
+        assert_eq!(i.effects[1].bus, None);
+    }
+}
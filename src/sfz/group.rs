@@ -7,13 +7,32 @@ use crate::sfz::{Opcode, OpcodeMap};
 /// A group is defined with the <group> opcode, and the parameters enumerated
 /// on it last till the next group opcode, or till the end of the file.
 ///
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Group {
     /// This list of opcodes overwrites the default ones.
     pub opcodes: OpcodeMap,
 
     /// The label of this group.
     pub label: String,
+
+    /// The opcodes of this master are inherited by the group.
+    pub master: Option<usize>,
+
+    /// The line of the SFZ code where this group's header is, if parsed.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) source_line: Option<usize>,
+
+    /// The index of the included file where this group's header is, if any.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) include: Option<usize>,
+}
+
+// The source line and the include are not part of the group's definition.
+impl PartialEq for Group {
+    fn eq(&self, other: &Self) -> bool {
+        self.opcodes == other.opcodes && self.label == other.label && self.master == other.master
+    }
 }
 
 impl Group {
@@ -22,8 +41,82 @@ impl Group {
         Self::default()
     }
 
+    /// New group with some label.
+    pub fn with_label(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Get the label of this group.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Get the line of the SFZ code where this group starts, counting from 1
+    ///
+    /// Only groups parsed from SFZ code have it.
+    pub fn source_line(&self) -> Option<usize> {
+        self.source_line
+    }
+
+    /// Get an opcode of this group by its full name, like `locc1`
+    pub fn opcode(&self, name: &str) -> Option<&Opcode> {
+        self.opcodes.get(name)
+    }
+
+    /// Get the value of an opcode of this group, if it's of type `T`
+    pub fn value<T: std::any::Any + Copy>(&self, name: &str) -> Option<T> {
+        self.opcode(name)?.value().copied()
+    }
+
+    /// Get the value of an `f32` opcode of this group
+    pub fn f32(&self, name: &str) -> Option<f32> {
+        self.value(name)
+    }
+
+    /// Get the value of a `u8` opcode of this group
+    pub fn u8(&self, name: &str) -> Option<u8> {
+        self.value(name)
+    }
+
+    /// Get the index of the [`Instrument::includes`][crate::Instrument::includes]
+    /// file where this group starts, if it was parsed from an included file.
+    pub fn include(&self) -> Option<usize> {
+        self.include
+    }
+
     /// Add an opcode to the group.
+    ///
+    /// An opcode with the same full name is replaced, keeping its position.
     pub fn add_opcode(&mut self, o: &Opcode) {
-        self.opcodes.insert(o.str_name(), o.clone());
+        self.opcodes.insert(o.full_name(), o.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests_group {
+    use super::*;
+
+    #[test]
+    fn test_group_with_label() {
+        let mut group = Group::with_label("Sustain");
+        group.add_opcode(&Opcode::volume(-3.));
+        assert_eq!(group.label(), "Sustain");
+        assert_eq!(group.opcodes.len(), 1);
+        assert_eq!(Group::new().label(), "");
+    }
+
+    #[test]
+    fn test_group_typed_getters() {
+        let mut group = Group::new();
+        group.add_opcode(&Opcode::cutoff(500.));
+        group.add_opcode(&Opcode::lokey(36));
+        assert_eq!(group.opcode("cutoff"), Some(&Opcode::cutoff(500.)));
+        assert_eq!(group.f32("cutoff"), Some(500.));
+        assert_eq!(group.u8("lokey"), Some(36));
+        assert_eq!(group.u8("cutoff"), None);
+        assert_eq!(group.f32("resonance"), None);
     }
 }
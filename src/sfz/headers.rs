@@ -1,3 +1,5 @@
+use std::fmt;
+
 use logos::Lexer;
 
 use crate::sfz::SfzToken;
@@ -10,6 +12,7 @@ use crate::sfz::SfzToken;
 /// [sfzformat.com/headers/](https://sfzformat.com/headers/)
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Header {
     // sfz v1 headers
     /// The basic component of an instrument. An instrument is defined by one or more regions.
@@ -99,4 +102,26 @@ impl Header {
             _ => None,
         }
     }
+
+    /// Returns the name of the header, without the brackets
+    pub fn name(&self) -> &'static str {
+        match self {
+            Header::Region => "region",
+            Header::Group => "group",
+            Header::Control => "control",
+            Header::Global => "global",
+            Header::Curve => "curve",
+            Header::Effect => "effect",
+            Header::Master => "master",
+            Header::Midi => "midi",
+            Header::Sample => "sample",
+        }
+    }
+}
+
+/// Formats the header as SFZ code, like `<region>`.
+impl fmt::Display for Header {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<{}>", self.name())
+    }
 }
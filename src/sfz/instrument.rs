@@ -1,16 +1,24 @@
 use std::{
-    fmt::Debug,
+    collections::BTreeSet,
+    fmt::{Debug, Write},
     fs::File,
     io::prelude::*,
+    ops::RangeInclusive,
     path::{Path, PathBuf},
+    str::FromStr,
 };
 
-use log::debug;
+use indexmap::IndexMap;
+use log::{debug, warn};
 use logos::Logos;
 
 use crate::{
     error::{Error, Result},
-    sfz::{types::OpcodeMap, Group, Header, Opcode, Region, SfzToken},
+    sfz::{
+        preprocess, types::OpcodeMap, Curve, Effect, Group, Header, Master, Opcode, Region,
+        SfzToken, Warning, WarningKind,
+    },
+    utils::fix_path_separators,
 };
 
 /// Represents the SFZ instrument parsed
@@ -23,26 +31,50 @@ use crate::{
 /// International Pitch Notation (IPN) convention. According to this rules,
 /// middle C in the keyboard is C4 and the MIDI note number 60.
 ///
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Instrument {
     /// The default opcodes for this instrument.
     pub global: OpcodeMap,
 
+    /// The list of masters.
+    ///
+    /// The opcodes in a master overrides those in global, for the associated groups and regions.
+    pub masters: Vec<Master>,
+
     /// The list of groups.
     ///
-    /// The opcodes in a group overrides those in global, for the associated region.
+    /// The opcodes in a group overrides those in global and in its master, for the associated region.
     pub groups: Vec<Group>,
 
     /// The list of regions.
     ///
-    /// The opcodes in a region overrides those in global and in its group.
+    /// The opcodes in a region overrides those in global, in its master and in its group.
     pub regions: Vec<Region>, // these opcodes override global, and their group ones
 
+    /// The list of effects.
+    pub effects: Vec<Effect>,
+
+    /// The list of curves.
+    pub curves: Vec<Curve>,
+
     /// The default path.
     // maybe make this later a: struct Control
     // https://sfzformat.com/headers/control
     pub default_path: PathBuf,
 
+    /// The root location the `default_path` is relative to.
+    pub(crate) root: PathBuf,
+
+    /// The variables defined with `#define`, with their values.
+    pub defines: IndexMap<String, String>,
+
+    /// The paths of the files included with `#include`, as written.
+    pub includes: Vec<PathBuf>,
+
+    /// The sample rate the instrument is going to be played at, if known.
+    pub(crate) sample_rate: Option<f32>,
+
     last_header_created: Header,
 }
 
@@ -52,44 +84,115 @@ impl Default for Instrument {
     }
 }
 
+/// Parses some SFZ code, looking for the samples relative to the current directory.
+impl FromStr for Instrument {
+    type Err = Error;
+
+    fn from_str(sfz: &str) -> Result<Self> {
+        Self::from_sfz(sfz, Path::new(""))
+    }
+}
+
+/// Parses some SFZ code, looking for the samples relative to the current directory.
+impl TryFrom<&str> for Instrument {
+    type Error = Error;
+
+    fn try_from(sfz: &str) -> Result<Self> {
+        sfz.parse()
+    }
+}
+
 // constructors:
 // - new
+// - new_with_path
+// - with_capacity
 // - from_file
 // - from_sfz
+// - from_sfz_verbose
+// - from_sfz_strict
 //
 // - add_opcode
 // - add_opcode_global
 // - add_opcode_to_group
 // - add_opcode_to_region
+// - masters
 // - groups
 // - regions
+// - curves
+// - into_groups
+// - into_regions
 // - regions_in
+// - regions_in_group
+// - regions_without_group
+// - resolved_opcodes
+// - sample_path
+// - sample_count
+// - regions_for_keyswitch
+// - matching_regions
+// - regions_without_keycenter
+// - opcode_names
+// - key_span
+// - effects_in_order
 // - new_group
 // - new_region
+// - add_group
+// - add_region
 // - set_region_group
 //
+// - to_sfz
+// - to_sfz_with
+// - to_sfz_pretty
+//
 impl Instrument {
     /// Creates an empty Instrument
     ///
     pub fn new() -> Instrument {
         Instrument {
             global: OpcodeMap::new(),
+            masters: Vec::<Master>::new(),
             groups: Vec::<Group>::new(),
             regions: Vec::<Region>::new(),
+            effects: Vec::<Effect>::new(),
+            curves: Vec::<Curve>::new(),
             default_path: PathBuf::new(),
+            root: PathBuf::new(),
+            defines: IndexMap::new(),
+            includes: Vec::new(),
+            sample_rate: None,
             last_header_created: Header::Global,
         }
     }
 
+    /// Creates an empty Instrument, whose samples are looked for in `root`
+    ///
+    pub fn new_with_path(root: impl Into<PathBuf>) -> Instrument {
+        let root = root.into();
+        Instrument {
+            default_path: root.clone(),
+            root,
+            ..Self::new()
+        }
+    }
+
+    /// Creates an empty Instrument, with space for some groups and regions
+    ///
+    pub fn with_capacity(groups: usize, regions: usize) -> Instrument {
+        Instrument {
+            groups: Vec::with_capacity(groups),
+            regions: Vec::with_capacity(regions),
+            ..Self::new()
+        }
+    }
+
     /// Creates an Instrument via loading and parsing some SFZ code in a file
     ///
     pub fn from_file(sfz_path: &Path) -> Result<Self> {
         // open sfz file, and read it into sfz_text
-        let mut sfz_file = File::open(&sfz_path)?;
+        let mut sfz_file = File::open(sfz_path)?;
         let mut sfz_text = String::new();
         sfz_file.read_to_string(&mut sfz_text)?;
 
-        Self::from_sfz(&sfz_text, sfz_path.parent().unwrap())
+        Self::from_sfz(&sfz_text, sfz_path.parent().unwrap_or(Path::new("")))
     }
 
     /// Creates an Instrument via parsing some SFZ code in a string
@@ -97,100 +200,290 @@ impl Instrument {
     /// sfz_path would be the root location from where to find the samples
     /// and default_path opcode value is appended to it.
     ///
+    /// The unknown headers are skipped along with their opcodes.
+    /// The malformed opcode values are errors, returned as [`Error::Parse`]
+    /// with the line and column where they are.
+    ///
     pub fn from_sfz(sfz: &str, sfz_path: &Path) -> Result<Self> {
+        Self::from_sfz_verbose(sfz, sfz_path).map(|(instrument, _)| instrument)
+    }
+
+    /// Creates an Instrument via parsing some SFZ code in a string,
+    /// alongside the warnings about everything that had to be ignored
+    ///
+    /// Like [`from_sfz`][Self::from_sfz], but reports the unknown opcodes,
+    /// the values out of range, the misplaced headers, and the variables
+    /// used without being defined, which are left as written.
+    ///
+    /// A repeated or misplaced `<control>` header is reported, but its opcodes
+    /// are still read. Only `default_path` is kept from the `<control>` headers,
+    /// and the last one found takes precedence. The rest of the `<control>`
+    /// opcodes aren't supported yet, and are reported as unknown opcodes.
+    ///
+    /// The unknown headers are reported, and skipped along with their opcodes.
+    /// The malformed opcode values are errors, returned as [`Error::Parse`]
+    /// with the line and column where they are.
+    ///
+    pub fn from_sfz_verbose(sfz: &str, sfz_path: &Path) -> Result<(Self, Vec<Warning>)> {
+        Self::parse(sfz, sfz_path, false)
+    }
+
+    /// Creates an Instrument via parsing some SFZ code in a string,
+    /// rejecting the code that [`from_sfz_verbose`][Self::from_sfz_verbose]
+    /// accepts with a warning about its structure
+    ///
+    /// A repeated or misplaced `<control>` header and an unknown header are
+    /// an [`Error::Parse`], and a variable used without being defined is an [`Error::UndefinedVariable`].
+    ///
+    pub fn from_sfz_strict(sfz: &str, sfz_path: &Path) -> Result<Self> {
+        Self::parse(sfz, sfz_path, true).map(|(instrument, _)| instrument)
+    }
+
+    /// The parser behind all the `from_sfz*` constructors
+    fn parse(sfz: &str, sfz_path: &Path, strict: bool) -> Result<(Self, Vec<Warning>)> {
         debug!("Instrument::from_sfz()\n-----------------------------");
 
-        // Initializes an instrument for construction
+        let pre = preprocess::preprocess(sfz, sfz_path)?;
+        let sfz = &pre.code;
+
+        // Initializes an instrument for construction, reserving space
+        // for the headers found, in order to avoid reallocations
         let mut instrument = Instrument {
-            global: OpcodeMap::new(),
-            groups: Vec::<Group>::new(),
-            regions: Vec::<Region>::new(),
             default_path: sfz_path.to_path_buf(),
-            last_header_created: Header::Global, // not used in this constructor
+            root: sfz_path.to_path_buf(),
+            defines: pre.defines.clone(),
+            includes: pre.includes.clone(),
+            ..Self::with_capacity(
+                sfz.matches("<group>").count(),
+                sfz.matches("<region>").count(),
+            )
         };
 
         // parser loop status
         let mut status = InstrumentParsingStatus::init();
+        let mut warnings = Vec::new();
+
+        // the variables used without being defined are left as written
+        for origin in &pre.lines {
+            for name in &origin.undefined {
+                if strict {
+                    return Err(Error::UndefinedVariable(name.clone()));
+                }
+                warnings.push(Warning {
+                    line: origin.line,
+                    ..Warning::new(&format!("${name}"), "", WarningKind::UndefinedVariable)
+                });
+            }
+        }
+
+        // the byte offsets where each line starts, for locating the headers
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(sfz.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        // the line of the code where each offset is
+        let line_index = |offset: usize| line_starts.partition_point(|&start| start <= offset) - 1;
+        // the line of the original code, and the include, where each offset comes from
+        let origin_at = |offset: usize| &pre.lines[line_index(offset)];
+        let line_at = |offset: usize| origin_at(offset).line;
+        let include_at = |offset: usize| origin_at(offset).include.map(|(i, _)| i);
+        let parse_error = |offset: usize, message: String| {
+            let origin = origin_at(offset);
+            let col = sfz[line_starts[line_index(offset)]..offset].chars().count() + 1;
+            let (line, include) = match origin.include {
+                Some((i, line)) => (line, Some(pre.includes[i].clone())),
+                None => (origin.line, None),
+            };
+            Error::Parse {
+                line,
+                col: origin.unsubstituted_col(col),
+                include,
+                message,
+            }
+        };
 
         // parser loop
-        let lex = SfzToken::lexer(sfz);
-        for t in lex {
+        let mut lex = SfzToken::lexer(sfz);
+        while let Some(t) = lex.next() {
+            let span = lex.span();
             match &t {
                 SfzToken::Header(h) => {
                     match h {
+                        Header::Master => {
+                            debug!("\nFound a new master:");
+
+                            status.new_master();
+                            instrument.masters.push(Master::new());
+                        }
                         Header::Group => {
                             debug!("\nFound a new group:");
 
                             status.new_group();
-                            instrument.groups.push(Group::new());
+                            instrument.groups.push(Group {
+                                master: status.master_counter,
+                                source_line: Some(line_at(span.start)),
+                                include: include_at(span.start),
+                                ..Group::new()
+                            });
                         }
                         Header::Region => {
                             debug!("Found a new region:");
 
                             status.new_region();
 
-                            // FIXME: SPEC: discard an empty region (or without a sample)
-                            instrument.regions.push(Region::new());
+                            // NOTE: the empty regions, or the ones without a sample, can be
+                            // discarded afterwards with `prune_empty_regions` and
+                            // `prune_regions_without_sample`
+                            instrument.regions.push(Region {
+                                group: status.current_group(),
+                                master: status.master_counter,
+                                source_line: Some(line_at(span.start)),
+                                include: include_at(span.start),
+                                ..Region::new()
+                            });
                         }
                         Header::Control => {
-                            status.new_control();
+                            let is_placed = status.new_control();
+                            if !is_placed && strict {
+                                let message = "repeated or misplaced `<control>` header";
+                                return Err(parse_error(span.start, message.into()));
+                            } else if !is_placed {
+                                warnings.push(Warning {
+                                    line: line_at(span.start),
+                                    ..Warning::new("<control>", "", WarningKind::MisplacedHeader)
+                                });
+                            }
                         }
                         Header::Global => {
-                            status.new_global();
+                            let is_placed = status.new_global();
+                            if !is_placed {
+                                warnings.push(Warning {
+                                    line: line_at(span.start),
+                                    ..Warning::new("<global>", "", WarningKind::MisplacedHeader)
+                                });
+                            }
+                        }
+                        Header::Curve => {
+                            debug!("\nFound a new curve:");
+
+                            status.new_curve();
+                            instrument.curves.push(Curve::new());
+                        }
+                        Header::Effect => {
+                            debug!("\nFound a new effect:");
+
+                            status.new_effect();
+                            instrument.effects.push(Effect::new());
                         }
-                        // TBD
-                        Header::Curve => debug!("\n<curve>"),
-                        // TBD
-                        Header::Effect => debug!("\n<effect>"),
                         _ => (),
                     }
                 }
 
                 SfzToken::Opcode(o) => {
+                    // an opcode for an ignored header
+                    if status.is_header_ignored {
+                        debug!("ignored OP {:?}", o);
+
                     // an opcode for <global>
-                    if status.is_header_global {
+                    } else if status.is_header_global {
                         debug!("global OP {:?}", o);
 
                         instrument.add_opcode_global(o);
 
+                    // an opcode for <master>
+                    } else if status.is_header_master {
+                        debug!("master OP {:?}", o);
+
+                        if let Some(master) = instrument.masters.last_mut() {
+                            master.add_opcode(o);
+                        }
+
+                    // an opcode for <effect>
+                    } else if status.is_header_effect {
+                        debug!("effect OP {:?}", o);
+
+                        if let Some(effect) = instrument.effects.last_mut() {
+                            effect.add_opcode(o);
+                        }
+
+                    // an opcode for <curve>
+                    } else if status.is_header_curve {
+                        debug!("curve OP {:?}", o);
+
+                        if let Some(curve) = instrument.curves.last_mut() {
+                            curve.add_opcode(o);
+                        }
+
                     // an opcode for <control>
                     } else if status.is_header_control {
                         if let Opcode::default_path(p) = o {
-                            instrument.default_path.push(p)
+                            instrument.default_path = sfz_path.join(p);
                         }
                     } else {
                         // an opcode for the <region>
-                        if status.are_regions_in_current_group() {
+                        if let (true, Some(region)) =
+                            (status.are_regions_in_current_group(), status.region_counter)
+                        {
                             debug!(
-                                "  - new region opcode: {:?} (g{} r{})",
+                                "  - new region opcode: {:?} (g{:?} r{})",
                                 o,
-                                status.group_counter.unwrap(),
-                                status.region_counter.unwrap()
+                                status.current_group(),
+                                region
                             );
 
-                            instrument.add_opcode_to_region(o, status.region_counter.unwrap())?;
-                            instrument.set_region_group(
-                                status.region_counter.unwrap(),
-                                status.group_counter,
-                            )?;
+                            instrument.add_opcode_to_region(o, region)?;
+                            instrument.set_region_group(region, status.current_group())?;
 
                         // an opcode for the <group>
-                        } else if status.are_groups() {
+                        } else if let Some(group) = status.current_group() {
                             debug!("  - new group opcode: {:?}", o);
 
-                            instrument.add_opcode_to_group(o, status.group_counter.unwrap())?;
+                            instrument.add_opcode_to_group(o, group)?;
+
+                        // an opcode before any header
                         } else {
-                            unreachable!();
+                            warn!("ignoring the opcode `{}` found before any header", o);
+                            let (name, value) = lex.slice().split_once('=').unwrap_or_default();
+                            warnings.push(Warning {
+                                line: line_at(span.start),
+                                ..Warning::new(name, value.trim(), WarningKind::MisplacedOpcode)
+                            });
                         }
                     }
                 }
+
+                // an opcode with a malformed value, or an unknown header
+                SfzToken::Error => {
+                    if let Some(error) = lex.extras.error.take() {
+                        return Err(parse_error(span.start, error.to_string()));
+                    }
+                    if lex.slice().starts_with('<') {
+                        if strict {
+                            let message = format!("unknown header `{}`", lex.slice());
+                            return Err(parse_error(span.start, message));
+                        }
+                        status.ignore_header();
+                        warnings.push(Warning {
+                            line: line_at(span.start),
+                            ..Warning::new(lex.slice(), "", WarningKind::UnknownHeader)
+                        });
+                    }
+                    if let Some(warning) = lex.extras.warning.take() {
+                        warnings.push(Warning {
+                            line: line_at(span.start),
+                            ..warning
+                        });
+                    }
+                }
                 _ => (),
             }
         }
 
         debug!("-----------------------------------\n");
 
-        Ok(instrument)
+        // the parser only assigns the regions to the groups it has created
+        debug_assert!(instrument.validate_region_groups().is_empty());
+        warnings.sort_by_key(|warning| warning.line);
+        Ok((instrument, warnings))
     }
 
     /// Add an opcode, depending on context, to either the last created region,
@@ -211,7 +504,7 @@ impl Instrument {
     /// Add an opcode to the global header
     ///
     pub fn add_opcode_global(&mut self, opcode: &Opcode) {
-        self.global.insert(opcode.str_name(), opcode.clone());
+        self.global.insert(opcode.full_name(), opcode.clone());
     }
 
     /// Add an opcode to a group
@@ -245,6 +538,11 @@ impl Instrument {
         Ok(())
     }
 
+    /// Get the number of masters
+    pub fn masters(&self) -> usize {
+        self.masters.len()
+    }
+
     /// Get the number of groups
     pub fn groups(&self) -> usize {
         self.groups.len()
@@ -255,6 +553,24 @@ impl Instrument {
         self.regions.len()
     }
 
+    /// Get the number of curves
+    pub fn curves(&self) -> usize {
+        self.curves.len()
+    }
+
+    /// Consumes the Instrument, returning its list of groups
+    pub fn into_groups(self) -> Vec<Group> {
+        self.groups
+    }
+
+    /// Consumes the Instrument, returning its list of regions
+    ///
+    /// The regions keep the index of their group, but the opcodes they
+    /// would inherit from it and from the global header are not included.
+    pub fn into_regions(self) -> Vec<Region> {
+        self.regions
+    }
+
     /// Get the number of regions in a group
     pub fn regions_in(&self, group: usize) -> Result<usize> {
         if group >= self.groups() {
@@ -265,13 +581,239 @@ impl Instrument {
             ]));
         }
 
-        let mut count = 0;
-        for region in self.regions.iter() {
-            if region.group() == Some(group) {
-                count += 1;
+        Ok(self.regions_in_group(group).count())
+    }
+
+    /// Iterates over the regions in a group
+    pub fn regions_in_group(&self, group: usize) -> impl Iterator<Item = &Region> {
+        self.regions
+            .iter()
+            .filter(move |region| region.group() == Some(group))
+    }
+
+    /// Iterates over the regions without a group
+    pub fn regions_without_group(&self) -> impl Iterator<Item = &Region> {
+        self.regions
+            .iter()
+            .filter(|region| region.group().is_none())
+    }
+
+    /// Get the opcodes that apply to a region, taking inheritance into account
+    ///
+    /// The global opcodes are overridden by the ones in the region's master,
+    /// these by the ones in its group, and these by the region's own opcodes.
+    pub fn resolved_opcodes(&self, region: usize) -> Result<OpcodeMap> {
+        let region = self.regions.get(region).ok_or_else(|| {
+            Error::OutOfBounds(format![
+                "There's no region `{0}`, the instrument has `{1}` regions",
+                region,
+                self.regions()
+            ])
+        })?;
+
+        let maps: Vec<&OpcodeMap> = region.inheritance(self).collect();
+        let mut resolved = OpcodeMap::new();
+        for opcodes in maps.into_iter().rev() {
+            resolved.extend(opcodes.iter().map(|(name, o)| (name.clone(), o.clone())));
+        }
+        Ok(resolved)
+    }
+
+    /// Get the path of the sample of a region, inside the default path
+    ///
+    /// The `sample` opcode is taken into account with inheritance, and the path
+    /// separators are normalized for the current platform.
+    /// Returns `None` if the region doesn't exist or has no sample.
+    pub fn sample_path(&self, region: usize) -> Option<PathBuf> {
+        match self.regions.get(region)?.inherited_opcode("sample", self) {
+            Some(Opcode::sample(sample)) => Some(fix_path_separators(
+                &self.default_path.join(sample).to_string_lossy(),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Get the number of sample references of the regions, and the number
+    /// of distinct samples among them, as `(total, distinct)`
+    ///
+    /// The samples are compared by their [`sample_path`][Self::sample_path],
+    /// and the regions without a sample are not counted.
+    pub fn sample_count(&self) -> (usize, usize) {
+        let paths: Vec<PathBuf> = (0..self.regions())
+            .filter_map(|region| self.sample_path(region))
+            .collect();
+        let distinct: BTreeSet<&PathBuf> = paths.iter().collect();
+        (paths.len(), distinct.len())
+    }
+
+    /// Get the indices of the regions selected by pressing a keyswitch
+    ///
+    /// These are the regions whose `sw_last`, or `sw_lolast` to `sw_hilast` range,
+    /// contains the key, as long as the key is in their `sw_lokey` to `sw_hikey`
+    /// range, taking inheritance into account.
+    pub fn regions_for_keyswitch(&self, sw_key: u8) -> Vec<usize> {
+        self.regions
+            .iter()
+            .enumerate()
+            .filter(|(_, region)| {
+                let value = |f: fn(&Opcode) -> Option<u8>| region.inherited_value(self, f);
+
+                let lokey = value(|o| match o {
+                    Opcode::sw_lokey(v) => Some(*v),
+                    _ => None,
+                });
+                let hikey = value(|o| match o {
+                    Opcode::sw_hikey(v) => Some(*v),
+                    _ => None,
+                });
+                if !(lokey.unwrap_or(0)..=hikey.unwrap_or(127)).contains(&sw_key) {
+                    return false;
+                }
+
+                if let Some(last) = value(|o| match o {
+                    Opcode::sw_last(v) => Some(*v),
+                    _ => None,
+                }) {
+                    return last == sw_key;
+                }
+                let lolast = value(|o| match o {
+                    Opcode::sw_lolast(v) => Some(*v),
+                    _ => None,
+                });
+                let hilast = value(|o| match o {
+                    Opcode::sw_hilast(v) => Some(*v),
+                    _ => None,
+                });
+                match (lolast, hilast) {
+                    (None, None) => false,
+                    (lo, hi) => (lo.unwrap_or(0)..=hi.unwrap_or(127)).contains(&sw_key),
+                }
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Get the indices of the regions that play a note with the provided
+    /// key, velocity and MIDI channel
+    ///
+    /// The `lokey`/`hikey`, `lovel`/`hivel` and `lochan`/`hichan` ranges
+    /// must all contain the note, taking inheritance into account.
+    /// See [`Region::matches`] for taking the rest of the MIDI input into account.
+    pub fn matching_regions(&self, key: u8, velocity: u8, channel: u8) -> Vec<usize> {
+        self.regions
+            .iter()
+            .enumerate()
+            .filter(|(_, region)| region.matches_note(key, velocity, channel, self))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Get the indices of the regions that don't set a `pitch_keycenter`
+    ///
+    /// These regions neither set it nor inherit it, either directly or through
+    /// `key`, so they play at the default keycenter of 60. The keycenter of
+    /// each one can then be guessed with [`Region::infer_keycenter_from_filename`].
+    pub fn regions_without_keycenter(&self) -> Vec<usize> {
+        self.regions
+            .iter()
+            .enumerate()
+            .filter(|(_, region)| {
+                region
+                    .inherited_value(self, |o| match o {
+                        Opcode::pitch_keycenter(v) | Opcode::key(v) => Some(*v),
+                        _ => None,
+                    })
+                    .is_none()
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Get the distinct names of the opcodes used in the instrument
+    ///
+    /// The names of the parametric opcodes keep their parameter letters, like `loccN`.
+    pub fn opcode_names(&self) -> BTreeSet<&'static str> {
+        let curves = self.curves.iter().flat_map(|c| c.opcodes());
+        self.blocks()
+            .flat_map(|(_, _, opcodes)| opcodes.values().map(Opcode::name))
+            .chain(curves.map(|o| o.name()))
+            .collect()
+    }
+
+    /// Get the range of keys that play any region of the instrument
+    ///
+    /// Returns `None` if there are no regions.
+    pub fn key_span(&self) -> Option<RangeInclusive<u8>> {
+        self.regions
+            .iter()
+            .map(|region| region.key_range(self))
+            .reduce(|span, range| *span.start().min(range.start())..=*span.end().max(range.end()))
+    }
+
+    /// Get the effects sorted by their processing order
+    ///
+    /// Effects without a `dsp_order` go last, in the order they were declared.
+    pub fn effects_in_order(&self) -> Vec<&Effect> {
+        self.effect_indices_in_order()
+            .into_iter()
+            .map(|e| &self.effects[e])
+            .collect()
+    }
+
+    /// Get the indices of the effects, in the order of [`effects_in_order`][Self::effects_in_order]
+    pub(crate) fn effect_indices_in_order(&self) -> Vec<usize> {
+        let mut effects: Vec<usize> = (0..self.effects.len()).collect();
+        effects.sort_by_key(|&e| self.effects[e].dsp_order.map_or(u16::MAX, u16::from));
+        effects
+    }
+
+    /// Rewrites the sample paths under `old_root` so that they are under `new_root`
+    ///
+    /// It applies to the `sample` opcodes of every header, and to the
+    /// `default_path` of the instrument. Paths not under `old_root` are left
+    /// as they are. Absolute and relative paths are only matched against
+    /// an `old_root` of the same kind.
+    ///
+    pub fn rebase_samples(&mut self, old_root: &Path, new_root: &Path) {
+        let rebase = |path: &mut PathBuf| {
+            if let Ok(rest) = path.strip_prefix(old_root) {
+                *path = new_root.join(rest);
+            }
+        };
+
+        rebase(&mut self.default_path);
+        rebase(&mut self.root);
+        self.for_each_opcode_mut(|opcode| {
+            if let Opcode::sample(path) = opcode {
+                rebase(path);
+            }
+        });
+    }
+
+    /// Calls a closure on every opcode of the global header, the masters, the groups and the regions
+    ///
+    /// The opcodes keep their order. If the closure changes an opcode into a
+    /// different one, its name in the map is updated accordingly, replacing any
+    /// other opcode with the same name in that header.
+    ///
+    pub fn for_each_opcode_mut(&mut self, mut f: impl FnMut(&mut Opcode)) {
+        let maps = std::iter::once(&mut self.global)
+            .chain(self.masters.iter_mut().map(|m| &mut m.opcodes))
+            .chain(self.groups.iter_mut().map(|g| &mut g.opcodes))
+            .chain(self.regions.iter_mut().map(|r| &mut r.opcodes));
+        for opcodes in maps {
+            let mut renamed = false;
+            for (name, opcode) in opcodes.iter_mut() {
+                f(opcode);
+                renamed |= *name != opcode.full_name();
+            }
+            if renamed {
+                *opcodes = opcodes
+                    .drain(..)
+                    .map(|(_, opcode)| (opcode.full_name(), opcode))
+                    .collect();
             }
         }
-        Ok(count)
     }
 
     /// Create a new empty group header in the Instrument
@@ -294,11 +836,23 @@ impl Instrument {
 
         self.last_header_created = Header::Region;
 
-        // NOTE: needs a Opcode::sample in order to be valid
-        // maybe if the previous region doesn't have sample code, delete it?
-        // I'm not sure that's a good idea. Maybe a sample can be added later.
-        //
-        // Maybe add a method to check and delete all the regions without a sample
+        // NOTE: needs a Opcode::sample in order to be valid, but it can be added later.
+        // The regions without a sample can be deleted with `prune_regions_without_sample`
+    }
+
+    /// Add a group header to the Instrument
+    pub fn add_group(&mut self, group: Group) {
+        self.groups.push(group);
+        self.last_header_created = Header::Group;
+    }
+
+    /// Add a region header to the Instrument
+    ///
+    /// The region keeps the group it has, which can be set with
+    /// [`Region::with_group`], or afterwards with [`set_region_group`][Self::set_region_group].
+    pub fn add_region(&mut self, region: Region) {
+        self.regions.push(region);
+        self.last_header_created = Header::Region;
     }
 
     /// Set the group of a region (group can be None)
@@ -330,15 +884,332 @@ impl Instrument {
 
     // TODO:
     pub fn groups_iter(&self) {}
+
+    /// Returns the opcodes of every header of the instrument, alongside
+    /// the kind of header and the index of the master, group, region or effect
+    pub(crate) fn blocks(&self) -> impl Iterator<Item = (Header, Option<usize>, &OpcodeMap)> {
+        std::iter::once((Header::Global, None, &self.global))
+            .chain(
+                self.masters
+                    .iter()
+                    .enumerate()
+                    .map(|(i, m)| (Header::Master, Some(i), &m.opcodes)),
+            )
+            .chain(
+                self.groups
+                    .iter()
+                    .enumerate()
+                    .map(|(i, g)| (Header::Group, Some(i), &g.opcodes)),
+            )
+            .chain(
+                self.regions
+                    .iter()
+                    .enumerate()
+                    .map(|(i, r)| (Header::Region, Some(i), &r.opcodes)),
+            )
+            .chain(
+                self.effects
+                    .iter()
+                    .enumerate()
+                    .map(|(i, e)| (Header::Effect, Some(i), &e.opcodes)),
+            )
+    }
+
+    /// Serializes the Instrument into SFZ code
+    ///
+    /// A `<control>` header goes first, with the `default_path` relative to the
+    /// root location of the instrument, unless they are the same. Then goes the
+    /// global header, followed by the regions without a group, and then each
+    /// group followed by its regions. Then each master follows,
+    /// with its own regions and groups in the same way. Opcodes are written
+    /// in the same order they were added.
+    ///
+    /// The included files are inlined, and the variables are left substituted.
+    /// See [`to_sfz_with`][Self::to_sfz_with] for keeping the directives.
+    ///
+    pub fn to_sfz(&self) -> String {
+        self.to_sfz_with(SfzWriteOptions::default())
+    }
+
+    /// Serializes the Instrument into SFZ code, choosing how to write
+    /// the `#define` and `#include` directives
+    ///
+    /// When the includes are not inlined, each `#include` directive is written
+    /// in place of the groups and regions that were parsed from that file.
+    /// The rest of the headers are always inlined.
+    ///
+    pub fn to_sfz_with(&self, options: SfzWriteOptions) -> String {
+        self.write_sfz(options, FormatOptions::default())
+    }
+
+    /// Serializes the Instrument into SFZ code, formatted for being edited by hand
+    ///
+    /// The headers and opcodes are written in the same order as with
+    /// [`to_sfz`][Self::to_sfz].
+    ///
+    pub fn to_sfz_pretty(&self, format: FormatOptions) -> String {
+        self.write_sfz(SfzWriteOptions::default(), format)
+    }
+
+    fn write_sfz(&self, options: SfzWriteOptions, format: FormatOptions) -> String {
+        let mut sfz = String::new();
+
+        if options.preserve_defines {
+            for (name, value) in &self.defines {
+                let _ = writeln!(sfz, "#define ${} {}", name, value);
+            }
+        }
+        let mut writer = SfzWriter {
+            options,
+            format,
+            written_includes: vec![false; self.includes.len()],
+        };
+        if self.default_path != self.root {
+            let path = self
+                .default_path
+                .strip_prefix(&self.root)
+                .unwrap_or(&self.default_path);
+            let mut control = OpcodeMap::new();
+            control.insert("default_path".into(), Opcode::default_path(path.join("")));
+            writer.write_block(&mut sfz, Header::Control, &control);
+        }
+        if !self.global.is_empty() {
+            writer.write_block(&mut sfz, Header::Global, &self.global);
+        }
+        self.write_sfz_master(&mut sfz, &mut writer, None);
+        for (m, master) in self.masters.iter().enumerate() {
+            writer.write_block(&mut sfz, Header::Master, &master.opcodes);
+            self.write_sfz_master(&mut sfz, &mut writer, Some(m));
+        }
+        for effect in &self.effects {
+            writer.write_block(&mut sfz, Header::Effect, &effect.opcodes);
+        }
+        for curve in &self.curves {
+            let opcodes = curve.opcodes().into_iter().map(|o| (o.full_name(), o));
+            writer.write_block(&mut sfz, Header::Curve, &opcodes.collect());
+        }
+        sfz
+    }
+
+    /// Writes the regions without a group and then each group followed by
+    /// its regions, of the provided master
+    fn write_sfz_master(&self, sfz: &mut String, writer: &mut SfzWriter, master: Option<usize>) {
+        for region in self
+            .regions
+            .iter()
+            .filter(|r| r.master() == master && r.group().is_none())
+        {
+            if !self.write_sfz_include(sfz, writer, region.include) {
+                writer.write_block(sfz, Header::Region, &region.opcodes);
+            }
+        }
+        for (g, group) in self.groups.iter().enumerate() {
+            if group.master != master {
+                continue;
+            }
+            if !self.write_sfz_include(sfz, writer, group.include) {
+                writer.write_block(sfz, Header::Group, &group.opcodes);
+            }
+            for region in self.regions.iter().filter(|r| r.group() == Some(g)) {
+                if !self.write_sfz_include(sfz, writer, region.include) {
+                    writer.write_block(sfz, Header::Region, &region.opcodes);
+                }
+            }
+        }
+    }
+
+    /// Writes the `#include` directive of a block the first time it's found,
+    /// returning whether the block comes from an include that is not inlined
+    fn write_sfz_include(
+        &self,
+        sfz: &mut String,
+        writer: &mut SfzWriter,
+        include: Option<usize>,
+    ) -> bool {
+        match include {
+            Some(i) if !writer.options.inline_includes => {
+                if !writer.written_includes[i] {
+                    writer.written_includes[i] = true;
+                    writer.write_separator(sfz);
+                    let path = self.includes[i].to_string_lossy().replace('\\', "/");
+                    let _ = writeln!(sfz, "#include \"{}\"", path);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Exports the regions as CSV, one row per region
+    ///
+    /// The values are resolved taking inheritance and defaults into account.
+    /// The first row contains the names of the columns.
+    ///
+    pub fn to_csv(&self) -> String {
+        let mut csv =
+            String::from("sample,lokey,hikey,lovel,hivel,pitch_keycenter,volume,group_label\n");
+        for region in &self.regions {
+            let sample = match region.inherited_opcode("sample", self) {
+                Some(Opcode::sample(path)) => path.display().to_string(),
+                _ => String::new(),
+            };
+            let keys = region.key_range(self);
+            let lovel = region
+                .inherited_value(self, |o| match o {
+                    Opcode::lovel(v) => Some(*v),
+                    _ => None,
+                })
+                .unwrap_or(0);
+            let hivel = region
+                .inherited_value(self, |o| match o {
+                    Opcode::hivel(v) => Some(*v),
+                    _ => None,
+                })
+                .unwrap_or(127);
+            let keycenter = region
+                .inherited_value(self, |o| match o {
+                    Opcode::pitch_keycenter(v) | Opcode::key(v) => Some(*v),
+                    _ => None,
+                })
+                .unwrap_or(60);
+            let volume = region
+                .inherited_value(self, |o| match o {
+                    Opcode::volume(v) => Some(*v),
+                    _ => None,
+                })
+                .unwrap_or(0.);
+            let group_label = match region.inherited_opcode("group_label", self) {
+                Some(Opcode::group_label(label)) => label.as_str(),
+                _ => "",
+            };
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{}",
+                csv_field(&sample),
+                keys.start(),
+                keys.end(),
+                lovel,
+                hivel,
+                keycenter,
+                volume,
+                csv_field(group_label),
+            );
+        }
+        csv
+    }
+}
+
+/// Quotes a CSV field if it contains commas, quotes or line breaks
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// How to write the directives with [`Instrument::to_sfz_with`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SfzWriteOptions {
+    /// Whether to write the content of the included files in place of
+    /// their `#include` directives. Defaults to true.
+    pub inline_includes: bool,
+
+    /// Whether to write the `#define` directives at the beginning.
+    /// Defaults to false.
+    pub preserve_defines: bool,
+}
+
+impl Default for SfzWriteOptions {
+    fn default() -> Self {
+        Self {
+            inline_includes: true,
+            preserve_defines: false,
+        }
+    }
+}
+
+/// How to format the code with [`Instrument::to_sfz_pretty`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// The number of spaces before each opcode. Defaults to 0.
+    pub indent: usize,
+
+    /// Whether to write an empty line between blocks. Defaults to true.
+    pub blank_lines: bool,
+
+    /// Whether to align the `=` signs of the opcodes of each block.
+    /// Defaults to false.
+    ///
+    /// Since no spaces are allowed around the sign, the shorter
+    /// opcode names are padded on their left.
+    pub align_equals: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent: 0,
+            blank_lines: true,
+            align_equals: false,
+        }
+    }
+}
+
+/// The state of the serialization of the instrument
+struct SfzWriter {
+    options: SfzWriteOptions,
+    format: FormatOptions,
+    // whether the directive of each include has already been written
+    written_includes: Vec<bool>,
+}
+
+impl SfzWriter {
+    /// Writes a header followed by its opcodes, one per line
+    fn write_block(&self, sfz: &mut String, header: Header, opcodes: &OpcodeMap) {
+        self.write_separator(sfz);
+        // writing into a String can't fail
+        let _ = writeln!(sfz, "{}", header);
+
+        let width = if self.format.align_equals {
+            opcodes.keys().map(|name| name.len()).max().unwrap_or(0)
+        } else {
+            0
+        };
+        for opcode in opcodes.values() {
+            let code = opcode.to_string();
+            let name_len = code.find('=').unwrap_or(0);
+            let padding = self.format.indent + width.saturating_sub(name_len);
+            let _ = writeln!(sfz, "{:padding$}{}", "", code);
+        }
+    }
+
+    /// Separates a block from the previous one
+    fn write_separator(&self, sfz: &mut String) {
+        if self.format.blank_lines && !sfz.is_empty() {
+            sfz.push('\n');
+        }
+    }
 }
 
 /// The current status of the parsing of the instrument
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 struct InstrumentParsingStatus {
+    // whether a control header has appeared
+    has_control: bool,
     is_header_control: bool,
     is_header_global: bool,
+    is_header_master: bool,
+    is_header_effect: bool,
+    is_header_curve: bool,
+    // whether the opcodes of the current header are ignored
+    is_header_ignored: bool,
+    // counts masters (first one is 0, valid as index)
+    master_counter: Option<usize>,
     // counts groups (first one is 0, valid as index)
     group_counter: Option<usize>,
+    // whether the last group belongs to the current master
+    is_group_in_current_master: bool,
     // counts regions inside last group (first one is 0, valid as index)
     region_counter_in_group: Option<usize>,
     // counts all regions
@@ -347,72 +1218,173 @@ struct InstrumentParsingStatus {
 impl InstrumentParsingStatus {
     pub fn init() -> Self {
         Self {
+            has_control: false,
             is_header_control: false,
             is_header_global: false,
+            is_header_master: false,
+            is_header_effect: false,
+            is_header_curve: false,
+            is_header_ignored: false,
+            master_counter: None,
             group_counter: None,
+            is_group_in_current_master: false,
             region_counter_in_group: None,
             region_counter: None,
         }
     }
 
+    /// A new master header appears
+    ///
+    /// The following opcodes belong to it until the next group or region,
+    /// and the following groups and regions belong to it.
+    pub fn new_master(&mut self) {
+        debug!("  status.new_master()");
+        self.is_header_ignored = false;
+        self.is_header_control = false;
+        self.is_header_global = false;
+        self.is_header_effect = false;
+        self.is_header_curve = false;
+        // enter the <master> header
+        self.is_header_master = true;
+        // ensure the following regions don't belong to the previous group
+        self.is_group_in_current_master = false;
+        self.region_reset_in_current_group();
+        match self.master_counter {
+            Some(c) => self.master_counter = Some(c + 1),
+            None => self.master_counter = Some(0),
+        }
+    }
+
     /// A new group header appears
     ///
     pub fn new_group(&mut self) {
         debug!("  status.new_group()");
+        self.is_header_ignored = false;
         // ensure we are out of the <control> header
         self.is_header_control = false;
-        // ensure we are out of the <global> header
+        // ensure we are out of the <global> and <master> headers
         self.is_header_global = false;
+        self.is_header_master = false;
+        // ensure we are out of the <effect> and <curve> headers
+        self.is_header_effect = false;
+        self.is_header_curve = false;
         // ensure we reset the region counter for the current group
         self.region_reset_in_current_group();
         // increment the group counter
         self.group_increment();
+        self.is_group_in_current_master = true;
     }
 
     /// A new region header appears
     ///
     pub fn new_region(&mut self) {
         debug!("  status.new_region()");
+        self.is_header_ignored = false;
         // ensure we are out of the <control> header
         self.is_header_control = false;
-        // ensure we are out of the <global> header
+        // ensure we are out of the <global> and <master> headers
         self.is_header_global = false;
+        self.is_header_master = false;
+        // ensure we are out of the <effect> and <curve> headers
+        self.is_header_effect = false;
+        self.is_header_curve = false;
         // increment the region counter for the current group
         self.region_increment();
     }
 
     /// A new control header appears
     ///
-    /// There can only be one, and must appear
+    /// There should only be one, and appear
     /// before the first global, group & region headers.
     ///
-    // TODO: if incorrectly placed, following opcodes should be ignored
-    pub fn new_control(&mut self) {
-        if !self.is_header_control
+    /// Returns `false` if it's a repeated one or it's incorrectly placed.
+    /// Its opcodes are still read, and its `default_path` overrides the previous one,
+    /// unless parsing with [`Instrument::from_sfz_strict`].
+    pub fn new_control(&mut self) -> bool {
+        let is_placed = !self.has_control
             && !self.is_header_global
-            && self.group_counter == None
-            && self.region_counter == None
-        {
-            // enter the <control> header
-            self.is_header_control = true;
-        }
+            && self.master_counter.is_none()
+            && self.group_counter.is_none()
+            && self.region_counter.is_none();
+
+        // enter the <control> header
+        self.is_header_ignored = false;
+        self.has_control = true;
+        self.is_header_control = true;
+        self.is_header_global = false;
+        self.is_header_master = false;
+        self.is_header_effect = false;
+        self.is_header_curve = false;
+        is_placed
     }
 
     /// A new global header appears
     ///
     /// There can only be one, and must appear
-    /// before the first group & region headers.
+    /// before the first master, group & region headers.
+    ///
+    /// Returns `false` if it's incorrectly placed,
+    /// and then it's ignored along with its opcodes.
     ///
-    // TODO: if incorrectly placed, following opcodes should be ignored
-    pub fn new_global(&mut self) {
-        if !self.is_header_global && self.group_counter == None && self.region_counter == None {
-            // ensure we are out of the <control> header
+    pub fn new_global(&mut self) -> bool {
+        if !self.is_header_global
+            && self.master_counter.is_none()
+            && self.group_counter.is_none()
+            && self.region_counter.is_none()
+        {
+            // ensure we are out of the <control>, <effect> and <curve> headers
             self.is_header_control = false;
+            self.is_header_effect = false;
+            self.is_header_curve = false;
+            self.is_header_ignored = false;
             // enter the <global> header
             self.is_header_global = true;
+            true
+        } else {
+            self.ignore_header();
+            false
         }
     }
 
+    /// A header to ignore appears
+    ///
+    /// The following opcodes are ignored until the next header.
+    pub fn ignore_header(&mut self) {
+        debug!("  status.ignore_header()");
+        self.is_header_control = false;
+        self.is_header_global = false;
+        self.is_header_master = false;
+        self.is_header_effect = false;
+        self.is_header_curve = false;
+        self.is_header_ignored = true;
+    }
+
+    /// A new effect header appears
+    ///
+    /// The following opcodes belong to it until the next header.
+    pub fn new_effect(&mut self) {
+        debug!("  status.new_effect()");
+        self.is_header_ignored = false;
+        self.is_header_control = false;
+        self.is_header_global = false;
+        self.is_header_master = false;
+        self.is_header_effect = true;
+        self.is_header_curve = false;
+    }
+
+    /// A new curve header appears
+    ///
+    /// The following opcodes belong to it until the next header.
+    pub fn new_curve(&mut self) {
+        debug!("  status.new_curve()");
+        self.is_header_ignored = false;
+        self.is_header_control = false;
+        self.is_header_global = false;
+        self.is_header_master = false;
+        self.is_header_effect = false;
+        self.is_header_curve = true;
+    }
+
     /// Increments the region counter
     fn region_increment(&mut self) {
         match self.region_counter {
@@ -424,10 +1396,8 @@ impl InstrumentParsingStatus {
             None => self.region_counter_in_group = Some(0),
         }
         debug!(
-            "  status.region_increment() g{}→rig{} (r{})",
-            self.group_counter.unwrap(),
-            self.region_counter_in_group.unwrap(),
-            self.region_counter.unwrap()
+            "  status.region_increment() g{:?}→rig{:?} (r{:?})",
+            self.group_counter, self.region_counter_in_group, self.region_counter
         );
     }
 
@@ -443,25 +1413,835 @@ impl InstrumentParsingStatus {
             Some(c) => self.group_counter = Some(c + 1),
             None => self.group_counter = Some(0),
         }
-        debug!(
-            "  status.group_increment() (→g{})",
-            self.group_counter.unwrap()
-        );
+        debug!("  status.group_increment() (→g{:?})", self.group_counter);
     }
 
-    /// Are there any groups already defined?
-    pub fn are_groups(&self) -> bool {
-        if self.group_counter == None {
-            return false;
-        }
-        true
+    /// The index of the current group, if the last group hasn't been
+    /// closed by a following master header
+    pub fn current_group(&self) -> Option<usize> {
+        self.group_counter
+            .filter(|_| self.is_group_in_current_master)
     }
 
     /// Are there any regions already defined for the current group?
     pub fn are_regions_in_current_group(&self) -> bool {
-        if self.region_counter_in_group == None {
+        if self.region_counter_in_group.is_none() {
             return false;
         }
         true
     }
 }
+
+#[cfg(test)]
+mod tests_instrument {
+    use super::*;
+
+    #[test]
+    fn test_instrument_matching_regions() {
+        let i = Instrument::from_sfz(
+            "<group> lokey=40 hikey=60
+                <region> sample=soft.wav hivel=63
+                <region> sample=loud.wav lovel=64
+            <group> hichan=2
+                <region> sample=pad.wav lokey=50 hikey=80
+                <region> sample=all.wav",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.matching_regions(45, 30, 1), [0, 3]);
+        assert_eq!(i.matching_regions(55, 100, 1), [1, 2, 3]);
+        assert_eq!(i.matching_regions(55, 100, 3), [1]);
+        assert_eq!(i.matching_regions(70, 64, 2), [2, 3]);
+        assert_eq!(i.matching_regions(20, 0, 2), [3]);
+        assert!(i.matching_regions(90, 127, 16).is_empty());
+    }
+
+    #[test]
+    fn test_instrument_regions_without_keycenter() {
+        let i = Instrument::from_sfz(
+            "<region> sample=Horn_A#1.wav pitch_keycenter=34
+            <region> sample=Horn_C3.wav
+            <group> key=50
+            <region> sample=Horn_D3.wav",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.regions_without_keycenter(), vec![1]);
+        assert_eq!(i.regions[1].infer_keycenter_from_filename(), Some(48));
+    }
+
+    #[test]
+    fn test_instrument_sample_path() {
+        let mut i = Instrument::from_sfz(
+            r"<region> sample=a.wav
+            <group> sample=samples\b.wav
+            <region> lokey=60
+            <region>",
+            Path::new(""),
+        )
+        .unwrap();
+        i.regions[2].opcodes.clear();
+        i.regions[2].set_group(None);
+        assert_eq!(i.sample_path(0), Some(PathBuf::from("a.wav")));
+        assert_eq!(i.sample_path(1), Some(fix_path_separators("samples/b.wav")));
+        assert_eq!(i.sample_path(2), None);
+        assert_eq!(i.sample_path(3), None);
+
+        let i = Instrument::from_sfz(
+            "<control> default_path=samples <region> sample=a.wav",
+            Path::new("/lib"),
+        )
+        .unwrap();
+        assert_eq!(
+            i.sample_path(0),
+            Some(fix_path_separators("/lib/samples/a.wav"))
+        );
+    }
+
+    #[test]
+    fn test_instrument_sample_count() {
+        let i = Instrument::from_sfz(
+            "<region> sample=a.wav lokey=60 hikey=61
+            <region> sample=a.wav lokey=62 hikey=63
+            <region> sample=b.wav
+            <region> lokey=64",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.sample_count(), (3, 2));
+        assert_eq!(Instrument::new().sample_count(), (0, 0));
+    }
+
+    #[test]
+    fn test_instrument_new_with_path() {
+        let i: Instrument = Default::default();
+        assert_eq!(i.default_path, PathBuf::new());
+
+        let mut i = Instrument::new_with_path("/lib/samples");
+        assert_eq!(i.default_path, Path::new("/lib/samples"));
+        i.add_region(Region::new());
+        i.add_opcode(&Opcode::sample(PathBuf::from("a.wav")))
+            .unwrap();
+        assert_eq!(i.sample_path(0), Some(PathBuf::from("/lib/samples/a.wav")));
+    }
+
+    #[test]
+    fn test_instrument_add_region() {
+        let mut group = Group::new();
+        group.add_opcode(&Opcode::volume(-6.));
+        let mut region = Region::with_group(0);
+        region.add_opcode(&Opcode::sample("a.wav".into()));
+        region.add_opcode(&Opcode::key(60));
+
+        let mut i = Instrument::new();
+        i.add_group(group);
+        i.add_region(region.clone());
+        assert_eq!(i.groups(), 1);
+        assert_eq!(i.regions, [region]);
+        assert!(i.validate().is_empty());
+
+        // the opcodes added afterwards go to the last header added
+        i.add_opcode(&Opcode::tune(10)).unwrap();
+        assert_eq!(i.regions[0].opcodes["tune"], Opcode::tune(10));
+        assert_eq!(
+            i.resolved_opcodes(0).unwrap()["volume"],
+            Opcode::volume(-6.)
+        );
+    }
+
+    #[test]
+    fn test_instrument_from_str() {
+        let sfz = "<region> sample=a.wav key=60 <region> sample=b.wav key=62";
+        let i: Instrument = sfz.parse().unwrap();
+        assert_eq!(i.regions(), 2);
+        assert_eq!(Instrument::try_from(sfz).unwrap(), i);
+        assert!("<region> cutoff=abc".parse::<Instrument>().is_err());
+    }
+
+    #[test]
+    fn test_instrument_defines() {
+        let i = Instrument::from_sfz(
+            "#define $LO 60
+            #define $HI 62
+            #define $HI 64
+            <region> sample=a.wav lokey=$LO hikey=$HI
+            <region> sample=b.wav key=$LO",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.regions[0].key_range(&i), 60..=64);
+        assert_eq!(i.regions[1].key_range(&i), 60..=60);
+
+        // the undefined variables are left as written
+        let sfz = "<region> sample=a$b.wav /* $5 */ lokey=$LO\n#define $LO 60";
+        let (i, warnings) = Instrument::from_sfz_verbose(sfz, Path::new("")).unwrap();
+        assert_eq!(i.sample_path(0), Some(PathBuf::from("a$b.wav")));
+        let warnings: Vec<String> = warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            warnings,
+            [
+                "line 1: undefined variable `$b`",
+                "line 1: undefined variable `$5`",
+                "line 1: undefined variable `$LO`",
+            ]
+        );
+        let result = Instrument::from_sfz_strict(sfz, Path::new(""));
+        assert!(matches!(result, Err(Error::UndefinedVariable(name)) if name == "b"));
+    }
+
+    #[test]
+    fn test_instrument_masters() {
+        let i = Instrument::from_sfz(
+            "<global> volume=-6 cutoff=100
+            <region> sample=a.wav
+            <master> master_label=Mics cutoff=200 pan=10
+            <group> pan=20
+            <region> sample=b.wav
+            <group>
+            <region> sample=c.wav cutoff=300
+            <master> volume=-1
+            <region> sample=d.wav",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.masters(), 2);
+        assert_eq!(i.groups(), 2);
+        assert_eq!(
+            i.masters[0].opcodes["master_label"],
+            Opcode::master_label("Mics".into())
+        );
+        assert_eq!(i.groups[0].master, Some(0));
+        assert_eq!(i.groups[1].master, Some(0));
+
+        let regions: Vec<_> = i.regions.iter().map(|r| (r.master(), r.group())).collect();
+        assert_eq!(
+            regions,
+            [
+                (None, None),
+                (Some(0), Some(0)),
+                (Some(0), Some(1)),
+                (Some(1), None)
+            ]
+        );
+
+        // global → master → group → region
+        let cutoff = |r: usize| i.regions[r].inherited_opcode("cutoff", &i).cloned();
+        let pan = |r: usize| i.regions[r].inherited_opcode("pan", &i).cloned();
+        let volume = |r: usize| i.regions[r].inherited_opcode("volume", &i).cloned();
+        assert_eq!(cutoff(0), Some(Opcode::cutoff(100.)));
+        assert_eq!(cutoff(1), Some(Opcode::cutoff(200.)));
+        assert_eq!(pan(1), Some(Opcode::pan(20.)));
+        assert_eq!(pan(2), Some(Opcode::pan(10.)));
+        assert_eq!(cutoff(2), Some(Opcode::cutoff(300.)));
+        assert_eq!(volume(2), Some(Opcode::volume(-6.)));
+        assert_eq!(volume(3), Some(Opcode::volume(-1.)));
+        assert_eq!(pan(3), None);
+
+        let reparsed = Instrument::from_sfz(&i.to_sfz(), Path::new("")).unwrap();
+        assert_eq!(reparsed.masters, i.masters);
+        assert_eq!(reparsed.groups, i.groups);
+        assert_eq!(reparsed.regions, i.regions);
+    }
+
+    #[test]
+    fn test_instrument_resolved_opcodes() {
+        let i = Instrument::from_sfz(
+            "<global> volume=-12 cutoff=100
+            <group> volume=-6 pan=10
+            <region> sample=a.wav volume=-3
+            <region> sample=b.wav",
+            Path::new(""),
+        )
+        .unwrap();
+
+        let resolved = i.resolved_opcodes(0).unwrap();
+        assert_eq!(resolved["volume"], Opcode::volume(-3.));
+        assert_eq!(resolved["cutoff"], Opcode::cutoff(100.));
+        assert_eq!(resolved["pan"], Opcode::pan(10.));
+        assert_eq!(resolved.len(), 4);
+
+        assert_eq!(
+            i.resolved_opcodes(1).unwrap()["volume"],
+            Opcode::volume(-6.)
+        );
+        assert!(matches!(i.resolved_opcodes(2), Err(Error::OutOfBounds(_))));
+    }
+
+    #[test]
+    fn test_instrument_regions_for_keyswitch() {
+        let i = Instrument::from_sfz(
+            "<global> sw_lokey=24 sw_hikey=26
+            <group> sw_last=24 sw_label=Sustain
+            <region> sample=sus_c.wav key=60
+            <region> sample=sus_d.wav key=62
+            <group> sw_last=25 sw_label=Staccato
+            <region> sample=stac_c.wav key=60
+            <region> sample=stac_d.wav key=62",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.regions_for_keyswitch(24), [0, 1]);
+        assert_eq!(i.regions_for_keyswitch(25), [2, 3]);
+        assert!(i.regions_for_keyswitch(26).is_empty());
+        // outside of the keyswitch range
+        assert!(i.regions_for_keyswitch(60).is_empty());
+
+        let i = Instrument::from_sfz(
+            "<region> sample=a.wav sw_lolast=30 sw_hilast=32",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.regions_for_keyswitch(31), [0]);
+        assert!(i.regions_for_keyswitch(33).is_empty());
+    }
+
+    #[test]
+    fn test_instrument_parametric_opcodes() {
+        let i = Instrument::from_sfz(
+            "<region> sample=a.wav locc1=10 locc2=20 hicc1=100 locc1=15",
+            Path::new(""),
+        )
+        .unwrap();
+        let opcodes = &i.regions[0].opcodes;
+        let names: Vec<&String> = opcodes.keys().collect();
+        assert_eq!(names, ["sample", "locc1", "locc2", "hicc1"]);
+        assert_eq!(opcodes["locc1"], Opcode::loccN(1, 15));
+        assert_eq!(opcodes["locc2"], Opcode::loccN(2, 20));
+        assert_eq!(opcodes["locc2"].params(), [2]);
+        assert_eq!(opcodes["hicc1"], Opcode::hiccN(1, 100));
+    }
+
+    #[test]
+    fn test_instrument_opcode_names() {
+        // the instrument of the create example
+        let mut i = Instrument::new();
+        i.add_opcode(&Opcode::fil_type(crate::fil_type::lpf_2p))
+            .unwrap();
+        i.new_region();
+        i.add_opcode(&Opcode::cutoff(500.0)).unwrap();
+        i.add_opcode(&Opcode::sample(PathBuf::from("sound1.wav")))
+            .unwrap();
+        i.new_group();
+        i.add_opcode(&Opcode::resonance(10.0)).unwrap();
+        i.new_region();
+        i.add_opcode(&Opcode::sample(PathBuf::from("sound2.wav")))
+            .unwrap();
+        i.new_group();
+        i.new_region();
+        i.add_opcode_global(&Opcode::delay(20.));
+        i.add_opcode_to_region(&Opcode::offset(400), 1).unwrap();
+        i.add_opcode_to_group(&Opcode::key(50), 0).unwrap();
+
+        assert_eq!(
+            i.opcode_names().into_iter().collect::<Vec<_>>(),
+            [
+                "cutoff",
+                "delay",
+                "fil_type",
+                "key",
+                "offset",
+                "resonance",
+                "sample"
+            ]
+        );
+
+        i.add_opcode_to_region(&Opcode::loccN(1, 10), 2).unwrap();
+        i.add_opcode_to_region(&Opcode::loccN(2, 10), 2).unwrap();
+        assert!(i.opcode_names().contains("loccN"));
+        assert_eq!(i.opcode_names().len(), 8);
+    }
+
+    #[test]
+    fn test_instrument_malformed_value() {
+        let result = Instrument::from_sfz("<region> sample=a.wav cutoff=abc", Path::new(""));
+        assert!(matches!(
+            result,
+            Err(Error::Parse { line: 1, col: 23, message, .. })
+                if message == "`abc` is not a valid value for `cutoff`"
+        ));
+
+        // the parametric opcodes keep their parameters in the error
+        let result = Instrument::from_sfz("<region> locc64=c4", Path::new(""));
+        assert!(matches!(
+            result,
+            Err(Error::Parse { message, .. }) if message.contains("`locc64`")
+        ));
+
+        // out of range values and unknown opcodes are still ignored
+        let i = Instrument::from_sfz(
+            "<region> sample=a.wav cutoff=-100 unknown_opcode=1",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.regions[0].opcodes.len(), 1);
+    }
+
+    #[test]
+    fn test_instrument_parse_error_position() {
+        let result = Instrument::from_sfz_strict(
+            "<group> lokey=60\n<region> sample=a.wav\n  <foo> sample=b.wav",
+            Path::new(""),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::Parse { line: 3, col: 3, message, .. }) if message == "unknown header `<foo>`"
+        ));
+
+        let result = Instrument::from_sfz(
+            "<region> sample=a.wav\n\n<region> sample=b.wav pan=left",
+            Path::new(""),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::Parse {
+                line: 3,
+                col: 23,
+                ..
+            })
+        ));
+
+        // the column is the one before substituting the variables
+        let result = Instrument::from_sfz_strict(
+            "#define $CUTOFF 1000\n<region> cutoff=$CUTOFF <foo>",
+            Path::new(""),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::Parse {
+                line: 2,
+                col: 25,
+                include: None,
+                ..
+            })
+        ));
+
+        // the errors in an included file are located in that file
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+        let result = Instrument::from_sfz_strict(
+            "#define $KEY 60\n<group> lokey=1\n#include \"directives_regions.sfz\"\n<foo>",
+            &fixtures,
+        );
+        assert!(matches!(
+            result,
+            Err(Error::Parse {
+                line: 4,
+                include: None,
+                ..
+            })
+        ));
+        let result = Instrument::from_sfz(
+            "#define $KEY c\n#include \"directives_regions.sfz\"",
+            &fixtures,
+        );
+        assert!(matches!(
+            result,
+            Err(Error::Parse { line: 4, col: 1, include: Some(path), .. })
+                if path == Path::new("directives_regions.sfz")
+        ));
+
+        // the unknown headers are skipped with their opcodes when not strict
+        let (i, warnings) = Instrument::from_sfz_verbose(
+            "<region> sample=a.wav\n<foo> sample=b.wav volume=1\n<region> sample=c.wav",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.regions(), 2);
+        assert_eq!(i.regions[0].opcodes.len(), 1);
+        assert_eq!(
+            warnings.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ["line 2: unknown header `<foo>`"]
+        );
+    }
+
+    #[test]
+    fn test_instrument_from_sfz_verbose() {
+        let (i, warnings) = Instrument::from_sfz_verbose(
+            "<region> sample=a.wav
+            unknown_opcode=1 pan=-200",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.regions[0].opcodes.len(), 1);
+        assert_eq!(
+            warnings,
+            vec![
+                Warning {
+                    line: 2,
+                    opcode: "unknown_opcode".into(),
+                    value: "1".into(),
+                    kind: WarningKind::UnknownOpcode,
+                },
+                Warning {
+                    line: 2,
+                    opcode: "pan".into(),
+                    value: "-200".into(),
+                    kind: WarningKind::OutOfRange,
+                },
+            ]
+        );
+
+        // a <global> header after a <group> is ignored
+        let (_, warnings) =
+            Instrument::from_sfz_verbose("<group> <global> <region>", Path::new("")).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, WarningKind::MisplacedHeader);
+        assert_eq!(
+            warnings[0].to_string(),
+            "line 1: misplaced header `<global>`"
+        );
+
+        // and so are its opcodes, until the next header
+        let (i, _) = Instrument::from_sfz_verbose(
+            "<region> sample=a.wav <global> volume=-6 <region> sample=b.wav pan=10",
+            Path::new(""),
+        )
+        .unwrap();
+        assert!(i.global.is_empty());
+        assert_eq!(i.regions[0].opcodes.len(), 1);
+        assert_eq!(i.regions[1].opcodes.len(), 2);
+
+        // the opcodes before any header are ignored
+        let (i, warnings) =
+            Instrument::from_sfz_verbose("cutoff=500\n<region> sample=a.wav", Path::new(""))
+                .unwrap();
+        assert!(i.global.is_empty());
+        assert_eq!(i.regions[0].opcodes.len(), 1);
+        assert_eq!(
+            warnings,
+            [Warning {
+                line: 1,
+                opcode: "cutoff".into(),
+                value: "500".into(),
+                kind: WarningKind::MisplacedOpcode,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_instrument_default_path_replaced() {
+        let i = Instrument::from_sfz(
+            "<control> default_path=samples/ default_path=other/
+            <region> sample=a.wav",
+            Path::new("root"),
+        )
+        .unwrap();
+        assert_eq!(i.default_path, Path::new("root").join("other/"));
+    }
+
+    #[test]
+    fn test_instrument_to_sfz_control() {
+        let i = Instrument::from_sfz(
+            r"<control> default_path=samples\piano\ <region> sample=a.wav",
+            Path::new("root"),
+        )
+        .unwrap();
+        let sfz = i.to_sfz();
+        assert!(sfz.starts_with("<control>\ndefault_path=samples/piano/\n"));
+        let reparsed = Instrument::from_sfz(&sfz, Path::new("root")).unwrap();
+        assert_eq!(reparsed.default_path, i.default_path);
+        assert_eq!(reparsed.sample_path(0), i.sample_path(0));
+
+        // no <control> when the samples are in the root
+        let i = Instrument::from_sfz("<region> sample=a.wav", Path::new("root")).unwrap();
+        assert_eq!(i.to_sfz(), "<region>\nsample=a.wav\n");
+    }
+
+    #[test]
+    fn test_instrument_repeated_control() {
+        let (i, warnings) = Instrument::from_sfz_verbose(
+            "<control> default_path=first
+            <control> default_path=second
+            <region> sample=a.wav",
+            Path::new("root"),
+        )
+        .unwrap();
+        assert_eq!(i.default_path, Path::new("root/second"));
+        assert_eq!(i.regions[0].opcodes.len(), 1);
+        assert_eq!(
+            warnings,
+            [Warning {
+                line: 2,
+                opcode: "<control>".into(),
+                value: "".into(),
+                kind: WarningKind::MisplacedHeader,
+            }]
+        );
+
+        // the opcodes of a <control> after a region don't go to the region
+        let (i, warnings) = Instrument::from_sfz_verbose(
+            "<region> sample=a.wav <control> default_path=other",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.default_path, Path::new("other"));
+        assert_eq!(i.regions[0].opcodes.len(), 1);
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_instrument_strict_control() {
+        let message = "repeated or misplaced `<control>` header";
+        let result = Instrument::from_sfz_strict(
+            "<control> default_path=first\n<control> default_path=second",
+            Path::new(""),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::Parse { line: 2, col: 1, message: m, .. }) if m == message
+        ));
+
+        let result = Instrument::from_sfz_strict(
+            "<region> sample=a.wav\n  <control> default_path=other",
+            Path::new(""),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::Parse {
+                line: 2,
+                col: 3,
+                ..
+            })
+        ));
+
+        // the lenient parser accepts the same code
+        assert!(Instrument::from_sfz(
+            "<control> default_path=first\n<control> default_path=second",
+            Path::new(""),
+        )
+        .is_ok());
+
+        let i = Instrument::from_sfz_strict(
+            "<control> default_path=samples\n<global> volume=-3\n<region> sample=a.wav",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.default_path, Path::new("samples"));
+    }
+
+    #[test]
+    fn test_instrument_strict_region_groups() {
+        let i = Instrument::from_sfz_strict(
+            "<region> sample=a.wav\n<group> lokey=60\n<region> sample=b.wav",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.regions[0].group(), None);
+        assert_eq!(i.regions[1].group(), Some(0));
+        assert!(i.validate_region_groups().is_empty());
+    }
+
+    #[test]
+    fn test_instrument_regions_in_group() {
+        let i = Instrument::from_sfz(
+            "<region> sample=a.wav
+            <group> lovel=1
+            <region> sample=b.wav
+            <region> sample=c.wav
+            <group> lovel=2
+            <region> sample=d.wav",
+            Path::new(""),
+        )
+        .unwrap();
+        let samples = |regions: Vec<&Region>| -> Vec<String> {
+            regions
+                .iter()
+                .map(|r| r.opcodes["sample"].to_string())
+                .collect()
+        };
+        assert_eq!(
+            samples(i.regions_in_group(0).collect()),
+            ["sample=b.wav", "sample=c.wav"]
+        );
+        assert_eq!(samples(i.regions_in_group(1).collect()), ["sample=d.wav"]);
+        assert_eq!(
+            samples(i.regions_without_group().collect()),
+            ["sample=a.wav"]
+        );
+        assert_eq!(i.regions_in_group(2).count(), 0);
+        assert_eq!(i.regions_in(0).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_instrument_round_robin() {
+        let i = Instrument::from_sfz(
+            "<region> sample=a.wav seq_length=8 seq_position=3",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.regions[0].opcodes["seq_length"], Opcode::seq_length(8));
+        assert_eq!(
+            i.regions[0].opcodes["seq_position"],
+            Opcode::seq_position(3)
+        );
+    }
+
+    #[test]
+    fn test_instrument_for_each_opcode_mut() {
+        let mut i = Instrument::from_sfz(
+            "<global> cutoff=100
+            <group> cutoff=200 volume=1
+            <region> sample=a.wav cutoff=300",
+            Path::new(""),
+        )
+        .unwrap();
+        i.for_each_opcode_mut(|o| {
+            if let Opcode::cutoff(c) = o {
+                *c *= 2.;
+            }
+        });
+        assert_eq!(i.global["cutoff"], Opcode::cutoff(200.));
+        assert_eq!(i.groups[0].opcodes["cutoff"], Opcode::cutoff(400.));
+        assert_eq!(i.regions[0].opcodes["cutoff"], Opcode::cutoff(600.));
+
+        // changing an opcode into another one updates its name
+        i.for_each_opcode_mut(|o| {
+            if let Opcode::volume(v) = o {
+                *o = Opcode::amplitude(*v);
+            }
+        });
+        let names: Vec<&String> = i.groups[0].opcodes.keys().collect();
+        assert_eq!(names, ["cutoff", "amplitude"]);
+    }
+
+    #[test]
+    fn test_instrument_header_level_opcodes() {
+        let sfz = "<global>\nglobal_label=Piano\nglobal_volume=-6\n\n\
+            <master>\nmaster_amplitude=50\n\n\
+            <group>\ngroup_volume=-3\ngroup_amplitude=80\n\n\
+            <region>\nregion_label=Soft\nsample=a.wav\n";
+        let i = Instrument::from_sfz(sfz, Path::new("")).unwrap();
+        assert_eq!(
+            i.global.get("global_volume"),
+            Some(&Opcode::global_volume(-6.))
+        );
+        assert_eq!(
+            i.masters[0].opcodes.get("master_amplitude"),
+            Some(&Opcode::master_amplitude(50.))
+        );
+        assert_eq!(
+            i.groups[0].opcodes.get("group_volume"),
+            Some(&Opcode::group_volume(-3.))
+        );
+        assert!(i.regions[0].opcodes.get("group_volume").is_none());
+        assert!(i.validate().is_empty());
+        assert_eq!(i.to_sfz(), sfz);
+    }
+
+    #[test]
+    fn test_instrument_rebase_samples() {
+        let mut i = Instrument::from_sfz(
+            "<group> sample=samples/a.wav
+            <region> sample=samples/b.wav
+            <region> sample=other/c.wav
+            <region> sample=/lib/samples/d.wav",
+            Path::new("/lib"),
+        )
+        .unwrap();
+        i.rebase_samples(Path::new("samples"), Path::new("audio/piano"));
+        i.rebase_samples(Path::new("/lib"), Path::new("/new/lib"));
+
+        assert_eq!(i.default_path, Path::new("/new/lib"));
+        assert_eq!(
+            i.to_sfz(),
+            "<group>\nsample=audio/piano/a.wav\n\n\
+            <region>\nsample=audio/piano/b.wav\n\n\
+            <region>\nsample=other/c.wav\n\n\
+            <region>\nsample=/new/lib/samples/d.wav\n"
+        );
+    }
+
+    #[test]
+    fn test_instrument_curve_points() {
+        let i = Instrument::from_sfz(
+            "<curve> curve_index=7 v0=-1 v64=0.0 v127=1
+            <region> sample=a.wav v1=0.5",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.curves(), 1);
+        assert_eq!(i.curves[0].curve_index, Some(7));
+        let points: Vec<(u8, f32)> = i.curves[0].points.iter().map(|(&n, &v)| (n, v)).collect();
+        assert_eq!(points, [(0, -1.), (64, 0.), (127, 1.)]);
+
+        // a curve point outside a curve stays in its header, as a misplaced opcode
+        assert_eq!(i.regions[0].opcodes.get("v1"), Some(&Opcode::vN(1, 0.5)));
+        assert_eq!(i.validate().len(), 1);
+        assert!(i
+            .to_sfz()
+            .ends_with("<curve>\ncurve_index=7\nv0=-1\nv64=0\nv127=1\n"));
+    }
+
+    #[test]
+    fn test_instrument_to_csv() {
+        let i = Instrument::from_sfz(
+            "<global> volume=-3
+            <group> group_label=Soft hivel=63
+            <region> sample=a.wav key=60
+            <region> sample=b,c.wav lokey=61 hikey=64 pitch_keycenter=62 volume=1",
+            Path::new(""),
+        )
+        .unwrap();
+        let csv = i.to_csv();
+        let mut rows = csv.lines();
+        assert_eq!(
+            rows.next(),
+            Some("sample,lokey,hikey,lovel,hivel,pitch_keycenter,volume,group_label")
+        );
+        assert_eq!(rows.next(), Some("a.wav,60,60,0,63,60,-3,Soft"));
+        assert_eq!(rows.next(), Some("\"b,c.wav\",61,64,0,63,62,1,Soft"));
+        assert_eq!(rows.next(), None);
+    }
+
+    #[test]
+    fn test_instrument_key_span() {
+        let i = Instrument::from_sfz(
+            "<group> lokey=c1
+            <region> hikey=b3
+            <region> lokey=c4 hikey=c7",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.key_span(), Some(24..=96));
+
+        assert_eq!(Instrument::new().key_span(), None);
+    }
+
+    #[test]
+    fn test_instrument_into_regions() {
+        let i = Instrument::from_sfz(
+            "<group> volume=1
+            <region> sample=a.wav
+            <region> sample=b.wav",
+            Path::new(""),
+        )
+        .unwrap();
+        let regions = i.clone().into_regions();
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[1].group(), Some(0));
+        assert_eq!(
+            regions[1].opcodes["sample"],
+            Opcode::sample(PathBuf::from("b.wav"))
+        );
+
+        let groups = i.into_groups();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].opcodes["volume"], Opcode::volume(1.));
+    }
+
+    #[test]
+    fn test_instrument_effects_in_order() {
+        let i = Instrument::from_sfz(
+            "<region> sample=a.wav
+            <effect> dsp_order=2
+            <effect>
+            <effect> dsp_order=1",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.effects.len(), 3);
+        assert_eq!(i.regions[0].opcodes.len(), 1);
+
+        let order: Vec<Option<u8>> = i.effects_in_order().iter().map(|e| e.dsp_order).collect();
+        assert_eq!(order, [Some(1), Some(2), None]);
+    }
+}
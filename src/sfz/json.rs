@@ -0,0 +1,126 @@
+//! Exports an instrument as JSON.
+
+use serde_json::{Map, Number, Value};
+
+use crate::sfz::{Instrument, Opcode, OpcodeMap};
+
+impl Instrument {
+    /// Exports the instrument as a JSON object
+    ///
+    /// The object has a `global` object, and `masters`, `groups` and `regions`
+    /// arrays. The opcodes are written as name-value pairs, and the opcodes of
+    /// each region are resolved taking inheritance into account.
+    ///
+    /// The numeric values are written as JSON numbers, and the rest as strings.
+    ///
+    /// Requires the `json` feature.
+    pub fn to_json(&self) -> Value {
+        let masters = self
+            .masters
+            .iter()
+            .map(|master| {
+                let mut object = Map::new();
+                object.insert("opcodes".into(), opcodes_to_json([&master.opcodes]));
+                Value::Object(object)
+            })
+            .collect();
+
+        let groups = self
+            .groups
+            .iter()
+            .map(|group| {
+                let mut object = Map::new();
+                object.insert("label".into(), group.label.clone().into());
+                object.insert("master".into(), group.master.into());
+                object.insert("opcodes".into(), opcodes_to_json([&group.opcodes]));
+                Value::Object(object)
+            })
+            .collect();
+
+        let regions = self
+            .regions
+            .iter()
+            .map(|region| {
+                let mut object = Map::new();
+                object.insert("group".into(), region.group.into());
+                object.insert("master".into(), region.master.into());
+                // the lower priority maps are inserted first, to be overriden
+                let maps: Vec<&OpcodeMap> = region.inheritance(self).collect();
+                object.insert("opcodes".into(), opcodes_to_json(maps.into_iter().rev()));
+                Value::Object(object)
+            })
+            .collect();
+
+        let mut object = Map::new();
+        object.insert("global".into(), opcodes_to_json([&self.global]));
+        object.insert("masters".into(), Value::Array(masters));
+        object.insert("groups".into(), Value::Array(groups));
+        object.insert("regions".into(), Value::Array(regions));
+        Value::Object(object)
+    }
+}
+
+/// Builds a JSON object from the opcodes, the later ones overriding the earlier.
+fn opcodes_to_json<'a>(maps: impl IntoIterator<Item = &'a OpcodeMap>) -> Value {
+    let mut object = Map::new();
+    for opcode in maps.into_iter().flat_map(|map| map.values()) {
+        object.insert(opcode.full_name(), opcode_value(opcode));
+    }
+    Value::Object(object)
+}
+
+/// Returns the value of the opcode, as a number if possible.
+fn opcode_value(opcode: &Opcode) -> Value {
+    let sfz = opcode.to_string();
+    let value = sfz.split_once('=').map_or("", |(_, value)| value);
+    match value.parse::<f64>().ok().and_then(Number::from_f64) {
+        Some(number) => Value::Number(number),
+        None => Value::String(value.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests_json {
+    use crate::sfz::Instrument;
+    use serde_json::json;
+    use std::path::Path;
+
+    #[test]
+    fn test_instrument_to_json() {
+        let i = Instrument::from_sfz(
+            "<global> volume=-3 lokey=40
+            <group> group_label=Piano lokey=50
+            <region> sample=a.wav hikey=60
+            <region> sample=b.wav lokey=61 trigger=release",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(
+            i.to_json(),
+            json!({
+                "global": { "volume": -3.0, "lokey": 40.0 },
+                "masters": [],
+                "groups": [{
+                    "label": "",
+                    "master": null,
+                    "opcodes": { "group_label": "Piano", "lokey": 50.0 },
+                }],
+                "regions": [{
+                    "group": 0,
+                    "master": null,
+                    "opcodes": {
+                        "volume": -3.0, "lokey": 50.0, "group_label": "Piano",
+                        "sample": "a.wav", "hikey": 60.0,
+                    },
+                }, {
+                    "group": 0,
+                    "master": null,
+                    "opcodes": {
+                        "volume": -3.0, "lokey": 61.0, "group_label": "Piano",
+                        "sample": "b.wav", "trigger": "release",
+                    },
+                }],
+            })
+        );
+    }
+}
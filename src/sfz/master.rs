@@ -0,0 +1,30 @@
+use std::fmt::Debug;
+
+use crate::sfz::{Opcode, OpcodeMap};
+
+/// Masters add a level in between the global header and the groups.
+///
+/// A master is defined with the <master> header, and the parameters enumerated
+/// on it are inherited by the groups and regions that follow it, till the next
+/// master header, or till the end of the file.
+///
+/// - info: [master](https://sfzformat.com/headers/master)
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Master {
+    /// This list of opcodes overwrites the global ones.
+    pub opcodes: OpcodeMap,
+}
+
+impl Master {
+    /// New master.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an opcode to the master.
+    pub fn add_opcode(&mut self, o: &Opcode) {
+        self.opcodes.insert(o.full_name(), o.clone());
+    }
+}
@@ -0,0 +1,56 @@
+/// The state of the MIDI input at the moment a note is played.
+///
+/// It's used to select which regions of an instrument must play,
+/// using [`Region::matches`][crate::Region::matches].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MidiContext {
+    /// The key of the note being played.
+    pub key: u8,
+
+    /// The velocity of the note being played.
+    pub velocity: u8,
+
+    /// The MIDI channel, from 1 to 16.
+    pub channel: u8,
+
+    /// The last value received for every MIDI CC.
+    pub cc: [u8; 128],
+
+    /// The pitch bend, from -8192 to 8192.
+    pub bend: i16,
+
+    /// The channel aftertouch.
+    pub channel_aftertouch: u8,
+
+    /// The polyphonic aftertouch of the note being played.
+    pub poly_aftertouch: u8,
+
+    /// The last keyswitch pressed, if any.
+    pub keyswitch: Option<u8>,
+}
+
+impl Default for MidiContext {
+    fn default() -> Self {
+        Self {
+            key: 60,
+            velocity: 127,
+            channel: 1,
+            cc: [0; 128],
+            bend: 0,
+            channel_aftertouch: 0,
+            poly_aftertouch: 0,
+            keyswitch: None,
+        }
+    }
+}
+
+impl MidiContext {
+    /// New context for a note with the provided key and velocity.
+    pub fn new(key: u8, velocity: u8) -> Self {
+        Self {
+            key,
+            velocity,
+            ..Self::default()
+        }
+    }
+}
@@ -4,19 +4,37 @@
 //! Multiple regions can be arranged in a group.
 //! Groups allow entering common parameters for multiple regions.
 
+mod curve;
+mod effect;
 mod group;
 mod headers;
 mod instrument;
+#[cfg(feature = "json")]
+mod json;
+mod master;
+mod midi;
+mod normalize;
 mod opcodes;
+mod preprocess;
 mod region;
+mod routing;
+mod validate;
+mod warning;
 
 pub mod types;
 
+pub use curve::Curve;
+pub use effect::Effect;
 pub use group::Group;
 pub use headers::Header;
-pub use instrument::Instrument;
+pub use instrument::{FormatOptions, Instrument, SfzWriteOptions};
+pub use master::Master;
+pub use midi::MidiContext;
 pub use opcodes::Opcode;
-pub use region::Region;
+pub use region::{LfoConfig, LoopSettings, Region};
+pub use routing::RoutingMatrix;
 pub use types::{OpcodeMap, OpcodeType};
+pub use validate::{Diagnostic, DiagnosticKind, SampleResolver};
+pub use warning::{Warning, WarningKind};
 
 pub(crate) use opcodes::SfzToken;
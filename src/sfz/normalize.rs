@@ -0,0 +1,211 @@
+//! Canonicalizes an instrument, for diffing and storage.
+
+use crate::sfz::{Instrument, Opcode, OpcodeMap};
+
+impl Instrument {
+    /// Canonicalizes the instrument, without changing how it sounds
+    ///
+    /// It prunes the empty regions, expands the `key` shorthand, removes the
+    /// redundant default opcodes, and sorts the opcodes by name.
+    ///
+    /// The regions without a sample are kept, since they may get one later.
+    /// They can be removed with [`prune_regions_without_sample`][Self::prune_regions_without_sample].
+    pub fn normalize(&mut self) {
+        self.prune_empty_regions();
+        self.expand_key_shorthand();
+        self.remove_default_opcodes();
+        self.sort_opcodes();
+    }
+
+    /// Removes the regions without opcodes
+    ///
+    /// The groups are kept, so the groups of the remaining regions stay valid.
+    pub fn prune_empty_regions(&mut self) {
+        self.regions.retain(|r| !r.opcodes.is_empty());
+    }
+
+    /// Removes the regions that don't have a sample, not even an inherited one,
+    /// since they can't play anything
+    ///
+    /// The groups are kept, so the groups of the remaining regions stay valid.
+    pub fn prune_regions_without_sample(&mut self) {
+        let keep: Vec<bool> = self
+            .regions
+            .iter()
+            .map(|r| r.inherited_opcode("sample", self).is_some())
+            .collect();
+        let mut keep = keep.into_iter();
+        self.regions.retain(|_| keep.next().unwrap_or(true));
+    }
+
+    /// Removes the opcodes that have their default value, as long as they
+    /// don't override a value inherited from a group, a master or from the global header
+    pub fn remove_default_opcodes(&mut self) {
+        let global = self.global.clone();
+        let masters: Vec<OpcodeMap> = self.masters.iter().map(|m| m.opcodes.clone()).collect();
+        let groups: Vec<OpcodeMap> = self.groups.iter().map(|g| g.opcodes.clone()).collect();
+
+        self.global.retain(|_, o| !o.is_default());
+        for master in self.masters.iter_mut() {
+            master
+                .opcodes
+                .retain(|name, o| !o.is_default() || global.contains_key(name));
+        }
+        for group in self.groups.iter_mut() {
+            let master = group.master.and_then(|m| masters.get(m));
+            group.opcodes.retain(|name, o| {
+                !o.is_default()
+                    || global.contains_key(name)
+                    || master.is_some_and(|m| m.contains_key(name))
+            });
+        }
+        for region in self.regions.iter_mut() {
+            let master = region.master.and_then(|m| masters.get(m));
+            let group = region.group.and_then(|g| groups.get(g));
+            region.opcodes.retain(|name, o| {
+                !o.is_default()
+                    || global.contains_key(name)
+                    || master.is_some_and(|m| m.contains_key(name))
+                    || group.is_some_and(|g| g.contains_key(name))
+            });
+        }
+    }
+
+    /// Replaces each `key` opcode with the `lokey`, `hikey` and `pitch_keycenter`
+    /// opcodes it stands for, except the ones already defined in the same header
+    pub fn expand_key_shorthand(&mut self) {
+        let maps = std::iter::once(&mut self.global)
+            .chain(self.masters.iter_mut().map(|m| &mut m.opcodes))
+            .chain(self.groups.iter_mut().map(|g| &mut g.opcodes))
+            .chain(self.regions.iter_mut().map(|r| &mut r.opcodes));
+        for opcodes in maps {
+            if let Some((index, _, Opcode::key(key))) = opcodes.shift_remove_full("key") {
+                let mut index = index;
+                for opcode in [
+                    Opcode::lokey(key),
+                    Opcode::hikey(key),
+                    Opcode::pitch_keycenter(key),
+                ] {
+                    let name = opcode.full_name();
+                    if !opcodes.contains_key(&name) {
+                        opcodes.shift_insert(index, name, opcode);
+                        index += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sorts the opcodes of every header by name
+    pub fn sort_opcodes(&mut self) {
+        self.global.sort_keys();
+        for master in self.masters.iter_mut() {
+            master.opcodes.sort_keys();
+        }
+        for group in self.groups.iter_mut() {
+            group.opcodes.sort_keys();
+        }
+        for region in self.regions.iter_mut() {
+            region.opcodes.sort_keys();
+        }
+        for effect in self.effects.iter_mut() {
+            effect.opcodes.sort_keys();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_normalize {
+    use super::*;
+    use std::path::Path;
+
+    fn instrument(sfz: &str) -> Instrument {
+        Instrument::from_sfz(sfz, Path::new("")).unwrap()
+    }
+
+    #[test]
+    fn test_remove_default_opcodes() {
+        let mut i = instrument(
+            "<global> volume=0 tune=0 lovel=10
+            <region> sample=a.wav pan=0 pitch_keycenter=60 lovel=0 hivel=100",
+        );
+        i.remove_default_opcodes();
+        assert_eq!(i.global.len(), 1);
+        // lovel=0 is kept, since it overrides the inherited lovel=10
+        assert_eq!(
+            i.regions[0].opcodes.keys().collect::<Vec<_>>(),
+            ["sample", "lovel", "hivel"]
+        );
+    }
+
+    #[test]
+    fn test_expand_key_shorthand() {
+        let mut i = instrument("<region> sample=a.wav key=62 hikey=64");
+        i.expand_key_shorthand();
+        assert_eq!(
+            i.regions[0].opcodes.values().cloned().collect::<Vec<_>>(),
+            [
+                Opcode::sample("a.wav".into()),
+                Opcode::lokey(62),
+                Opcode::pitch_keycenter(62),
+                Opcode::hikey(64),
+            ]
+        );
+        assert_eq!(i.regions[0].key_range(&i), 62..=64);
+    }
+
+    #[test]
+    fn test_prune_empty_regions() {
+        let mut i = instrument(
+            "<region>
+            <group> volume=1 <region> <region> lokey=2
+            <group> <region> sample=c.wav",
+        );
+        i.prune_empty_regions();
+        assert_eq!(i.regions.len(), 2);
+        assert_eq!(i.groups.len(), 2);
+        assert_eq!(i.regions[0].group, Some(0));
+        assert_eq!(i.regions[1].group, Some(1));
+        assert!(i.validate().is_empty());
+    }
+
+    #[test]
+    fn test_prune_regions_without_sample() {
+        let mut i = instrument(
+            "<region> lokey=1
+            <group> sample=b.wav <region> lokey=2
+            <group> <region> sample=c.wav <region>",
+        );
+        i.prune_regions_without_sample();
+        assert_eq!(i.regions.len(), 2);
+        assert_eq!(i.regions[0].group, Some(0));
+        assert_eq!(i.regions[1].group, Some(1));
+        assert!(i.validate().is_empty());
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut i = instrument("<region> volume=0 sample=a.wav key=60 tune=5");
+        i.normalize();
+        assert_eq!(
+            i.to_sfz(),
+            "<region>\nhikey=60\nlokey=60\nsample=a.wav\ntune=5\n"
+        );
+    }
+
+    #[test]
+    fn test_normalize_regions() {
+        // the empty regions are pruned, but not the ones without a sample,
+        // nor the ones left empty by removing their default opcodes
+        let sfz = "<region> <region> volume=0 <region> lokey=60";
+        let mut i = instrument(sfz);
+        i.normalize();
+        assert_eq!(i.regions.len(), 2);
+        assert!(i.regions[0].opcodes.is_empty());
+        assert_eq!(i.regions[1].opcodes.len(), 1);
+
+        let mut i = instrument(sfz);
+        i.prune_regions_without_sample();
+        assert!(i.regions.is_empty());
+    }
+}
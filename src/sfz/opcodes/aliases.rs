@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use once_cell::sync::Lazy;
+use phf::phf_map;
+
+use crate::sfz::Opcode;
+
+/// This map returns the canonical name of an opcode from one of its aliases
+///
+/// The names have their numeric parameters replaced by letters, like the ones
+/// returned by `Opcode::parse_name`, so that the parameters carry over.
+pub(crate) static OPCODE_ALIASES: phf::Map<&'static str, &'static str> = phf_map! {
+
+    // Spellings without underscores, accepted by several players ----------
+
+    "benddown" => "bend_down",
+    "bendup" => "bend_up",
+    "filtype" => "fil_type",
+    "loopend" => "loop_end",
+    "loopmode" => "loop_mode",
+    "loopstart" => "loop_start",
+    "offby" => "off_by",
+
+    // ARIA `_ccN` spellings of the `_onccN` opcodes --------------------
+    // (the `_onccN` spellings of the `_ccN` opcodes are added in `ALIASES`)
+
+    "resonance2_ccN" => "resonance2_onccN",
+};
+
+/// The [`OPCODE_ALIASES`] plus the ARIA `_onccN` spellings of the opcodes
+/// named the same but ending in `_ccN` or `ccN`, like `cutoff_onccN` for
+/// `cutoff_ccN` and `ampeg_attack_onccN` for `ampeg_attackccN`
+///
+/// The `_onccN` spellings that are opcodes on their own aren't aliases.
+static ALIASES: Lazy<HashMap<String, &'static str>> = Lazy::new(|| {
+    let mut aliases: HashMap<String, &'static str> = OPCODE_ALIASES
+        .entries()
+        .map(|(alias, canonical)| (alias.to_string(), *canonical))
+        .collect();
+    for canonical in Opcode::NAMES {
+        let base = match canonical.strip_suffix("_ccN") {
+            Some(base) => base,
+            None => match canonical.strip_suffix("ccN") {
+                Some(base) => base,
+                None => continue,
+            },
+        };
+        let alias = format!("{base}_onccN");
+        if !Opcode::NAMES.contains(&alias.as_str()) {
+            aliases.entry(alias).or_insert(canonical);
+        }
+    }
+    aliases
+});
+
+/// Returns the canonical name of an opcode from one of its aliases,
+/// or `None` if it's not an alias
+pub(crate) fn canonical_name(name: &str) -> Option<&'static str> {
+    ALIASES.get(name).copied()
+}
@@ -1,58 +1,214 @@
 use phf::phf_map;
 
-use crate::sfz::types::{fil_type, trigger, OpcodeType};
+use crate::sfz::types::{
+    direction, fil_type, loop_type, off_mode, on_off, phase, sw_vel, trigger, xf_curve, OpcodeType,
+};
 
 /// This map returns the optional default value of an opcode type
 pub(crate) static OPCODE_DEFAULT: phf::Map<&'static str, OpcodeType> = phf_map! {
 
     // Opcodes in SFZ format version 1.0 --------------------------------
 
+    "amp_keycenter" => OpcodeType::u8(Some(60)),
+    "amp_keytrack" => OpcodeType::f32(Some(0.0)),
     "amp_random" => OpcodeType::f32(Some(0.0)),
+    "amp_velcurve_N" => OpcodeType::f32(None), // the standard curve of amp_veltrack
     "amp_veltrack" => OpcodeType::f32(Some(100.0)),
     "ampeg_attack" => OpcodeType::f32(Some(0.0)),
     "ampeg_attackccN" => OpcodeType::f32(Some(0.0)), // XXX < how to store. Vec? HashMap?
     "ampeg_decay" => OpcodeType::f32(Some(0.0)),
+    "ampeg_decayccN" => OpcodeType::f32(Some(0.0)),
+    "ampeg_delay" => OpcodeType::f32(Some(0.0)),
+    "ampeg_delayccN" => OpcodeType::f32(Some(0.0)),
     "ampeg_hold" => OpcodeType::f32(Some(0.0)),
+    "ampeg_holdccN" => OpcodeType::f32(Some(0.0)),
     "ampeg_release" => OpcodeType::f32(Some(0.0)),
+    "ampeg_releaseccN" => OpcodeType::f32(Some(0.0)),
+    "ampeg_start" => OpcodeType::f32(Some(0.0)),
+    "ampeg_startccN" => OpcodeType::f32(Some(0.0)),
     "ampeg_sustain" => OpcodeType::f32(Some(100.0)),
+    "ampeg_sustainccN" => OpcodeType::f32(Some(0.0)),
+    "ampeg_vel2attack" => OpcodeType::f32(Some(0.0)),
+    "ampeg_vel2decay" => OpcodeType::f32(Some(0.0)),
+    "ampeg_vel2delay" => OpcodeType::f32(Some(0.0)),
+    "ampeg_vel2hold" => OpcodeType::f32(Some(0.0)),
+    "ampeg_vel2release" => OpcodeType::f32(Some(0.0)),
+    "ampeg_vel2sustain" => OpcodeType::f32(Some(0.0)),
+    "amplfo_delay" => OpcodeType::f32(Some(0.0)),
+    "amplfo_depth" => OpcodeType::f32(Some(0.0)),
+    "amplfo_depthccN" => OpcodeType::f32(Some(0.0)),
+    "amplfo_depthchanaft" => OpcodeType::f32(Some(0.0)),
+    "amplfo_depthpolyaft" => OpcodeType::f32(Some(0.0)),
+    "amplfo_fade" => OpcodeType::f32(Some(0.0)),
+    "amplfo_freq" => OpcodeType::f32(Some(0.0)),
+    "amplfo_freqccN" => OpcodeType::f32(Some(0.0)),
+    "amplfo_freqchanaft" => OpcodeType::f32(Some(0.0)),
+    "amplfo_freqpolyaft" => OpcodeType::f32(Some(0.0)),
     "bend_down" => OpcodeType::i16(Some(-200)),
+    "bend_step" => OpcodeType::u16(Some(1)),
     "bend_up" => OpcodeType::i16(Some(200)),
+    "count" => OpcodeType::u32(Some(0)),
     "cutoff" => OpcodeType::f32(None),
+    "cutoff_ccN" => OpcodeType::i16(Some(0)),
+    "cutoff_chanaft" => OpcodeType::i16(Some(0)),
+    "cutoff_polyaft" => OpcodeType::i16(Some(0)),
+    "delay" => OpcodeType::f32(Some(0.0)),
+    "delay_ccN" => OpcodeType::f32(Some(0.0)),
+    "delay_random" => OpcodeType::f32(Some(0.0)),
+    "effect1" => OpcodeType::f32(Some(0.0)),
+    "effect2" => OpcodeType::f32(Some(0.0)),
+    "end" => OpcodeType::u32(None), // the end of the sample
+    "eqN_bw" => OpcodeType::f32(Some(1.0)),
+    "eqN_bwccX" => OpcodeType::f32(Some(0.0)),
+    "eqN_freq" => OpcodeType::f32(None), // eq1: 50, eq2: 500, eq3: 5000
+    "eqN_freqccX" => OpcodeType::f32(Some(0.0)),
+    "eqN_gain" => OpcodeType::f32(Some(0.0)),
+    "eqN_gainccX" => OpcodeType::f32(Some(0.0)),
+    "eqN_vel2freq" => OpcodeType::f32(Some(0.0)),
+    "eqN_vel2gain" => OpcodeType::f32(Some(0.0)),
+    "fil_keycenter" => OpcodeType::u8(Some(60)),
+    "fil_keytrack" => OpcodeType::i16(Some(0)),
+    "fil_random" => OpcodeType::u16(Some(0)),
     "fil_type" => OpcodeType::fil_type(Some(fil_type::lpf_2p)),
     "fil_veltrack" => OpcodeType::i16(Some(0)),
+    "fileg_attack" => OpcodeType::f32(Some(0.0)),
+    "fileg_decay" => OpcodeType::f32(Some(0.0)),
+    "fileg_delay" => OpcodeType::f32(Some(0.0)),
+    "fileg_depth" => OpcodeType::i16(Some(0)),
+    "fileg_hold" => OpcodeType::f32(Some(0.0)),
+    "fileg_release" => OpcodeType::f32(Some(0.0)),
+    "fileg_start" => OpcodeType::f32(Some(0.0)),
+    "fileg_sustain" => OpcodeType::f32(Some(0.0)),
+    "fileg_vel2attack" => OpcodeType::f32(Some(0.0)),
+    "fileg_vel2decay" => OpcodeType::f32(Some(0.0)),
+    "fileg_vel2delay" => OpcodeType::f32(Some(0.0)),
+    "fileg_vel2depth" => OpcodeType::i16(Some(0)),
+    "fileg_vel2hold" => OpcodeType::f32(Some(0.0)),
+    "fileg_vel2release" => OpcodeType::f32(Some(0.0)),
+    "fileg_vel2sustain" => OpcodeType::f32(Some(0.0)),
+    "fillfo_delay" => OpcodeType::f32(Some(0.0)),
+    "fillfo_depth" => OpcodeType::f32(Some(0.0)),
+    "fillfo_depthccN" => OpcodeType::f32(Some(0.0)),
+    "fillfo_depthchanaft" => OpcodeType::f32(Some(0.0)),
+    "fillfo_depthpolyaft" => OpcodeType::f32(Some(0.0)),
+    "fillfo_fade" => OpcodeType::f32(Some(0.0)),
+    "fillfo_freq" => OpcodeType::f32(Some(0.0)),
+    "fillfo_freqccN" => OpcodeType::f32(Some(0.0)),
+    "fillfo_freqchanaft" => OpcodeType::f32(Some(0.0)),
+    "fillfo_freqpolyaft" => OpcodeType::f32(Some(0.0)),
+    "gain_ccN" => OpcodeType::f32(Some(0.0)),
+    "group" => OpcodeType::u32(Some(0)),
+    "hibend" => OpcodeType::i16(Some(8192)),
+    "hibpm" => OpcodeType::f32(Some(500.0)),
+    "hiccN" => OpcodeType::u8(Some(127)),
+    "hichan" => OpcodeType::u8(Some(16)),
+    "hichanaft" => OpcodeType::u8(Some(127)),
     "hikey" => OpcodeType::u8(Some(127)),
+    "hipolyaft" => OpcodeType::u8(Some(127)),
     "hirand" => OpcodeType::f32(Some(1.0)),
     "hivel" => OpcodeType::u8(Some(127)),
+    "key" => OpcodeType::u8(None),
+    "lobend" => OpcodeType::i16(Some(-8192)),
+    "lobpm" => OpcodeType::f32(Some(0.0)),
+    "loccN" => OpcodeType::u8(Some(0)),
+    "lochan" => OpcodeType::u8(Some(1)),
+    "lochanaft" => OpcodeType::u8(Some(0)),
     "lokey" => OpcodeType::u8(Some(0)),
+    "loop_end" => OpcodeType::u32(Some(0)),
     "loop_mode" => OpcodeType::loop_mode(None), // no_loop for samples without a loop defined,
                                                 // loop_continuous for samples w defined loop(s)
+    "loop_start" => OpcodeType::u32(Some(0)),
+    "lopolyaft" => OpcodeType::u8(Some(0)),
     "lorand" => OpcodeType::f32(Some(0.0)),
     "lovel" => OpcodeType::u8(Some(0)),
-    "off_by" => OpcodeType::f32(Some(0.0)),
+    "off_by" => OpcodeType::u32(Some(0)),
+    "off_mode" => OpcodeType::off_mode(Some(off_mode::fast)),
     "offset" => OpcodeType::u32(Some(0)),
-    "on_loccN" => OpcodeType::i8(Some(-1)), // XXX < how to store. Vec? HashMap?
+    "offset_ccN" => OpcodeType::u32(Some(0)),
+    "offset_random" => OpcodeType::u32(Some(0)),
     "on_hiccN" => OpcodeType::i8(Some(-1)), // XXX < how to store. Vec? HashMap?
+    "on_loccN" => OpcodeType::i8(Some(-1)), // XXX < how to store. Vec? HashMap?
+    "output" => OpcodeType::u16(Some(0)),
     "pan" => OpcodeType::f32(Some(0.0)),
     "pitch_keycenter" => OpcodeType::u8(Some(60)),
     "pitch_keytrack" => OpcodeType::i16(Some(100)),
     "pitch_random" => OpcodeType::u16(Some(0)),
+    "pitch_veltrack" => OpcodeType::i16(Some(0)),
+    "pitcheg_attack" => OpcodeType::f32(Some(0.0)),
+    "pitcheg_decay" => OpcodeType::f32(Some(0.0)),
+    "pitcheg_delay" => OpcodeType::f32(Some(0.0)),
+    "pitcheg_depth" => OpcodeType::i16(Some(0)),
+    "pitcheg_hold" => OpcodeType::f32(Some(0.0)),
+    "pitcheg_release" => OpcodeType::f32(Some(0.0)),
+    "pitcheg_start" => OpcodeType::f32(Some(0.0)),
+    "pitcheg_sustain" => OpcodeType::f32(Some(0.0)),
+    "pitcheg_vel2attack" => OpcodeType::f32(Some(0.0)),
+    "pitcheg_vel2decay" => OpcodeType::f32(Some(0.0)),
+    "pitcheg_vel2delay" => OpcodeType::f32(Some(0.0)),
+    "pitcheg_vel2depth" => OpcodeType::i16(Some(0)),
+    "pitcheg_vel2hold" => OpcodeType::f32(Some(0.0)),
+    "pitcheg_vel2release" => OpcodeType::f32(Some(0.0)),
+    "pitcheg_vel2sustain" => OpcodeType::f32(Some(0.0)),
+    "pitchlfo_delay" => OpcodeType::f32(Some(0.0)),
+    "pitchlfo_depth" => OpcodeType::f32(Some(0.0)),
+    "pitchlfo_depthccN" => OpcodeType::f32(Some(0.0)),
+    "pitchlfo_depthchanaft" => OpcodeType::f32(Some(0.0)),
+    "pitchlfo_depthpolyaft" => OpcodeType::f32(Some(0.0)),
+    "pitchlfo_fade" => OpcodeType::f32(Some(0.0)),
+    "pitchlfo_freq" => OpcodeType::f32(Some(0.0)),
+    "pitchlfo_freqccN" => OpcodeType::f32(Some(0.0)),
+    "pitchlfo_freqchanaft" => OpcodeType::f32(Some(0.0)),
+    "pitchlfo_freqpolyaft" => OpcodeType::f32(Some(0.0)),
+    "position" => OpcodeType::f32(Some(0.0)),
+    "resonance" => OpcodeType::f32(Some(0.0)),
     "rt_decay" => OpcodeType::f32(Some(0.0)),
     "sample" => OpcodeType::PathBuf(None),
-    "seq_lengh" => OpcodeType::u8(Some(1)),
+    "seq_length" => OpcodeType::u8(Some(1)),
     "seq_position" => OpcodeType::u8(Some(1)),
+    "sw_down" => OpcodeType::u8(Some(0)),
     "sw_hikey" => OpcodeType::u8(Some(127)),
     "sw_last" => OpcodeType::u8(Some(0)),
-    "sw_lowkey" => OpcodeType::u8(Some(0)),
+    "sw_lokey" => OpcodeType::u8(Some(0)),
+    "sw_previous" => OpcodeType::u8(None),
+    "sw_up" => OpcodeType::u8(Some(0)),
+    "sw_vel" => OpcodeType::sw_vel(Some(sw_vel::current)),
+    "sync_beats" => OpcodeType::f32(Some(0.0)),
+    "sync_offset" => OpcodeType::f32(Some(0.0)),
+    "transpose" => OpcodeType::i8(Some(0)),
     "trigger" => OpcodeType::trigger(Some(trigger::attack)),
     "tune" => OpcodeType::i8(Some(0)),
     "volume" => OpcodeType::f32(Some(0.0)),
+    "width" => OpcodeType::f32(Some(100.0)),
+    "xf_cccurve" => OpcodeType::xf_curve(Some(xf_curve::power)),
+    "xf_keycurve" => OpcodeType::xf_curve(Some(xf_curve::power)),
+    "xf_velcurve" => OpcodeType::xf_curve(Some(xf_curve::power)),
+    "xfin_hiccN" => OpcodeType::u8(Some(0)),
+    "xfin_hikey" => OpcodeType::u8(Some(0)),
     "xfin_hivel" => OpcodeType::u8(Some(0)),
+    "xfin_loccN" => OpcodeType::u8(Some(0)),
+    "xfin_lokey" => OpcodeType::u8(Some(0)),
     "xfin_lovel" => OpcodeType::u8(Some(0)),
+    "xfout_hiccN" => OpcodeType::u8(Some(0)),
+    "xfout_hikey" => OpcodeType::u8(Some(127)),
     "xfout_hivel" => OpcodeType::u8(Some(127)),
+    "xfout_loccN" => OpcodeType::u8(Some(0)),
+    "xfout_lokey" => OpcodeType::u8(Some(127)),
     "xfout_lovel" => OpcodeType::u8(Some(127)),
 
     // Opcodes in SFZ format version 2.0 --------------------------------
 
+    "direction" => OpcodeType::direction(Some(direction::forward)),
+    "loop_type" => OpcodeType::loop_type(Some(loop_type::forward)),
+    "note_selfmask" => OpcodeType::on_off(Some(on_off::on)),
+    "on_hihdccN" => OpcodeType::f32(Some(-1.0)),
+    "on_lohdccN" => OpcodeType::f32(Some(-1.0)),
+    "phase" => OpcodeType::phase(Some(phase::normal)),
+    "polyphony_group" => OpcodeType::u32(Some(0)),
+    "rt_dead" => OpcodeType::on_off(Some(on_off::off)),
+    "start_hihdccN" => OpcodeType::f32(Some(-1.0)),
+    "start_lohdccN" => OpcodeType::f32(Some(-1.0)),
+    "stop_hihdccN" => OpcodeType::f32(Some(-1.0)),
+    "stop_lohdccN" => OpcodeType::f32(Some(-1.0)),
     "sw_default" => OpcodeType::u8(None),
 
     // Opcodes in SFZ aria extensions -----------------------------------
@@ -61,3 +217,38 @@ pub(crate) static OPCODE_DEFAULT: phf::Map<&'static str, OpcodeType> = phf_map!
     "group_label" => OpcodeType::String(Some("")),
     "sw_label" => OpcodeType::String(None),
 };
+
+#[cfg(test)]
+mod tests_defaults {
+    use crate::sfz::{types::OpcodeType, Opcode};
+
+    #[test]
+    fn test_default_value_v1() {
+        assert_eq!(
+            Opcode::width(0.).default_value(),
+            Some(OpcodeType::f32(Some(100.)))
+        );
+        assert_eq!(
+            Opcode::hichan(1).default_value(),
+            Some(OpcodeType::u8(Some(16)))
+        );
+        assert_eq!(
+            Opcode::lochan(16).default_value(),
+            Some(OpcodeType::u8(Some(1)))
+        );
+        assert_eq!(
+            Opcode::bend_up(0).default_value(),
+            Some(OpcodeType::i16(Some(200)))
+        );
+        assert_eq!(
+            Opcode::bend_down(0).default_value(),
+            Some(OpcodeType::i16(Some(-200)))
+        );
+        assert_eq!(
+            Opcode::ampeg_holdccN(1, 2.).default_value(),
+            Some(OpcodeType::f32(Some(0.)))
+        );
+        assert_eq!(Opcode::key(60).default_value(), Some(OpcodeType::u8(None)));
+        assert!(Opcode::sw_lokey(0).is_default());
+    }
+}
@@ -0,0 +1,165 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::sfz::types::{
+    direction, effect_filter, effect_type, fil_type, loop_mode, loop_type, off_mode, on_off, phase,
+    sw_vel, trigger, waveform, xf_curve, OpcodeType, UnknownType,
+};
+use crate::sfz::Opcode;
+
+/// Formats an opcode value the way it's written in an SFZ file.
+pub(crate) trait OpcodeValue {
+    fn fmt_value(&self, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+macro_rules! impl_opcode_value_display {
+    ($($t:ty),+) => {
+        $( impl OpcodeValue for $t {
+            fn fmt_value(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self)
+            }
+        } )+
+    };
+}
+impl_opcode_value_display![i8, u8, i16, u16, i32, u32, f32, String];
+
+macro_rules! impl_opcode_value_as_str {
+    ($($t:ty),+) => {
+        $( impl OpcodeValue for $t {
+            fn fmt_value(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        } )+
+    };
+}
+impl_opcode_value_as_str![
+    direction, fil_type, loop_mode, loop_type, off_mode, on_off, phase, sw_vel, trigger, xf_curve
+];
+
+impl OpcodeValue for waveform {
+    fn fmt_value(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_number())
+    }
+}
+
+impl OpcodeValue for effect_type {
+    fn fmt_value(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl OpcodeValue for effect_filter {
+    fn fmt_value(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::on => f.write_str("on"),
+            Self::off => f.write_str("off"),
+            Self::Filter(t) => t.fmt_value(f),
+        }
+    }
+}
+
+// Paths are always written with forward slashes, undoing `fix_path_separators`.
+impl OpcodeValue for PathBuf {
+    fn fmt_value(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string_lossy().replace('\\', "/"))
+    }
+}
+
+impl OpcodeValue for UnknownType {
+    fn fmt_value(&self, _f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {}
+    }
+}
+
+/// Formats the value as SFZ code, or nothing if there's no value.
+impl fmt::Display for OpcodeType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OpcodeType::i8(Some(v)) => v.fmt_value(f),
+            OpcodeType::u8(Some(v)) => v.fmt_value(f),
+            OpcodeType::i16(Some(v)) => v.fmt_value(f),
+            OpcodeType::u16(Some(v)) => v.fmt_value(f),
+            OpcodeType::u32(Some(v)) => v.fmt_value(f),
+            OpcodeType::f32(Some(v)) => v.fmt_value(f),
+            OpcodeType::fil_type(Some(v)) => v.fmt_value(f),
+            OpcodeType::loop_mode(Some(v)) => v.fmt_value(f),
+            OpcodeType::trigger(Some(v)) => v.fmt_value(f),
+            OpcodeType::xf_curve(Some(v)) => v.fmt_value(f),
+            OpcodeType::off_mode(Some(v)) => v.fmt_value(f),
+            OpcodeType::sw_vel(Some(v)) => v.fmt_value(f),
+            OpcodeType::direction(Some(v)) => v.fmt_value(f),
+            OpcodeType::loop_type(Some(v)) => v.fmt_value(f),
+            OpcodeType::phase(Some(v)) => v.fmt_value(f),
+            OpcodeType::on_off(Some(v)) => v.fmt_value(f),
+            OpcodeType::PathBuf(Some(v)) => v.fmt_value(f),
+            OpcodeType::String(Some(v)) => write!(f, "{}", v),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Formats the opcode as SFZ code, like `cutoff=500`.
+impl fmt::Display for Opcode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}=", self.full_name())?;
+        match self.value_field() {
+            Some(value) => value.fmt_value(f),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_display {
+    use super::*;
+
+    #[test]
+    fn test_display_float() {
+        assert_eq!(Opcode::cutoff(500.).to_string(), "cutoff=500");
+        assert_eq!(
+            Opcode::ampeg_release(0.25).to_string(),
+            "ampeg_release=0.25"
+        );
+        assert_eq!(Opcode::volume(-6.).to_string(), "volume=-6");
+    }
+
+    #[test]
+    fn test_display_path() {
+        assert_eq!(
+            Opcode::sample(PathBuf::from("drums/kick.wav")).to_string(),
+            "sample=drums/kick.wav"
+        );
+        assert_eq!(
+            Opcode::sample(PathBuf::from(r"drums\kick.wav")).to_string(),
+            "sample=drums/kick.wav"
+        );
+    }
+
+    #[test]
+    fn test_display_enums() {
+        assert_eq!(
+            Opcode::fil_type(fil_type::hpf_2p).to_string(),
+            "fil_type=hpf_2p"
+        );
+        assert_eq!(
+            Opcode::loop_mode(loop_mode::loop_continuous).to_string(),
+            "loop_mode=loop_continuous"
+        );
+        assert_eq!(
+            Opcode::trigger(trigger::release).to_string(),
+            "trigger=release"
+        );
+        assert_eq!(
+            Opcode::xf_velcurve(xf_curve::gain).to_string(),
+            "xf_velcurve=gain"
+        );
+        assert_eq!(
+            Opcode::phaser_waveform(waveform::pulse12_5).to_string(),
+            "phaser_waveform=5"
+        );
+        assert_eq!(
+            Opcode::apan_waveform(waveform::saw_up).to_string(),
+            "apan_waveform=6"
+        );
+    }
+}
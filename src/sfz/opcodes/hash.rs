@@ -0,0 +1,107 @@
+//! Hashing of opcodes, so that they can be used as keys of sets and maps.
+
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+use crate::sfz::types::{
+    direction, effect_filter, effect_type, fil_type, loop_mode, loop_type, off_mode, on_off, phase,
+    sw_vel, trigger, waveform, xf_curve, UnknownType,
+};
+use crate::sfz::Opcode;
+
+/// Hashes an opcode value consistently with its `PartialEq`.
+pub(crate) trait OpcodeValueHash {
+    fn hash_value(&self, state: &mut dyn Hasher);
+}
+
+macro_rules! impl_opcode_value_hash {
+    ($($t:ty),+) => {
+        $( impl OpcodeValueHash for $t {
+            fn hash_value(&self, mut state: &mut dyn Hasher) {
+                self.hash(&mut state)
+            }
+        } )+
+    };
+}
+impl_opcode_value_hash![i8, u8, i16, u16, i32, u32, String, PathBuf];
+impl_opcode_value_hash![
+    direction,
+    effect_filter,
+    effect_type,
+    fil_type,
+    loop_mode,
+    loop_type,
+    off_mode,
+    on_off,
+    phase,
+    sw_vel,
+    trigger,
+    waveform,
+    xf_curve
+];
+
+// Floats are hashed by their bit pattern, with both zeros being equal.
+impl OpcodeValueHash for f32 {
+    fn hash_value(&self, mut state: &mut dyn Hasher) {
+        let canonical = if *self == 0. { 0_f32 } else { *self };
+        canonical.to_bits().hash(&mut state)
+    }
+}
+
+impl OpcodeValueHash for UnknownType {
+    fn hash_value(&self, _state: &mut dyn Hasher) {
+        match *self {}
+    }
+}
+
+/// Hashes the opcode name, its parameters and its value.
+///
+/// Floats are hashed by their bit pattern, except `-0.0`, which is hashed as `0.0`.
+///
+/// Note that an opcode with a NaN value is not equal to itself, so many of them
+/// can be inserted into a set, and they can't be found in it.
+impl Hash for Opcode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name().hash(state);
+        self.params().hash(state);
+        if let Some(value) = self.value_field() {
+            value.hash_value(state);
+        }
+    }
+}
+
+/// Opcodes are equal when their names, parameters and values are, like
+/// with `PartialEq`.
+///
+/// The parser never produces NaN values, since they are out of range,
+/// but an opcode constructed with one is not equal to itself.
+impl Eq for Opcode {}
+
+#[cfg(test)]
+mod tests_hash {
+    use crate::sfz::{Opcode, SfzToken};
+    use logos::Logos;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_opcode_hash() {
+        let mut set = HashSet::new();
+        assert!(set.insert(Opcode::cutoff(500.)));
+        assert!(set.insert(Opcode::cutoff(600.)));
+        assert!(set.insert(Opcode::loccN(1, 64)));
+        assert!(set.insert(Opcode::loccN(2, 64)));
+        assert!(set.insert(Opcode::volume(0.)));
+
+        // duplicates
+        assert!(!set.insert(Opcode::cutoff(500.)));
+        assert!(!set.insert(Opcode::loccN(1, 64)));
+        assert!(!set.insert(Opcode::volume(-0.)));
+        assert_eq!(set.len(), 5);
+
+        // the parser rejects the NaN values that would not be equal
+        let mut lex = SfzToken::lexer("comp_attack=nan pan=inf");
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+        assert_eq!(lex.next(), None);
+    }
+}
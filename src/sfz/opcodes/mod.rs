@@ -1,6 +1,10 @@
+mod aliases;
 mod defaults;
+mod display;
+mod hash;
 mod opcode;
 mod parse;
+mod value;
 
 pub use opcode::Opcode;
 
@@ -1,11 +1,93 @@
-use std::path::PathBuf;
+use std::{any::Any, path::PathBuf};
+
+use crate::sfz::Header;
+
+use crate::sfz::opcodes::{display::OpcodeValue, hash::OpcodeValueHash};
 
 use crate::sfz::opcodes::defaults::OPCODE_DEFAULT;
 use crate::sfz::types::{
-    fil_type, loop_mode, trigger, OpcodeType, UndefinedInteger, UndefinedUnsignedInteger,
-    UnknownType,
+    direction, effect_filter, effect_type, fil_type, loop_mode, loop_type, off_mode, on_off, phase,
+    sw_vel, trigger, waveform, xf_curve, OpcodeType, RawValue, UndefinedInteger,
+    UndefinedUnsignedInteger, UnknownType,
 };
 
+/// A field of an opcode, either one of its numeric parameters or its value.
+pub(crate) trait OpcodeField: OpcodeValue + OpcodeValueHash {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any + OpcodeValue + OpcodeValueHash> OpcodeField for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Defines the [`Opcode`] enum, and the methods that have to match every
+/// one of its variants, so that they all come from the same list.
+///
+/// The rest of the methods that need to know about every variant, like
+/// `params`, `value`, `Display` and `Hash`, are built on top of these.
+macro_rules! define_opcodes {
+    (
+        $(#[$enum_meta:meta])*
+        pub enum Opcode {
+            $( $(#[$meta:meta])* $variant:ident ( $($field:ty),* ) ),* $(,)?
+        }
+    ) => {
+        $(#[$enum_meta])*
+        pub enum Opcode {
+            $( $(#[$meta])* $variant($($field),*), )*
+        }
+
+        impl Opcode {
+            /// The names of all the opcodes, with the letters that stand
+            /// for their numeric parameters.
+            pub(crate) const NAMES: &'static [&'static str] = &[$( stringify!($variant) ),*];
+
+            /// Returns the name of the current opcode, with the letters that stand
+            /// for its numeric parameters, like `hiccN`
+            ///
+            /// Unlike [`str_name`][Self::str_name], it doesn't allocate.
+            pub fn name(&self) -> &'static str {
+                let name = match self {
+                    $( Opcode::$variant(..) => stringify!($variant), )*
+                };
+                name.trim_start_matches("r#")
+            }
+
+            /// Calls `f` with each one of the fields of the current opcode, in order.
+            pub(crate) fn for_each_field<'a>(&'a self, f: &mut dyn FnMut(&'a dyn OpcodeField)) {
+                match self {
+                    $(
+                        opcode_pattern!($variant [a b c d] [] $($field),*)
+                            => opcode_fields!(f [a b c d] $($field),*),
+                    )*
+                }
+            }
+        }
+    };
+}
+
+/// The pattern binding each field of a variant to one of the `$name`s.
+macro_rules! opcode_pattern {
+    ($variant:ident [$($name:ident)*] [$($bound:ident)*]) => {
+        Opcode::$variant($($bound),*)
+    };
+    ($variant:ident [$next:ident $($name:ident)*] [$($bound:ident)*] $field:ty $(, $rest:ty)*) => {
+        opcode_pattern!($variant [$($name)*] [$($bound)* $next] $($rest),*)
+    };
+}
+
+/// Calls `$f` with each field bound by [`opcode_pattern`].
+macro_rules! opcode_fields {
+    ($f:ident [$($name:ident)*]) => {{}};
+    ($f:ident [$next:ident $($name:ident)*] $field:ty $(, $rest:ty)*) => {{
+        $f($next);
+        opcode_fields!($f [$($name)*] $($rest),*)
+    }};
+}
+
+define_opcodes! {
 /// Opcodes are special keywords which instruct the player
 /// on what, when and how to play a sample.
 ///
@@ -16,6 +98,10 @@ use crate::sfz::types::{
 /// Opcodes and assigned opcode values are separated by the equal to sign (=),
 /// without spaces between the opcode and the sign.
 ///
+/// Opcodes with numeric parameters in their name (the N, X and Y letters)
+/// store them as their first fields, in that order, followed by the value.
+/// For example `eq2_gaincc11=3` is `Opcode::eqN_gainccX(2, 11, 3.)`.
+///
 /// ## Links
 ///
 /// - [All Opcodes](https://sfzformat.com/opcodes/)
@@ -27,6 +113,7 @@ use crate::sfz::types::{
 ///
 #[allow(non_camel_case_types)]
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Opcode {
     // sfz v1 opcodes -----------------------------------------------------------
     // https://sfzformat.com/misc/sfz2
@@ -52,7 +139,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [delay_ccN](https://sfzformat.com/opcodes/delay_ccN)
     ///
-    delay_ccN(f32),
+    delay_ccN(u8, f32),
 
     ///
     /// - range: 0 to 100 seconds
@@ -112,7 +199,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [offset_ccN](https://sfzformat.com/opcodes/offset_ccN)
     ///
-    offset_ccN(u32),
+    offset_ccN(u8, u32),
 
     ///
     /// - range: 0 to u32::MAX sample units
@@ -175,7 +262,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [off_mode](https://sfzformat.com/opcodes/off_mode)
     ///
-    off_mode(String),
+    off_mode(off_mode),
 
     ///
     /// - range: 0 to 1024
@@ -257,7 +344,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [loccN](https://sfzformat.com/opcodes/loccN)
     ///
-    loccN(u8),
+    loccN(u8, u8),
 
     ///
     /// - range: 0 to 127
@@ -265,7 +352,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [hiccN](https://sfzformat.com/opcodes/hiccN)
     ///
-    hiccN(u8),
+    hiccN(u8, u8),
 
     ///
     /// - range: -8192 to 8192
@@ -337,7 +424,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [sw_vel](https://sfzformat.com/opcodes/sw_vel)
     ///
-    sw_vel(String),
+    sw_vel(sw_vel),
 
     ///
     /// - range: 0 to 500 bpm
@@ -433,7 +520,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [on_loccN](https://sfzformat.com/opcodes/on_loccN)
     ///
-    on_loccN(i8),
+    on_loccN(u8, i8),
 
     ///
     /// - range: 0 to 127
@@ -441,7 +528,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [on_hiccN](https://sfzformat.com/opcodes/on_hiccN)
     ///
-    on_hiccN(i8),
+    on_hiccN(u8, i8),
 
     /// The panoramic position for the region.
     ///
@@ -481,7 +568,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [gain_ccN](https://sfzformat.com/opcodes/gain_ccN)
     ///
-    gain_ccN(f32),
+    gain_ccN(u8, f32),
 
     ///
     /// - range: -100 to 100 %
@@ -521,7 +608,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [amp_velcurve_N](https://sfzformat.com/opcodes/amp_velcurve_N)
     ///
-    amp_velcurve_N(f32),
+    amp_velcurve_N(u8, f32),
 
     ///
     /// - range: 0 to 24 dB
@@ -545,7 +632,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [xf_cccurve](https://sfzformat.com/opcodes/xf_cccurve)
     ///
-    xf_cccurve(String),
+    xf_cccurve(xf_curve),
 
     ///
     /// - range: gain, power
@@ -553,7 +640,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [xf_keycurve](https://sfzformat.com/opcodes/xf_keycurve)
     ///
-    xf_keycurve(String),
+    xf_keycurve(xf_curve),
 
     ///
     /// - range: gain, power
@@ -561,7 +648,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [xf_velcurve](https://sfzformat.com/opcodes/xf_velcurve)
     ///
-    xf_velcurve(String),
+    xf_velcurve(xf_curve),
 
     ///
     /// - range: 0 to 127
@@ -569,7 +656,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [xfin_loccN](https://sfzformat.com/opcodes/xfin_loccN)
     ///
-    xfin_loccN(u8),
+    xfin_loccN(u8, u8),
 
     ///
     /// - range: 0 to 127
@@ -577,7 +664,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [xfin_hiccN](https://sfzformat.com/opcodes/xfin_hiccN)
     ///
-    xfin_hiccN(u8),
+    xfin_hiccN(u8, u8),
 
     ///
     /// - range: 0 to 127
@@ -585,7 +672,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [xfout_loccN](https://sfzformat.com/opcodes/xfout_loccN)
     ///
-    xfout_loccN(u8),
+    xfout_loccN(u8, u8),
 
     ///
     /// - range: 0 to 127
@@ -593,7 +680,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [xfout_hiccN](https://sfzformat.com/opcodes/xfout_hiccN)
     ///
-    xfout_hiccN(u8),
+    xfout_hiccN(u8, u8),
 
     ///
     /// - range: 0 to 127
@@ -665,7 +752,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [eqN_bw](https://sfzformat.com/opcodes/eqN_bw)
     ///
-    eqN_bw(f32),
+    eqN_bw(u8, f32),
 
     ///
     /// - range: -4 to 4 octaves
@@ -673,7 +760,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [eqN_bwccX](https://sfzformat.com/opcodes/eqN_bwccX)
     ///
-    eqN_bwccX(f32),
+    eqN_bwccX(u8, u8, f32),
 
     ///
     /// - range: 0 to 30000 Hz
@@ -681,7 +768,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [eqN_freq](https://sfzformat.com/opcodes/eqN_freq)
     ///
-    eqN_freq(f32),
+    eqN_freq(u8, f32),
 
     ///
     /// - range: -30000 to 30000 Hz
@@ -689,7 +776,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [eqN_freqccX](https://sfzformat.com/opcodes/eqN_freqccX)
     ///
-    eqN_freqccX(f32),
+    eqN_freqccX(u8, u8, f32),
 
     ///
     /// - range: -30000 to 30000 Hz
@@ -697,7 +784,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [eqN_vel2freq](https://sfzformat.com/opcodes/eqN_vel2freq)
     ///
-    eqN_vel2freq(f32),
+    eqN_vel2freq(u8, f32),
 
     ///
     /// - range: -96 to 24 dB
@@ -705,7 +792,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [eqN_gain](https://sfzformat.com/opcodes/eqN_gain)
     ///
-    eqN_gain(f32),
+    eqN_gain(u8, f32),
 
     ///
     /// - range: -96 to 24 dB
@@ -713,7 +800,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [eqN_gainccX](https://sfzformat.com/opcodes/eqN_gainccX)
     ///
-    eqN_gainccX(f32),
+    eqN_gainccX(u8, u8, f32),
 
     ///
     /// - range: -96 to 24 dB
@@ -721,7 +808,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [eqN_vel2gain](https://sfzformat.com/opcodes/eqN_vel2gain)
     ///
-    eqN_vel2gain(f32),
+    eqN_vel2gain(u8, f32),
 
     ///
     /// - range: 0 to SampleRate / 2 Hz
@@ -737,7 +824,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [cutoff_ccN](https://sfzformat.com/opcodes/cutoff_ccN)
     ///
-    cutoff_ccN(i16),
+    cutoff_ccN(u8, i16),
 
     ///
     /// - range: -9600 to 9600 cents
@@ -781,8 +868,8 @@ pub enum Opcode {
 
     ///
     /// - range: lpf_1p, hpf_1p, lpf_2p, hpf_2p, bpf_2p, brf_2p, bpf_1p, brf_1p,
-    ///          apf_1p, lpf_2p_sv, hpf_2p_sv, bpf_2p_sv, brf_2p_sv, pkf_2p,
-    ///          lpf_4p, hpf_4p, lpf_6p, hpf_6p, comb, pink, lsh, hsh, peq
+    ///   apf_1p, lpf_2p_sv, hpf_2p_sv, bpf_2p_sv, brf_2p_sv, pkf_2p,
+    ///   lpf_4p, hpf_4p, lpf_6p, hpf_6p, comb, pink, lsh, hsh, peq
     /// - default: lpf_2p
     /// - version: v1
     /// - info: [fil_type](https://sfzformat.com/opcodes/fil_type)
@@ -895,7 +982,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [ampeg_attackccN](https://sfzformat.com/opcodes/ampeg_attackccN)
     ///
-    ampeg_attackccN(f32),
+    ampeg_attackccN(u8, f32),
 
     ///
     /// - range: -100 to 100 seconds
@@ -919,7 +1006,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [ampeg_decayccN](https://sfzformat.com/opcodes/ampeg_decayccN)
     ///
-    ampeg_decayccN(f32),
+    ampeg_decayccN(u8, f32),
 
     ///
     /// - range: -100 to 100 seconds
@@ -943,7 +1030,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [ampeg_delayccN](https://sfzformat.com/opcodes/ampeg_delayccN)
     ///
-    ampeg_delayccN(f32),
+    ampeg_delayccN(u8, f32),
 
     ///
     /// - range: -100 to 100 seconds
@@ -967,7 +1054,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [ampeg_holdccN](https://sfzformat.com/opcodes/ampeg_holdccN)
     ///
-    ampeg_holdccN(f32),
+    ampeg_holdccN(u8, f32),
 
     ///
     /// - range: -100 to 100 seconds
@@ -991,7 +1078,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [ampeg_releaseccN](https://sfzformat.com/opcodes/ampeg_releaseccN)
     ///
-    ampeg_releaseccN(f32),
+    ampeg_releaseccN(u8, f32),
 
     ///
     /// - range: -100 to 100 seconds
@@ -1015,7 +1102,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [ampeg_sustainccN](https://sfzformat.com/opcodes/ampeg_sustainccN)
     ///
-    ampeg_sustainccN(f32),
+    ampeg_sustainccN(u8, f32),
 
     ///
     /// - range: -100 to 100 %
@@ -1039,7 +1126,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [ampeg_startccN](https://sfzformat.com/opcodes/ampeg_startccN)
     ///
-    ampeg_startccN(f32),
+    ampeg_startccN(u8, f32),
 
     ///
     /// - range: 0 to 100 seconds
@@ -1303,7 +1390,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [amplfo_depthccN](https://sfzformat.com/opcodes/amplfo_depthccN)
     ///
-    amplfo_depthccN(f32),
+    amplfo_depthccN(u8, f32),
 
     ///
     /// - range: -10 to 10 dB
@@ -1343,7 +1430,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [amplfo_freqccN](https://sfzformat.com/opcodes/amplfo_freqccN)
     ///
-    amplfo_freqccN(f32),
+    amplfo_freqccN(u8, f32),
 
     ///
     /// - range: -200 to 200 Hz
@@ -1383,7 +1470,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [fillfo_depthccN](https://sfzformat.com/opcodes/fillfo_depthccN)
     ///
-    fillfo_depthccN(f32),
+    fillfo_depthccN(u8, f32),
 
     ///
     /// - range: -1200 to 1200 cents
@@ -1423,7 +1510,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [fillfo_freqccN](https://sfzformat.com/opcodes/fillfo_freqccN)
     ///
-    fillfo_freqccN(f32),
+    fillfo_freqccN(u8, f32),
 
     ///
     /// - range: -200 to 200 Hz
@@ -1463,7 +1550,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [pitchlfo_depthccN](https://sfzformat.com/opcodes/pitchlfo_depthccN)
     ///
-    pitchlfo_depthccN(f32),
+    pitchlfo_depthccN(u8, f32),
 
     ///
     /// - range: -1200 to 1200 cents
@@ -1503,7 +1590,7 @@ pub enum Opcode {
     /// - version: v1
     /// - info: [pitchlfo_freqccN](https://sfzformat.com/opcodes/pitchlfo_freqccN)
     ///
-    pitchlfo_freqccN(f32),
+    pitchlfo_freqccN(u8, f32),
 
     ///
     /// - range: -200 to 200 Hz
@@ -1553,7 +1640,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_samples_onccN](https://sfzformat.com/opcodes/delay_samples_onccN)
     ///
-    delay_samples_onccN(u32),
+    delay_samples_onccN(u8, u32),
 
     ///
     /// - range: None
@@ -1577,7 +1664,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [direction](https://sfzformat.com/opcodes/direction)
     ///
-    direction(String),
+    direction(direction),
 
     ///
     /// - range: None
@@ -1601,7 +1688,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [loop_type](https://sfzformat.com/opcodes/loop_type)
     ///
-    loop_type(String),
+    loop_type(loop_type),
 
     ///
     /// - range: None
@@ -1617,7 +1704,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [reverse_loccN](https://sfzformat.com/opcodes/reverse_loccN)
     ///
-    reverse_loccN(u8),
+    reverse_loccN(u8, u8),
 
     ///
     /// - range: 0 to 127
@@ -1625,7 +1712,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [reverse_hiccN](https://sfzformat.com/opcodes/reverse_hiccN)
     ///
-    reverse_hiccN(u8),
+    reverse_hiccN(u8, u8),
 
     ///
     /// - range: on, off
@@ -1633,7 +1720,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [waveguide](https://sfzformat.com/opcodes/waveguide)
     ///
-    waveguide(String),
+    waveguide(on_off),
 
     ///
     /// - range: None
@@ -1673,7 +1760,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [set_ccN](https://sfzformat.com/opcodes/set_ccN)
     ///
-    set_ccN(u8),
+    set_ccN(u8, u8),
 
     ///
     /// - range: None
@@ -1697,7 +1784,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [note_selfmask](https://sfzformat.com/opcodes/note_selfmask)
     ///
-    note_selfmask(String),
+    note_selfmask(on_off),
 
     ///
     /// - range: on, off
@@ -1705,7 +1792,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [rt_dead](https://sfzformat.com/opcodes/rt_dead)
     ///
-    rt_dead(String),
+    rt_dead(on_off),
 
     ///
     /// - range: on, off
@@ -1713,7 +1800,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [sostenuto_sw](https://sfzformat.com/opcodes/sostenuto_sw)
     ///
-    sostenuto_sw(String),
+    sostenuto_sw(on_off),
 
     ///
     /// - range: on, off
@@ -1721,7 +1808,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [sustain_sw](https://sfzformat.com/opcodes/sustain_sw)
     ///
-    sustain_sw(String),
+    sustain_sw(on_off),
 
     ///
     /// - range: 0 to 127
@@ -1769,7 +1856,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [start_loccN](https://sfzformat.com/opcodes/start_loccN)
     ///
-    start_loccN(i8),
+    start_loccN(u8, i8),
 
     ///
     /// - range: 0 to 127
@@ -1777,7 +1864,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [start_hiccN](https://sfzformat.com/opcodes/start_hiccN)
     ///
-    start_hiccN(i8),
+    start_hiccN(u8, i8),
 
     ///
     /// - range: 0 to 127
@@ -1785,7 +1872,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [stop_loccN](https://sfzformat.com/opcodes/stop_loccN)
     ///
-    stop_loccN(i8),
+    stop_loccN(u8, i8),
 
     ///
     /// - range: 0 to 127
@@ -1793,7 +1880,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [stop_hiccN](https://sfzformat.com/opcodes/stop_hiccN)
     ///
-    stop_hiccN(i8),
+    stop_hiccN(u8, i8),
 
     ///
     /// - range: normal, invert
@@ -1801,7 +1888,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [phase](https://sfzformat.com/opcodes/phase)
     ///
-    phase(String),
+    phase(phase),
 
     ///
     /// - range: 0 to 127
@@ -1833,7 +1920,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [eqN_type](https://sfzformat.com/opcodes/eqN_type)
     ///
-    eqN_type(String),
+    eqN_type(u8, String),
 
     ///
     /// - range: 0 to SampleRate / 2 Hz
@@ -1849,7 +1936,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [cutoff2_onccN](https://sfzformat.com/opcodes/cutoff2_onccN)
     ///
-    cutoff2_onccN(i16),
+    cutoff2_onccN(u8, i16),
 
     ///
     /// - range: 0 to 255
@@ -1857,7 +1944,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [cutoff2_curveccN](https://sfzformat.com/opcodes/cutoff2_curveccN)
     ///
-    cutoff2_curveccN(u8),
+    cutoff2_curveccN(u8, u8),
 
     ///
     /// - range: 0 to ms
@@ -1865,7 +1952,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [cutoff2_smoothccN](https://sfzformat.com/opcodes/cutoff2_smoothccN)
     ///
-    cutoff2_smoothccN(f32),
+    cutoff2_smoothccN(u8, f32),
 
     ///
     /// - range: 0 to ...?
@@ -1873,7 +1960,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [cutoff2_stepccN](https://sfzformat.com/opcodes/cutoff2_stepccN)
     ///
-    cutoff2_stepccN(UndefinedUnsignedInteger),
+    cutoff2_stepccN(u8, UndefinedUnsignedInteger),
 
     ///
     /// - range: 0 to 127
@@ -1897,7 +1984,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [fil2_type](https://sfzformat.com/opcodes/fil2_type)
     ///
-    fil2_type(String),
+    fil2_type(fil_type),
 
     ///
     /// - range: -9600 to 9600 cents
@@ -1921,7 +2008,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [resonance2_onccN](https://sfzformat.com/opcodes/resonance2_onccN)
     ///
-    resonance2_onccN(f32),
+    resonance2_onccN(u8, f32),
 
     ///
     /// - range: 0 to 255
@@ -1929,7 +2016,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [resonance2_curveccN](https://sfzformat.com/opcodes/resonance2_curveccN)
     ///
-    resonance2_curveccN(u8),
+    resonance2_curveccN(u8, u8),
 
     ///
     /// - range: 0 to ms
@@ -1937,7 +2024,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [resonance2_smoothccN](https://sfzformat.com/opcodes/resonance2_smoothccN)
     ///
-    resonance2_smoothccN(f32),
+    resonance2_smoothccN(u8, f32),
 
     ///
     /// - range: 0 to ...?
@@ -1945,7 +2032,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [resonance2_stepccN](https://sfzformat.com/opcodes/resonance2_stepccN)
     ///
-    resonance2_stepccN(UndefinedUnsignedInteger),
+    resonance2_stepccN(u8, UndefinedUnsignedInteger),
 
     ///
     /// - range: 0 to ms
@@ -1971,13 +2058,14 @@ pub enum Opcode {
     ///
     bend_stepdown(u16),
 
+    /// The number of points of the flexible envelope generator N.
     ///
-    /// - range: None
+    /// - range: 0 to 255
     /// - default: None
     /// - version: v2
     /// - info: [egN_points](https://sfzformat.com/opcodes/egN_points)
     ///
-    egN_points(UnknownType),
+    egN_points(u8, u8),
 
     ///
     /// - range: None
@@ -1985,7 +2073,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_timeX](https://sfzformat.com/opcodes/egN_timeX)
     ///
-    egN_timeX(f32),
+    egN_timeX(u8, u8, f32),
 
     ///
     /// - range: None
@@ -1993,7 +2081,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_timeX_onccY](https://sfzformat.com/opcodes/egN_timeX_onccY)
     ///
-    egN_timeX_onccY(f32),
+    egN_timeX_onccY(u8, u8, u8, f32),
 
     ///
     /// - range: -1 to 1
@@ -2001,7 +2089,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_levelX](https://sfzformat.com/opcodes/egN_levelX)
     ///
-    egN_levelX(f32),
+    egN_levelX(u8, u8, f32),
 
     ///
     /// - range: -1 to 1
@@ -2009,7 +2097,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_levelX_onccY](https://sfzformat.com/opcodes/egN_levelX_onccY)
     ///
-    egN_levelX_onccY(f32),
+    egN_levelX_onccY(u8, u8, u8, f32),
 
     ///
     /// - range: None
@@ -2017,15 +2105,17 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_shapeX](https://sfzformat.com/opcodes/egN_shapeX)
     ///
-    egN_shapeX(f32),
+    egN_shapeX(u8, u8, f32),
 
+    /// The index of the `<curve>` used by the segment X of the flexible
+    /// envelope generator N, in place of its `egN_shapeX`.
     ///
-    /// - range: None
+    /// - range: 0 to 255
     /// - default: None
     /// - version: v2
     /// - info: [egN_curveX](https://sfzformat.com/opcodes/egN_curveX)
     ///
-    egN_curveX(UnknownType),
+    egN_curveX(u8, u8, u8),
 
     ///
     /// - range: None
@@ -2033,7 +2123,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_sustain](https://sfzformat.com/opcodes/egN_sustain)
     ///
-    egN_sustain(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_sustain(u8, UnknownType),
 
     ///
     /// - range: None
@@ -2041,7 +2132,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_loop](https://sfzformat.com/opcodes/egN_loop)
     ///
-    egN_loop(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_loop(u8, UnknownType),
 
     ///
     /// - range: None
@@ -2049,7 +2141,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_loop_count](https://sfzformat.com/opcodes/egN_loop_count)
     ///
-    egN_loop_count(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_loop_count(u8, UnknownType),
 
     ///
     /// - range: None
@@ -2057,7 +2150,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_volume](https://sfzformat.com/opcodes/egN_volume)
     ///
-    egN_volume(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_volume(u8, UnknownType),
 
     ///
     /// - range: None
@@ -2065,7 +2159,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_volume_onccX](https://sfzformat.com/opcodes/egN_volume_onccX)
     ///
-    egN_volume_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_volume_onccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2073,7 +2168,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_amplitude](https://sfzformat.com/opcodes/egN_amplitude)
     ///
-    egN_amplitude(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_amplitude(u8, UnknownType),
 
     ///
     /// - range: None
@@ -2081,7 +2177,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_amplitude_onccX](https://sfzformat.com/opcodes/egN_amplitude_onccX)
     ///
-    egN_amplitude_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_amplitude_onccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2089,7 +2186,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_pan](https://sfzformat.com/opcodes/egN_pan)
     ///
-    egN_pan(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_pan(u8, UnknownType),
 
     ///
     /// - range: None
@@ -2097,7 +2195,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_pan_onccX](https://sfzformat.com/opcodes/egN_pan_onccX)
     ///
-    egN_pan_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_pan_onccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2105,7 +2204,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_width](https://sfzformat.com/opcodes/egN_width)
     ///
-    egN_width(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_width(u8, UnknownType),
 
     ///
     /// - range: None
@@ -2113,7 +2213,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_width_onccX](https://sfzformat.com/opcodes/egN_width_onccX)
     ///
-    egN_width_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_width_onccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2121,15 +2222,16 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_pan_curve](https://sfzformat.com/opcodes/egN_pan_curve)
     ///
-    egN_pan_curve(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_pan_curve(u8, UnknownType),
 
     ///
-    /// - range: None
+    /// - range: 0 to 255
     /// - default: None
     /// - version: v2
     /// - info: [egN_pan_curveccX](https://sfzformat.com/opcodes/egN_pan_curveccX)
     ///
-    egN_pan_curveccX(UnknownType),
+    egN_pan_curveccX(u8, u8, u8),
 
     ///
     /// - range: None
@@ -2137,7 +2239,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_freq_lfoX](https://sfzformat.com/opcodes/egN_freq_lfoX)
     ///
-    egN_freq_lfoX(UnknownType),
+    egN_freq_lfoX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2145,7 +2247,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_depth_lfoX](https://sfzformat.com/opcodes/egN_depth_lfoX)
     ///
-    egN_depth_lfoX(UnknownType),
+    egN_depth_lfoX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2153,7 +2255,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_depthadd_lfoX](https://sfzformat.com/opcodes/egN_depthadd_lfoX)
     ///
-    egN_depthadd_lfoX(UnknownType),
+    egN_depthadd_lfoX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2161,7 +2263,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_pitch](https://sfzformat.com/opcodes/egN_pitch)
     ///
-    egN_pitch(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_pitch(u8, UnknownType),
 
     ///
     /// - range: None
@@ -2169,7 +2272,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_pitch_onccX](https://sfzformat.com/opcodes/egN_pitch_onccX)
     ///
-    egN_pitch_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_pitch_onccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2177,7 +2281,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_cutoff](https://sfzformat.com/opcodes/egN_cutoff)
     ///
-    egN_cutoff(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_cutoff(u8, UnknownType),
 
     ///
     /// - range: None
@@ -2185,7 +2290,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_cutoff_onccX](https://sfzformat.com/opcodes/egN_cutoff_onccX)
     ///
-    egN_cutoff_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_cutoff_onccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2193,7 +2299,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_cutoff2](https://sfzformat.com/opcodes/egN_cutoff2)
     ///
-    egN_cutoff2(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_cutoff2(u8, UnknownType),
 
     ///
     /// - range: None
@@ -2201,7 +2308,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_cutoff2_onccX](https://sfzformat.com/opcodes/egN_cutoff2_onccX)
     ///
-    egN_cutoff2_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_cutoff2_onccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2209,7 +2317,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_resonance](https://sfzformat.com/opcodes/egN_resonance)
     ///
-    egN_resonance(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_resonance(u8, UnknownType),
 
     ///
     /// - range: None
@@ -2217,7 +2326,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_resonance_onccX](https://sfzformat.com/opcodes/egN_resonance_onccX)
     ///
-    egN_resonance_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_resonance_onccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2225,7 +2335,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_resonance2](https://sfzformat.com/opcodes/egN_resonance2)
     ///
-    egN_resonance2(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_resonance2(u8, UnknownType),
 
     ///
     /// - range: None
@@ -2233,7 +2344,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_resonance2_onccX](https://sfzformat.com/opcodes/egN_resonance2_onccX)
     ///
-    egN_resonance2_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_resonance2_onccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2241,7 +2353,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_eqXfreq](https://sfzformat.com/opcodes/egN_eqXfreq)
     ///
-    egN_eqXfreq(UnknownType),
+    egN_eqXfreq(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2249,7 +2361,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_eqXfreq_onccY](https://sfzformat.com/opcodes/egN_eqXfreq_onccY)
     ///
-    egN_eqXfreq_onccY(UnknownType),
+    egN_eqXfreq_onccY(u8, u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2257,7 +2369,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_eqXbw](https://sfzformat.com/opcodes/egN_eqXbw)
     ///
-    egN_eqXbw(UnknownType),
+    egN_eqXbw(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2265,7 +2377,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_eqXbw_onccY](https://sfzformat.com/opcodes/egN_eqXbw_onccY)
     ///
-    egN_eqXbw_onccY(UnknownType),
+    egN_eqXbw_onccY(u8, u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2273,7 +2385,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_eqXgain](https://sfzformat.com/opcodes/egN_eqXgain)
     ///
-    egN_eqXgain(UnknownType),
+    egN_eqXgain(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2281,7 +2393,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_eqXgain_onccY](https://sfzformat.com/opcodes/egN_eqXgain_onccY)
     ///
-    egN_eqXgain_onccY(UnknownType),
+    egN_eqXgain_onccY(u8, u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2289,7 +2401,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_freq](https://sfzformat.com/opcodes/lfoN_freq)
     ///
-    lfoN_freq(f32),
+    lfoN_freq(u8, f32),
 
     ///
     /// - range: None
@@ -2297,7 +2409,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_freq_onccX](https://sfzformat.com/opcodes/lfoN_freq_onccX)
     ///
-    lfoN_freq_onccX(UnknownType),
+    lfoN_freq_onccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2305,7 +2417,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_freq_smoothccX](https://sfzformat.com/opcodes/lfoN_freq_smoothccX)
     ///
-    lfoN_freq_smoothccX(UnknownType),
+    lfoN_freq_smoothccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2313,7 +2425,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_freq_stepccX](https://sfzformat.com/opcodes/lfoN_freq_stepccX)
     ///
-    lfoN_freq_stepccX(UnknownType),
+    lfoN_freq_stepccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2321,7 +2433,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_delay](https://sfzformat.com/opcodes/lfoN_delay)
     ///
-    lfoN_delay(f32),
+    lfoN_delay(u8, f32),
 
     ///
     /// - range: None
@@ -2329,7 +2441,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_delay_onccX](https://sfzformat.com/opcodes/lfoN_delay_onccX)
     ///
-    lfoN_delay_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_delay_onccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2337,7 +2450,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_fade](https://sfzformat.com/opcodes/lfoN_fade)
     ///
-    lfoN_fade(f32),
+    lfoN_fade(u8, f32),
 
     ///
     /// - range: None
@@ -2345,7 +2458,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_fade_onccX](https://sfzformat.com/opcodes/lfoN_fade_onccX)
     ///
-    lfoN_fade_onccX(f32),
+    lfoN_fade_onccX(u8, u8, f32),
 
     ///
     /// - range: 0 to 1
@@ -2353,7 +2466,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_phase](https://sfzformat.com/opcodes/lfoN_phase)
     ///
-    lfoN_phase(f32),
+    lfoN_phase(u8, f32),
 
     ///
     /// - range: None
@@ -2361,7 +2474,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_phase_onccX](https://sfzformat.com/opcodes/lfoN_phase_onccX)
     ///
-    lfoN_phase_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_phase_onccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2369,7 +2483,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_count](https://sfzformat.com/opcodes/lfoN_count)
     ///
-    lfoN_count(UndefinedInteger),
+    lfoN_count(u8, UndefinedInteger),
 
     ///
     /// - range: None
@@ -2377,7 +2491,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_wave](https://sfzformat.com/opcodes/lfoN_wave)
     ///
-    lfoN_wave(UndefinedInteger),
+    lfoN_wave(u8, UndefinedInteger),
 
     ///
     /// - range: None
@@ -2385,7 +2499,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_steps](https://sfzformat.com/opcodes/lfoN_steps)
     ///
-    lfoN_steps(UndefinedInteger),
+    lfoN_steps(u8, UndefinedInteger),
 
     ///
     /// - range: -100 to 100 percent
@@ -2393,7 +2507,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_stepX](https://sfzformat.com/opcodes/lfoN_stepX)
     ///
-    lfoN_stepX(f32),
+    lfoN_stepX(u8, u8, f32),
 
     ///
     /// - range: None
@@ -2401,15 +2515,16 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_stepX_onccY](https://sfzformat.com/opcodes/lfoN_stepX_onccY)
     ///
-    lfoN_stepX_onccY(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_stepX_onccY(u8, u8, u8, UnknownType),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [lfoN_smooth](https://sfzformat.com/opcodes/lfoN_smooth)
     ///
-    lfoN_smooth(UnknownType),
+    lfoN_smooth(u8, f32),
 
     ///
     /// - range: None
@@ -2417,7 +2532,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_smooth_onccX](https://sfzformat.com/opcodes/lfoN_smooth_onccX)
     ///
-    lfoN_smooth_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_smooth_onccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2425,7 +2541,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_volume](https://sfzformat.com/opcodes/lfoN_volume)
     ///
-    lfoN_volume(UnknownType),
+    lfoN_volume(u8, RawValue),
 
     ///
     /// - range: None
@@ -2433,7 +2549,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_volume_onccX](https://sfzformat.com/opcodes/lfoN_volume_onccX)
     ///
-    lfoN_volume_onccX(UnknownType),
+    lfoN_volume_onccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2441,7 +2557,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_volume_smoothccX](https://sfzformat.com/opcodes/lfoN_volume_smoothccX)
     ///
-    lfoN_volume_smoothccX(UnknownType),
+    lfoN_volume_smoothccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2449,7 +2565,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_volume_stepccX](https://sfzformat.com/opcodes/lfoN_volume_stepccX)
     ///
-    lfoN_volume_stepccX(UnknownType),
+    lfoN_volume_stepccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2457,7 +2573,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_amplitude](https://sfzformat.com/opcodes/lfoN_amplitude)
     ///
-    lfoN_amplitude(UnknownType),
+    lfoN_amplitude(u8, RawValue),
 
     ///
     /// - range: None
@@ -2465,7 +2581,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_amplitude_onccX](https://sfzformat.com/opcodes/lfoN_amplitude_onccX)
     ///
-    lfoN_amplitude_onccX(UnknownType),
+    lfoN_amplitude_onccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2473,7 +2589,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_amplitude_smoothccX](https://sfzformat.com/opcodes/lfoN_amplitude_smoothccX)
     ///
-    lfoN_amplitude_smoothccX(UnknownType),
+    lfoN_amplitude_smoothccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2481,7 +2597,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_amplitude_stepccX](https://sfzformat.com/opcodes/lfoN_amplitude_stepccX)
     ///
-    lfoN_amplitude_stepccX(UnknownType),
+    lfoN_amplitude_stepccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2489,7 +2605,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_pan](https://sfzformat.com/opcodes/lfoN_pan)
     ///
-    lfoN_pan(UnknownType),
+    lfoN_pan(u8, RawValue),
 
     ///
     /// - range: None
@@ -2497,7 +2613,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_pan_onccX](https://sfzformat.com/opcodes/lfoN_pan_onccX)
     ///
-    lfoN_pan_onccX(UnknownType),
+    lfoN_pan_onccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2505,7 +2621,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_pan_smoothccX](https://sfzformat.com/opcodes/lfoN_pan_smoothccX)
     ///
-    lfoN_pan_smoothccX(UnknownType),
+    lfoN_pan_smoothccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2513,7 +2629,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_pan_stepccX](https://sfzformat.com/opcodes/lfoN_pan_stepccX)
     ///
-    lfoN_pan_stepccX(UnknownType),
+    lfoN_pan_stepccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2521,7 +2637,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_width](https://sfzformat.com/opcodes/lfoN_width)
     ///
-    lfoN_width(UnknownType),
+    lfoN_width(u8, RawValue),
 
     ///
     /// - range: None
@@ -2529,7 +2645,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_width_onccX](https://sfzformat.com/opcodes/lfoN_width_onccX)
     ///
-    lfoN_width_onccX(UnknownType),
+    lfoN_width_onccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2537,7 +2653,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_width_smoothccX](https://sfzformat.com/opcodes/lfoN_width_smoothccX)
     ///
-    lfoN_width_smoothccX(UnknownType),
+    lfoN_width_smoothccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2545,7 +2661,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_width_stepccX](https://sfzformat.com/opcodes/lfoN_width_stepccX)
     ///
-    lfoN_width_stepccX(UnknownType),
+    lfoN_width_stepccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2553,7 +2669,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_freq_lfoX](https://sfzformat.com/opcodes/lfoN_freq_lfoX)
     ///
-    lfoN_freq_lfoX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_freq_lfoX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2561,7 +2678,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_depth_lfoX](https://sfzformat.com/opcodes/lfoN_depth_lfoX)
     ///
-    lfoN_depth_lfoX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_depth_lfoX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2569,7 +2687,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_depthadd_lfoX](https://sfzformat.com/opcodes/lfoN_depthadd_lfoX)
     ///
-    lfoN_depthadd_lfoX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_depthadd_lfoX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2577,7 +2696,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_pitch](https://sfzformat.com/opcodes/lfoN_pitch)
     ///
-    lfoN_pitch(UnknownType),
+    lfoN_pitch(u8, RawValue),
 
     ///
     /// - range: None
@@ -2585,7 +2704,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_pitch_onccX](https://sfzformat.com/opcodes/lfoN_pitch_onccX)
     ///
-    lfoN_pitch_onccX(UnknownType),
+    lfoN_pitch_onccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2593,7 +2712,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_pitch_smoothccX](https://sfzformat.com/opcodes/lfoN_pitch_smoothccX)
     ///
-    lfoN_pitch_smoothccX(UnknownType),
+    lfoN_pitch_smoothccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2601,7 +2720,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_pitch_stepccX](https://sfzformat.com/opcodes/lfoN_pitch_stepccX)
     ///
-    lfoN_pitch_stepccX(UnknownType),
+    lfoN_pitch_stepccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -2609,7 +2728,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_cutoff](https://sfzformat.com/opcodes/lfoN_cutoff)
     ///
-    lfoN_cutoff(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_cutoff(u8, UnknownType),
 
     ///
     /// - range: None
@@ -2617,15 +2737,16 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_cutoff_onccX](https://sfzformat.com/opcodes/lfoN_cutoff_onccX)
     ///
-    lfoN_cutoff_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_cutoff_onccX(u8, u8, UnknownType),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [lfoN_cutoff_smoothccX](https://sfzformat.com/opcodes/lfoN_cutoff_smoothccX)
     ///
-    lfoN_cutoff_smoothccX(UnknownType),
+    lfoN_cutoff_smoothccX(u8, u8, f32),
 
     ///
     /// - range: None
@@ -2633,7 +2754,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_cutoff_stepccX](https://sfzformat.com/opcodes/lfoN_cutoff_stepccX)
     ///
-    lfoN_cutoff_stepccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_cutoff_stepccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2641,7 +2763,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_cutoff2](https://sfzformat.com/opcodes/lfoN_cutoff2)
     ///
-    lfoN_cutoff2(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_cutoff2(u8, UnknownType),
 
     ///
     /// - range: None
@@ -2649,15 +2772,16 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_cutoff2_onccX](https://sfzformat.com/opcodes/lfoN_cutoff2_onccX)
     ///
-    lfoN_cutoff2_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_cutoff2_onccX(u8, u8, UnknownType),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [lfoN_cutoff2_smoothccX](https://sfzformat.com/opcodes/lfoN_cutoff2_smoothccX)
     ///
-    lfoN_cutoff2_smoothccX(UnknownType),
+    lfoN_cutoff2_smoothccX(u8, u8, f32),
 
     ///
     /// - range: None
@@ -2665,7 +2789,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_cutoff2_stepccX](https://sfzformat.com/opcodes/lfoN_cutoff2_stepccX)
     ///
-    lfoN_cutoff2_stepccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_cutoff2_stepccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2673,7 +2798,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_resonance](https://sfzformat.com/opcodes/lfoN_resonance)
     ///
-    lfoN_resonance(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_resonance(u8, UnknownType),
 
     ///
     /// - range: None
@@ -2681,15 +2807,16 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_resonance_onccX](https://sfzformat.com/opcodes/lfoN_resonance_onccX)
     ///
-    lfoN_resonance_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_resonance_onccX(u8, u8, UnknownType),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [lfoN_resonance_smoothccX](https://sfzformat.com/opcodes/lfoN_resonance_smoothccX)
     ///
-    lfoN_resonance_smoothccX(UnknownType),
+    lfoN_resonance_smoothccX(u8, u8, f32),
 
     ///
     /// - range: None
@@ -2697,7 +2824,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_resonance_stepccX](https://sfzformat.com/opcodes/lfoN_resonance_stepccX)
     ///
-    lfoN_resonance_stepccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_resonance_stepccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2705,7 +2833,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_resonance2](https://sfzformat.com/opcodes/lfoN_resonance2)
     ///
-    lfoN_resonance2(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_resonance2(u8, UnknownType),
 
     ///
     /// - range: None
@@ -2713,15 +2842,16 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_resonance2_onccX](https://sfzformat.com/opcodes/lfoN_resonance2_onccX)
     ///
-    lfoN_resonance2_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_resonance2_onccX(u8, u8, UnknownType),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [lfoN_resonance2_smoothccX](https://sfzformat.com/opcodes/lfoN_resonance2_smoothccX)
     ///
-    lfoN_resonance2_smoothccX(UnknownType),
+    lfoN_resonance2_smoothccX(u8, u8, f32),
 
     ///
     /// - range: None
@@ -2729,7 +2859,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_resonance2_stepccX](https://sfzformat.com/opcodes/lfoN_resonance2_stepccX)
     ///
-    lfoN_resonance2_stepccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_resonance2_stepccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2737,7 +2868,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_eqXfreq](https://sfzformat.com/opcodes/lfoN_eqXfreq)
     ///
-    lfoN_eqXfreq(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_eqXfreq(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2745,15 +2877,16 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_eqXfreq_onccY](https://sfzformat.com/opcodes/lfoN_eqXfreq_onccY)
     ///
-    lfoN_eqXfreq_onccY(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_eqXfreq_onccY(u8, u8, u8, UnknownType),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [lfoN_eqXfreq_smoothccY](https://sfzformat.com/opcodes/lfoN_eqXfreq_smoothccY)
     ///
-    lfoN_eqXfreq_smoothccY(UnknownType),
+    lfoN_eqXfreq_smoothccY(u8, u8, u8, f32),
 
     ///
     /// - range: None
@@ -2761,7 +2894,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_eqXfreq_stepccY](https://sfzformat.com/opcodes/lfoN_eqXfreq_stepccY)
     ///
-    lfoN_eqXfreq_stepccY(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_eqXfreq_stepccY(u8, u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2769,7 +2903,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_eqXbw](https://sfzformat.com/opcodes/lfoN_eqXbw)
     ///
-    lfoN_eqXbw(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_eqXbw(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2777,15 +2912,16 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_eqXbw_onccY](https://sfzformat.com/opcodes/lfoN_eqXbw_onccY)
     ///
-    lfoN_eqXbw_onccY(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_eqXbw_onccY(u8, u8, u8, UnknownType),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [lfoN_eqXbw_smoothccY](https://sfzformat.com/opcodes/lfoN_eqXbw_smoothccY)
     ///
-    lfoN_eqXbw_smoothccY(UnknownType),
+    lfoN_eqXbw_smoothccY(u8, u8, u8, f32),
 
     ///
     /// - range: None
@@ -2793,7 +2929,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_eqXbw_stepccY](https://sfzformat.com/opcodes/lfoN_eqXbw_stepccY)
     ///
-    lfoN_eqXbw_stepccY(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_eqXbw_stepccY(u8, u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2801,7 +2938,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_eqXgain](https://sfzformat.com/opcodes/lfoN_eqXgain)
     ///
-    lfoN_eqXgain(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_eqXgain(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -2809,15 +2947,16 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_eqXgain_onccY](https://sfzformat.com/opcodes/lfoN_eqXgain_onccY)
     ///
-    lfoN_eqXgain_onccY(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_eqXgain_onccY(u8, u8, u8, UnknownType),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [lfoN_eqXgain_smoothccY](https://sfzformat.com/opcodes/lfoN_eqXgain_smoothccY)
     ///
-    lfoN_eqXgain_smoothccY(UnknownType),
+    lfoN_eqXgain_smoothccY(u8, u8, u8, f32),
 
     ///
     /// - range: None
@@ -2825,7 +2964,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_eqXgain_stepccY](https://sfzformat.com/opcodes/lfoN_eqXgain_stepccY)
     ///
-    lfoN_eqXgain_stepccY(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_eqXgain_stepccY(u8, u8, u8, UnknownType),
 
     ///
     /// - range: -1 to 1
@@ -2833,7 +2973,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [vN](https://sfzformat.com/opcodes/vN)
     ///
-    vN(f32),
+    vN(u8, f32),
 
     ///
     /// - range: main, aux1, aux2, aux3, aux4, aux5, aux6, aux7, aux8, fx1, fx2, fx3, fx4, midi
@@ -2843,6 +2983,15 @@ pub enum Opcode {
     ///
     bus(String),
 
+    /// Sets the processing order of an effect.
+    ///
+    /// - range: 0 to 14
+    /// - default: None
+    /// - version: v2
+    /// - info: [dsp_order](https://sfzformat.com/opcodes/dsp_order)
+    ///
+    dsp_order(u8),
+
     ///
     /// - range: 0 to 100 %
     /// - default: 0
@@ -2865,7 +3014,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [type](https://sfzformat.com/opcodes/type)
     ///
-    r#type(String),
+    r#type(effect_type),
 
     // aria extension opcodes --------------------------------------------------
     // https://sfzformat.com/extensions/aria/
@@ -2875,7 +3024,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [label_ccN](https://sfzformat.com/opcodes/label_ccN)
     ///
-    label_ccN(String),
+    label_ccN(u8, String),
 
     ///
     /// - range: None
@@ -2911,7 +3060,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [set_hdccN](https://sfzformat.com/opcodes/set_hdccN)
     ///
-    set_hdccN(f32),
+    set_hdccN(u8, f32),
 
     ///
     /// - range: None
@@ -3039,7 +3188,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lohdccN](https://sfzformat.com/opcodes/lohdccN)
     ///
-    lohdccN(f32),
+    lohdccN(u8, f32),
 
     ///
     /// - range: 0 to 1
@@ -3047,7 +3196,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [hihdccN](https://sfzformat.com/opcodes/hihdccN)
     ///
-    hihdccN(f32),
+    hihdccN(u8, f32),
 
     ///
     /// - range: None
@@ -3079,7 +3228,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [varNN_mod](https://sfzformat.com/opcodes/varNN_mod)
     ///
-    varNN_mod(String),
+    varNN_mod(u8, String),
 
     ///
     /// - range: 0 to 1
@@ -3087,7 +3236,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [varNN_onccX](https://sfzformat.com/opcodes/varNN_onccX)
     ///
-    varNN_onccX(f32),
+    varNN_onccX(u8, u8, f32),
 
     ///
     /// - range: 0 to 255
@@ -3095,7 +3244,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [varNN_curveccX](https://sfzformat.com/opcodes/varNN_curveccX)
     ///
-    varNN_curveccX(u8),
+    varNN_curveccX(u8, u8, u8),
 
     ///
     /// - range: None
@@ -3103,7 +3252,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [varNN_target](https://sfzformat.com/opcodes/varNN_target)
     ///
-    varNN_target(),
+    varNN_target(u8),
 
     ///
     /// - range: 0 to 1
@@ -3111,7 +3260,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [on_lohdccN](https://sfzformat.com/opcodes/on_lohdccN)
     ///
-    on_lohdccN(f32),
+    on_lohdccN(u8, f32),
 
     ///
     /// - range: 0 to 1
@@ -3119,7 +3268,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [on_hihdccN](https://sfzformat.com/opcodes/on_hihdccN)
     ///
-    on_hihdccN(f32),
+    on_hihdccN(u8, f32),
 
     ///
     /// - range: 0 to 1
@@ -3127,7 +3276,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [start_lohdccN](https://sfzformat.com/opcodes/start_lohdccN)
     ///
-    start_lohdccN(f32),
+    start_lohdccN(u8, f32),
 
     ///
     /// - range: 0 to 1
@@ -3135,7 +3284,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [start_hihdccN](https://sfzformat.com/opcodes/start_hihdccN)
     ///
-    start_hihdccN(f32),
+    start_hihdccN(u8, f32),
 
     ///
     /// - range: 0 to 1
@@ -3143,7 +3292,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [stop_lohdccN](https://sfzformat.com/opcodes/stop_lohdccN)
     ///
-    stop_lohdccN(f32),
+    stop_lohdccN(u8, f32),
 
     ///
     /// - range: 0 to 1
@@ -3151,7 +3300,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [stop_hihdccN](https://sfzformat.com/opcodes/stop_hihdccN)
     ///
-    stop_hihdccN(f32),
+    stop_hihdccN(u8, f32),
 
     ///
     /// - range: None
@@ -3183,7 +3332,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [amplitude_onccN](https://sfzformat.com/opcodes/amplitude_onccN)
     ///
-    amplitude_onccN(f32),
+    amplitude_onccN(u8, f32),
 
     ///
     /// - range: 0 to 255
@@ -3191,15 +3340,15 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [amplitude_curveccN](https://sfzformat.com/opcodes/amplitude_curveccN)
     ///
-    amplitude_curveccN(u8),
+    amplitude_curveccN(u8, u8),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [amplitude_smoothccN](https://sfzformat.com/opcodes/amplitude_smoothccN)
     ///
-    amplitude_smoothccN(),
+    amplitude_smoothccN(u8, f32),
 
     ///
     /// - range: 0 to 100 %
@@ -3263,7 +3412,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [eqN_dynamic](https://sfzformat.com/opcodes/eqN_dynamic)
     ///
-    eqN_dynamic(u8),
+    eqN_dynamic(u8, u8),
 
     ///
     /// - range: None
@@ -3442,7 +3591,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_ampeg](https://sfzformat.com/opcodes/egN_ampeg)
     ///
-    egN_ampeg(UnknownType),
+    egN_ampeg(u8, RawValue),
 
     ///
     /// - range: None
@@ -3450,7 +3599,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_waveX](https://sfzformat.com/opcodes/lfoN_waveX)
     ///
-    lfoN_waveX(UndefinedInteger),
+    lfoN_waveX(u8, u8, UndefinedInteger),
 
     ///
     /// - range: None
@@ -3458,7 +3607,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_offset](https://sfzformat.com/opcodes/lfoN_offset)
     ///
-    lfoN_offset(f32),
+    lfoN_offset(u8, f32),
 
     ///
     /// - range: None
@@ -3466,7 +3615,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_ratio](https://sfzformat.com/opcodes/lfoN_ratio)
     ///
-    lfoN_ratio(f32),
+    lfoN_ratio(u8, f32),
 
     ///
     /// - range: None
@@ -3474,7 +3623,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_scale](https://sfzformat.com/opcodes/lfoN_scale)
     ///
-    lfoN_scale(f32),
+    lfoN_scale(u8, f32),
 
     ///
     /// - range: 0 to 255
@@ -3509,7 +3658,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [noise_filter](https://sfzformat.com/opcodes/noise_filter)
     ///
-    noise_filter(String),
+    noise_filter(effect_filter),
 
     ///
     /// - range: on, off
@@ -3517,7 +3666,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [noise_stereo](https://sfzformat.com/opcodes/noise_stereo)
     ///
-    noise_stereo(String),
+    noise_stereo(on_off),
 
     ///
     /// - range: -96 to 24 dB
@@ -3533,7 +3682,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [noise_level_onccN](https://sfzformat.com/opcodes/noise_level_onccN)
     ///
-    noise_level_onccN(f32),
+    noise_level_onccN(u8, f32),
 
     ///
     /// - range: 0 to ms
@@ -3541,7 +3690,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [noise_level_smoothccN](https://sfzformat.com/opcodes/noise_level_smoothccN)
     ///
-    noise_level_smoothccN(f32),
+    noise_level_smoothccN(u8, f32),
 
     ///
     /// - range: 0 to 100
@@ -3557,7 +3706,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [noise_step_onccN](https://sfzformat.com/opcodes/noise_step_onccN)
     ///
-    noise_step_onccN(u8),
+    noise_step_onccN(u8, u8),
 
     ///
     /// - range: 0 to 100
@@ -3573,7 +3722,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [noise_tone_onccN](https://sfzformat.com/opcodes/noise_tone_onccN)
     ///
-    noise_tone_onccN(u8),
+    noise_tone_onccN(u8, u8),
 
     ///
     /// - range: None
@@ -3581,7 +3730,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_decim](https://sfzformat.com/opcodes/egN_decim)
     ///
-    egN_decim(UnknownType),
+    egN_decim(u8, RawValue),
 
     ///
     /// - range: None
@@ -3589,7 +3738,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_decim_onccX](https://sfzformat.com/opcodes/egN_decim_onccX)
     ///
-    egN_decim_onccX(UnknownType),
+    egN_decim_onccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -3597,7 +3746,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_bitred](https://sfzformat.com/opcodes/egN_bitred)
     ///
-    egN_bitred(UnknownType),
+    egN_bitred(u8, RawValue),
 
     ///
     /// - range: None
@@ -3605,7 +3754,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_bitred_onccX](https://sfzformat.com/opcodes/egN_bitred_onccX)
     ///
-    egN_bitred_onccX(UnknownType),
+    egN_bitred_onccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -3613,7 +3762,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_rectify](https://sfzformat.com/opcodes/egN_rectify)
     ///
-    egN_rectify(UnknownType),
+    egN_rectify(u8, RawValue),
 
     ///
     /// - range: None
@@ -3621,7 +3770,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_rectify_onccX](https://sfzformat.com/opcodes/egN_rectify_onccX)
     ///
-    egN_rectify_onccX(UnknownType),
+    egN_rectify_onccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -3629,7 +3778,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_ringmod](https://sfzformat.com/opcodes/egN_ringmod)
     ///
-    egN_ringmod(UnknownType),
+    egN_ringmod(u8, RawValue),
 
     ///
     /// - range: None
@@ -3637,7 +3786,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_ringmod_onccX](https://sfzformat.com/opcodes/egN_ringmod_onccX)
     ///
-    egN_ringmod_onccX(UnknownType),
+    egN_ringmod_onccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -3645,7 +3794,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_noiselevel](https://sfzformat.com/opcodes/egN_noiselevel)
     ///
-    egN_noiselevel(UnknownType),
+    egN_noiselevel(u8, RawValue),
 
     ///
     /// - range: None
@@ -3653,7 +3802,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_noiselevel_onccX](https://sfzformat.com/opcodes/egN_noiselevel_onccX)
     ///
-    egN_noiselevel_onccX(UnknownType),
+    egN_noiselevel_onccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -3661,7 +3810,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_noisestep](https://sfzformat.com/opcodes/egN_noisestep)
     ///
-    egN_noisestep(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_noisestep(u8, UnknownType),
 
     ///
     /// - range: None
@@ -3669,7 +3819,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_noisestep_onccX](https://sfzformat.com/opcodes/egN_noisestep_onccX)
     ///
-    egN_noisestep_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_noisestep_onccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -3677,7 +3828,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_noisetone](https://sfzformat.com/opcodes/egN_noisetone)
     ///
-    egN_noisetone(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_noisetone(u8, UnknownType),
 
     ///
     /// - range: None
@@ -3685,7 +3837,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_noisetone_onccX](https://sfzformat.com/opcodes/egN_noisetone_onccX)
     ///
-    egN_noisetone_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    egN_noisetone_onccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -3693,7 +3846,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_driveshape](https://sfzformat.com/opcodes/egN_driveshape)
     ///
-    egN_driveshape(UnknownType),
+    egN_driveshape(u8, RawValue),
 
     ///
     /// - range: None
@@ -3701,7 +3854,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [egN_driveshape_onccX](https://sfzformat.com/opcodes/egN_driveshape_onccX)
     ///
-    egN_driveshape_onccX(UnknownType),
+    egN_driveshape_onccX(u8, u8, RawValue),
 
     ///
     /// - range: None
@@ -3709,7 +3862,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_decim](https://sfzformat.com/opcodes/lfoN_decim)
     ///
-    lfoN_decim(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_decim(u8, UnknownType),
 
     ///
     /// - range: None
@@ -3717,15 +3871,16 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_decim_onccX](https://sfzformat.com/opcodes/lfoN_decim_onccX)
     ///
-    lfoN_decim_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_decim_onccX(u8, u8, UnknownType),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [lfoN_decim_smoothccX](https://sfzformat.com/opcodes/lfoN_decim_smoothccX)
     ///
-    lfoN_decim_smoothccX(UnknownType),
+    lfoN_decim_smoothccX(u8, u8, f32),
 
     ///
     /// - range: None
@@ -3733,7 +3888,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_decim_stepccX](https://sfzformat.com/opcodes/lfoN_decim_stepccX)
     ///
-    lfoN_decim_stepccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_decim_stepccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -3741,7 +3897,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_bitred](https://sfzformat.com/opcodes/lfoN_bitred)
     ///
-    lfoN_bitred(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_bitred(u8, UnknownType),
 
     ///
     /// - range: None
@@ -3749,15 +3906,16 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_bitred_onccX](https://sfzformat.com/opcodes/lfoN_bitred_onccX)
     ///
-    lfoN_bitred_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_bitred_onccX(u8, u8, UnknownType),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [lfoN_bitred_smoothccX](https://sfzformat.com/opcodes/lfoN_bitred_smoothccX)
     ///
-    lfoN_bitred_smoothccX(UnknownType),
+    lfoN_bitred_smoothccX(u8, u8, f32),
 
     ///
     /// - range: None
@@ -3765,7 +3923,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_bitred_stepccX](https://sfzformat.com/opcodes/lfoN_bitred_stepccX)
     ///
-    lfoN_bitred_stepccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_bitred_stepccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -3773,7 +3932,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_noiselevel](https://sfzformat.com/opcodes/lfoN_noiselevel)
     ///
-    lfoN_noiselevel(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_noiselevel(u8, UnknownType),
 
     ///
     /// - range: None
@@ -3781,15 +3941,16 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_noiselevel_onccX](https://sfzformat.com/opcodes/lfoN_noiselevel_onccX)
     ///
-    lfoN_noiselevel_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_noiselevel_onccX(u8, u8, UnknownType),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [lfoN_noiselevel_smoothccX](https://sfzformat.com/opcodes/lfoN_noiselevel_smoothccX)
     ///
-    lfoN_noiselevel_smoothccX(UnknownType),
+    lfoN_noiselevel_smoothccX(u8, u8, f32),
 
     ///
     /// - range: None
@@ -3797,7 +3958,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_noiselevel_stepccX](https://sfzformat.com/opcodes/lfoN_noiselevel_stepccX)
     ///
-    lfoN_noiselevel_stepccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_noiselevel_stepccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -3805,7 +3967,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_noisestep](https://sfzformat.com/opcodes/lfoN_noisestep)
     ///
-    lfoN_noisestep(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_noisestep(u8, UnknownType),
 
     ///
     /// - range: None
@@ -3813,15 +3976,16 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_noisestep_onccX](https://sfzformat.com/opcodes/lfoN_noisestep_onccX)
     ///
-    lfoN_noisestep_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_noisestep_onccX(u8, u8, UnknownType),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [lfoN_noisestep_smoothccX](https://sfzformat.com/opcodes/lfoN_noisestep_smoothccX)
     ///
-    lfoN_noisestep_smoothccX(UnknownType),
+    lfoN_noisestep_smoothccX(u8, u8, f32),
 
     ///
     /// - range: None
@@ -3829,7 +3993,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_noisestep_stepccX](https://sfzformat.com/opcodes/lfoN_noisestep_stepccX)
     ///
-    lfoN_noisestep_stepccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_noisestep_stepccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -3837,7 +4002,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_noisetone](https://sfzformat.com/opcodes/lfoN_noisetone)
     ///
-    lfoN_noisetone(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_noisetone(u8, UnknownType),
 
     ///
     /// - range: None
@@ -3845,15 +4011,16 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_noisetone_onccX](https://sfzformat.com/opcodes/lfoN_noisetone_onccX)
     ///
-    lfoN_noisetone_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_noisetone_onccX(u8, u8, UnknownType),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [lfoN_noisetone_smoothccX](https://sfzformat.com/opcodes/lfoN_noisetone_smoothccX)
     ///
-    lfoN_noisetone_smoothccX(UnknownType),
+    lfoN_noisetone_smoothccX(u8, u8, f32),
 
     ///
     /// - range: None
@@ -3861,7 +4028,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_noisetone_stepccX](https://sfzformat.com/opcodes/lfoN_noisetone_stepccX)
     ///
-    lfoN_noisetone_stepccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_noisetone_stepccX(u8, u8, UnknownType),
 
     ///
     /// - range: None
@@ -3869,7 +4037,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_drive](https://sfzformat.com/opcodes/lfoN_drive)
     ///
-    lfoN_drive(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_drive(u8, UnknownType),
 
     ///
     /// - range: None
@@ -3877,15 +4046,16 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_drive_onccX](https://sfzformat.com/opcodes/lfoN_drive_onccX)
     ///
-    lfoN_drive_onccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_drive_onccX(u8, u8, UnknownType),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [lfoN_drive_smoothccX](https://sfzformat.com/opcodes/lfoN_drive_smoothccX)
     ///
-    lfoN_drive_smoothccX(UnknownType),
+    lfoN_drive_smoothccX(u8, u8, f32),
 
     ///
     /// - range: None
@@ -3893,7 +4063,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [lfoN_drive_stepccX](https://sfzformat.com/opcodes/lfoN_drive_stepccX)
     ///
-    lfoN_drive_stepccX(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lfoN_drive_stepccX(u8, u8, UnknownType),
 
     ///
     /// - range: 0 to 100
@@ -3901,6 +4072,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [apan_depth](https://sfzformat.com/opcodes/apan_depth)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     apan_depth(UnknownType),
 
     ///
@@ -3909,6 +4081,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [apan_dry](https://sfzformat.com/opcodes/apan_dry)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     apan_dry(UnknownType),
 
     ///
@@ -3933,7 +4106,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [apan_waveform](https://sfzformat.com/opcodes/apan_waveform)
     ///
-    apan_waveform(UnknownType),
+    apan_waveform(waveform),
 
     ///
     /// - range: 0 to 100
@@ -3941,6 +4114,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [apan_wet](https://sfzformat.com/opcodes/apan_wet)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     apan_wet(UnknownType),
 
     ///
@@ -3949,6 +4123,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [bitred](https://sfzformat.com/opcodes/bitred)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     bitred(UnknownType),
 
     ///
@@ -3957,23 +4132,23 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [bitred_onccN](https://sfzformat.com/opcodes/bitred_onccN)
     ///
-    bitred_onccN(UnknownType),
+    bitred_onccN(u8, RawValue),
 
     ///
-    /// - range: None
+    /// - range: 0 to 255
     /// - default: None
     /// - version: v2
     /// - info: [bitred_curveccN](https://sfzformat.com/opcodes/bitred_curveccN)
     ///
-    bitred_curveccN(UnknownType),
+    bitred_curveccN(u8, u8),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [bitred_smoothccN](https://sfzformat.com/opcodes/bitred_smoothccN)
     ///
-    bitred_smoothccN(UnknownType),
+    bitred_smoothccN(u8, f32),
 
     ///
     /// - range: None
@@ -3981,7 +4156,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [bitred_stepccN](https://sfzformat.com/opcodes/bitred_stepccN)
     ///
-    bitred_stepccN(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    bitred_stepccN(u8, UnknownType),
 
     ///
     /// - range: None
@@ -3997,6 +4173,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [comp_gain](https://sfzformat.com/opcodes/comp_gain)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     comp_gain(UnknownType),
 
     ///
@@ -4005,6 +4182,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [comp_ratio](https://sfzformat.com/opcodes/comp_ratio)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     comp_ratio(UnknownType),
 
     ///
@@ -4021,10 +4199,10 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [comp_stlink](https://sfzformat.com/opcodes/comp_stlink)
     ///
-    comp_stlink(String),
+    comp_stlink(on_off),
 
     ///
-    /// - range: None
+    /// - range: -144 to 0
     /// - default: None
     /// - version: v2
     /// - info: [comp_threshold](https://sfzformat.com/opcodes/comp_threshold)
@@ -4037,6 +4215,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [decim](https://sfzformat.com/opcodes/decim)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     decim(UnknownType),
 
     ///
@@ -4045,23 +4224,23 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [decim_onccN](https://sfzformat.com/opcodes/decim_onccN)
     ///
-    decim_onccN(UnknownType),
+    decim_onccN(u8, RawValue),
 
     ///
-    /// - range: None
+    /// - range: 0 to 255
     /// - default: None
     /// - version: v2
     /// - info: [decim_curveccN](https://sfzformat.com/opcodes/decim_curveccN)
     ///
-    decim_curveccN(UnknownType),
+    decim_curveccN(u8, u8),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [decim_smoothccN](https://sfzformat.com/opcodes/decim_smoothccN)
     ///
-    decim_smoothccN(UnknownType),
+    decim_smoothccN(u8, f32),
 
     ///
     /// - range: None
@@ -4069,7 +4248,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [decim_stepccN](https://sfzformat.com/opcodes/decim_stepccN)
     ///
-    decim_stepccN(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    decim_stepccN(u8, UnknownType),
 
     ///
     /// - range: None
@@ -4085,6 +4265,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_damphi](https://sfzformat.com/opcodes/delay_damphi)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     delay_damphi(UnknownType),
 
     ///
@@ -4093,6 +4274,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_damplo](https://sfzformat.com/opcodes/delay_damplo)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     delay_damplo(UnknownType),
 
     ///
@@ -4101,7 +4283,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_dry](https://sfzformat.com/opcodes/delay_dry)
     ///
-    delay_dry(UnknownType),
+    delay_dry(f32),
 
     ///
     /// - range: 0 to 100
@@ -4109,6 +4291,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_feedback](https://sfzformat.com/opcodes/delay_feedback)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     delay_feedback(UnknownType),
 
     ///
@@ -4117,7 +4300,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_filter](https://sfzformat.com/opcodes/delay_filter)
     ///
-    delay_filter(String),
+    delay_filter(effect_filter),
 
     ///
     /// - range: 0 to 100
@@ -4125,6 +4308,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_input](https://sfzformat.com/opcodes/delay_input)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     delay_input(UnknownType),
 
     ///
@@ -4133,6 +4317,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_levelc](https://sfzformat.com/opcodes/delay_levelc)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     delay_levelc(UnknownType),
 
     ///
@@ -4141,6 +4326,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_levell](https://sfzformat.com/opcodes/delay_levell)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     delay_levell(UnknownType),
 
     ///
@@ -4149,6 +4335,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_levelr](https://sfzformat.com/opcodes/delay_levelr)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     delay_levelr(UnknownType),
 
     ///
@@ -4165,6 +4352,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_moddepth](https://sfzformat.com/opcodes/delay_moddepth)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     delay_moddepth(UnknownType),
 
     ///
@@ -4181,6 +4369,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_panc](https://sfzformat.com/opcodes/delay_panc)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     delay_panc(UnknownType),
 
     ///
@@ -4189,6 +4378,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_panl](https://sfzformat.com/opcodes/delay_panl)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     delay_panl(UnknownType),
 
     ///
@@ -4197,6 +4387,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_panr](https://sfzformat.com/opcodes/delay_panr)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     delay_panr(UnknownType),
 
     ///
@@ -4205,6 +4396,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_resonance](https://sfzformat.com/opcodes/delay_resonance)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     delay_resonance(UnknownType),
 
     ///
@@ -4213,6 +4405,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_spread](https://sfzformat.com/opcodes/delay_spread)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     delay_spread(UnknownType),
 
     ///
@@ -4221,7 +4414,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_syncc_onccN](https://sfzformat.com/opcodes/delay_syncc_onccN)
     ///
-    delay_syncc_onccN(UnknownType),
+    delay_syncc_onccN(u8, RawValue),
 
     ///
     /// - range: None
@@ -4229,7 +4422,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_syncl_onccN](https://sfzformat.com/opcodes/delay_syncl_onccN)
     ///
-    delay_syncl_onccN(UnknownType),
+    delay_syncl_onccN(u8, RawValue),
 
     ///
     /// - range: None
@@ -4237,7 +4430,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_syncr_onccN](https://sfzformat.com/opcodes/delay_syncr_onccN)
     ///
-    delay_syncr_onccN(UnknownType),
+    delay_syncr_onccN(u8, RawValue),
 
     ///
     /// - range: None
@@ -4245,6 +4438,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_time_tap](https://sfzformat.com/opcodes/delay_time_tap)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     delay_time_tap(UnknownType),
 
     ///
@@ -4253,6 +4447,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_timec](https://sfzformat.com/opcodes/delay_timec)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     delay_timec(UnknownType),
 
     ///
@@ -4261,6 +4456,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_timel](https://sfzformat.com/opcodes/delay_timel)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     delay_timel(UnknownType),
 
     ///
@@ -4269,6 +4465,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_timer](https://sfzformat.com/opcodes/delay_timer)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     delay_timer(UnknownType),
 
     ///
@@ -4277,7 +4474,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [delay_wet](https://sfzformat.com/opcodes/delay_wet)
     ///
-    delay_wet(UnknownType),
+    delay_wet(f32),
 
     ///
     /// - range: 0 to 100 %
@@ -4293,6 +4490,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [disto_depth](https://sfzformat.com/opcodes/disto_depth)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     disto_depth(UnknownType),
 
     ///
@@ -4301,7 +4499,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [disto_dry](https://sfzformat.com/opcodes/disto_dry)
     ///
-    disto_dry(UnknownType),
+    disto_dry(f32),
 
     ///
     /// - range: None
@@ -4309,6 +4507,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [disto_stages](https://sfzformat.com/opcodes/disto_stages)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     disto_stages(UnknownType),
 
     ///
@@ -4317,6 +4516,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [disto_tone](https://sfzformat.com/opcodes/disto_tone)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     disto_tone(UnknownType),
 
     ///
@@ -4325,7 +4525,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [disto_wet](https://sfzformat.com/opcodes/disto_wet)
     ///
-    disto_wet(UnknownType),
+    disto_wet(f32),
 
     ///
     /// - range: None
@@ -4333,6 +4533,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [eq_bw](https://sfzformat.com/opcodes/eq_bw)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     eq_bw(UnknownType),
 
     ///
@@ -4341,6 +4542,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [eq_freq](https://sfzformat.com/opcodes/eq_freq)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     eq_freq(UnknownType),
 
     ///
@@ -4349,6 +4551,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [eq_gain](https://sfzformat.com/opcodes/eq_gain)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     eq_gain(UnknownType),
 
     ///
@@ -4357,6 +4560,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [eq_type](https://sfzformat.com/opcodes/eq_type)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     eq_type(UnknownType),
 
     ///
@@ -4365,7 +4569,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [filter_cutoff](https://sfzformat.com/opcodes/filter_cutoff)
     ///
-    filter_cutoff(UnknownType),
+    filter_cutoff(f32),
 
     ///
     /// - range: None
@@ -4373,7 +4577,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [filter_resonance](https://sfzformat.com/opcodes/filter_resonance)
     ///
-    filter_resonance(UnknownType),
+    filter_resonance(f32),
 
     ///
     /// - range: None
@@ -4381,7 +4585,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [filter_type](https://sfzformat.com/opcodes/filter_type)
     ///
-    filter_type(String),
+    filter_type(fil_type),
 
     ///
     /// - range: 0 to 100 %
@@ -4389,7 +4593,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [fxNtomain](https://sfzformat.com/opcodes/fxNtomain)
     ///
-    fxNtomain(f32),
+    fxNtomain(u8, f32),
 
     ///
     /// - range: None
@@ -4397,7 +4601,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [gate_onccN](https://sfzformat.com/opcodes/gate_onccN)
     ///
-    gate_onccN(UnknownType),
+    gate_onccN(u8, RawValue),
 
     ///
     /// - range: None
@@ -4405,7 +4609,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [gate_attack](https://sfzformat.com/opcodes/gate_attack)
     ///
-    gate_attack(UnknownType),
+    gate_attack(f32),
 
     ///
     /// - range: None
@@ -4413,7 +4617,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [gate_release](https://sfzformat.com/opcodes/gate_release)
     ///
-    gate_release(UnknownType),
+    gate_release(f32),
 
     ///
     /// - range: on, off
@@ -4421,7 +4625,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [gate_stlink](https://sfzformat.com/opcodes/gate_stlink)
     ///
-    gate_stlink(String),
+    gate_stlink(on_off),
 
     ///
     /// - range: None
@@ -4429,7 +4633,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [gate_threshold](https://sfzformat.com/opcodes/gate_threshold)
     ///
-    gate_threshold(UnknownType),
+    gate_threshold(RawValue),
 
     ///
     /// - range: 0 to 100
@@ -4437,6 +4641,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [phaser_depth](https://sfzformat.com/opcodes/phaser_depth)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     phaser_depth(UnknownType),
 
     ///
@@ -4445,6 +4650,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [phaser_feedback](https://sfzformat.com/opcodes/phaser_feedback)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     phaser_feedback(UnknownType),
 
     ///
@@ -4461,7 +4667,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [phaser_phase_onccN](https://sfzformat.com/opcodes/phaser_phase_onccN)
     ///
-    phaser_phase_onccN(UnknownType),
+    phaser_phase_onccN(u8, f32),
 
     ///
     /// - range: None
@@ -4469,6 +4675,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [phaser_stages](https://sfzformat.com/opcodes/phaser_stages)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     phaser_stages(UnknownType),
 
     ///
@@ -4477,7 +4684,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [phaser_waveform](https://sfzformat.com/opcodes/phaser_waveform)
     ///
-    phaser_waveform(UnknownType),
+    phaser_waveform(waveform),
 
     ///
     /// - range: 0 to 100
@@ -4485,7 +4692,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [phaser_wet](https://sfzformat.com/opcodes/phaser_wet)
     ///
-    phaser_wet(UnknownType),
+    phaser_wet(f32),
 
     ///
     /// - range: 0 to 100
@@ -4493,6 +4700,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [reverb_damp](https://sfzformat.com/opcodes/reverb_damp)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     reverb_damp(UnknownType),
 
     ///
@@ -4501,7 +4709,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [reverb_dry](https://sfzformat.com/opcodes/reverb_dry)
     ///
-    reverb_dry(UnknownType),
+    reverb_dry(f32),
 
     ///
     /// - range: 0 to 100
@@ -4509,6 +4717,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [reverb_input](https://sfzformat.com/opcodes/reverb_input)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     reverb_input(UnknownType),
 
     ///
@@ -4525,6 +4734,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [reverb_size](https://sfzformat.com/opcodes/reverb_size)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     reverb_size(UnknownType),
 
     ///
@@ -4533,6 +4743,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [reverb_tone](https://sfzformat.com/opcodes/reverb_tone)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     reverb_tone(UnknownType),
 
     ///
@@ -4549,7 +4760,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [reverb_wet](https://sfzformat.com/opcodes/reverb_wet)
     ///
-    reverb_wet(UnknownType),
+    reverb_wet(f32),
 
     ///
     /// - range: 0 to 100
@@ -4557,6 +4768,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [static_cyclic_level](https://sfzformat.com/opcodes/static_cyclic_level)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     static_cyclic_level(UnknownType),
 
     ///
@@ -4573,7 +4785,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [static_filter](https://sfzformat.com/opcodes/static_filter)
     ///
-    static_filter(String),
+    static_filter(effect_filter),
 
     ///
     /// - range: 0 to 100
@@ -4581,6 +4793,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [static_level](https://sfzformat.com/opcodes/static_level)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     static_level(UnknownType),
 
     ///
@@ -4589,6 +4802,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [static_random_level](https://sfzformat.com/opcodes/static_random_level)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     static_random_level(UnknownType),
 
     ///
@@ -4608,12 +4822,12 @@ pub enum Opcode {
     static_random_mintime(f32),
 
     ///
-    /// - range: None
+    /// - range: on, off
     /// - default: None
     /// - version: v2
     /// - info: [static_stereo](https://sfzformat.com/opcodes/static_stereo)
     ///
-    static_stereo(UnknownType),
+    static_stereo(on_off),
 
     ///
     /// - range: 0 to 100
@@ -4621,6 +4835,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [static_tone](https://sfzformat.com/opcodes/static_tone)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     static_tone(UnknownType),
 
     ///
@@ -4629,6 +4844,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [strings_number](https://sfzformat.com/opcodes/strings_number)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     strings_number(UnknownType),
 
     ///
@@ -4637,7 +4853,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [strings_wet_onccN](https://sfzformat.com/opcodes/strings_wet_onccN)
     ///
-    strings_wet_onccN(UnknownType),
+    strings_wet_onccN(u8, f32),
 
     ///
     /// - range: 0 to 100
@@ -4645,7 +4861,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [tdfir_dry](https://sfzformat.com/opcodes/tdfir_dry)
     ///
-    tdfir_dry(UnknownType),
+    tdfir_dry(f32),
 
     ///
     /// - range: 0 to 100
@@ -4653,6 +4869,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [tdfir_gain](https://sfzformat.com/opcodes/tdfir_gain)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     tdfir_gain(UnknownType),
 
     ///
@@ -4661,6 +4878,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [tdfir_impulse](https://sfzformat.com/opcodes/tdfir_impulse)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     tdfir_impulse(UnknownType),
 
     ///
@@ -4669,7 +4887,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [tdfir_wet](https://sfzformat.com/opcodes/tdfir_wet)
     ///
-    tdfir_wet(UnknownType),
+    tdfir_wet(f32),
 
     ///
     /// - range: 0 to 1
@@ -4717,7 +4935,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [oscillator](https://sfzformat.com/opcodes/oscillator)
     ///
-    oscillator(String),
+    oscillator(on_off),
 
     ///
     /// - range: None
@@ -4725,6 +4943,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [oscillator_detune](https://sfzformat.com/opcodes/oscillator_detune)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     oscillator_detune(UnknownType),
 
     ///
@@ -4733,7 +4952,8 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [oscillator_detune_onccN](https://sfzformat.com/opcodes/oscillator_detune_onccN)
     ///
-    oscillator_detune_onccN(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    oscillator_detune_onccN(u8, UnknownType),
 
     ///
     /// - range: 0 to 2
@@ -4749,6 +4969,7 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [oscillator_mod_depth](https://sfzformat.com/opcodes/oscillator_mod_depth)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     oscillator_mod_depth(UnknownType),
 
     ///
@@ -4757,15 +4978,16 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [oscillator_mod_depth_onccN](https://sfzformat.com/opcodes/oscillator_mod_depth_onccN)
     ///
-    oscillator_mod_depth_onccN(UnknownType),
+    #[cfg_attr(feature = "serde", serde(skip))]
+    oscillator_mod_depth_onccN(u8, UnknownType),
 
     ///
-    /// - range: None
+    /// - range: 0 to ms
     /// - default: None
     /// - version: v2
     /// - info: [oscillator_mod_smoothccN](https://sfzformat.com/opcodes/oscillator_mod_smoothccN)
     ///
-    oscillator_mod_smoothccN(UnknownType),
+    oscillator_mod_smoothccN(u8, f32),
 
     ///
     /// - range: 1 to 9
@@ -4797,23 +5019,244 @@ pub enum Opcode {
     /// - version: v2
     /// - info: [oscillator_table_size](https://sfzformat.com/opcodes/oscillator_table_size)
     ///
+    #[cfg_attr(feature = "serde", serde(skip))]
     oscillator_table_size(UnknownType),
     // cakewalk extension opcodes -----------------------------------------------
     // https://sfzformat.com/extensions/cakewalk/
 }
+}
 
 impl Opcode {
     /// Returns the default value of the current opcode
     ///
     pub fn default_value(&self) -> Option<OpcodeType> {
-        OPCODE_DEFAULT.get::<str>(&self.str_name()).cloned()
+        OPCODE_DEFAULT.get(self.name()).cloned()
     }
 
-    // Returns the name of the current opcode as a string
-    //
-    // used by .default_value()
+    /// Returns true if the value of the current opcode is its default value
+    pub fn is_default(&self) -> bool {
+        match self.default_value().map(|v| v.to_string()) {
+            Some(default) if !default.is_empty() => {
+                self.to_string() == format!("{}={}", self.full_name(), default)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the name of the current opcode as a string, like [`name`][Self::name]
     pub fn str_name(&self) -> String {
-        format!("{:?}", &self).split('(').collect::<Vec<&str>>()[0].to_string()
+        self.name().to_string()
+    }
+
+    /// Returns the numeric parameters of the current opcode,
+    /// in the same order they appear in its name.
+    pub fn params(&self) -> Vec<u8> {
+        let count = self.params_count();
+        let mut params = Vec::with_capacity(count);
+        self.for_each_field(&mut |field| {
+            if params.len() < count {
+                params.extend(field.as_any().downcast_ref::<u8>());
+            }
+        });
+        params
+    }
+
+    /// Returns the number of numeric parameters of the current opcode,
+    /// one for each N, NN, X or Y in its name.
+    fn params_count(&self) -> usize {
+        let name = self.name();
+        name.matches(|c: char| c.is_ascii_uppercase()).count() - name.matches("NN").count()
+    }
+
+    /// Returns the value of the current opcode, which is its field after
+    /// the numeric parameters, if it has one.
+    pub(crate) fn value_field(&self) -> Option<&dyn OpcodeField> {
+        let mut index = 0;
+        let mut value = None;
+        let count = self.params_count();
+        self.for_each_field(&mut |field| {
+            if index == count {
+                value = Some(field);
+            }
+            index += 1;
+        });
+        value
+    }
+
+    /// Returns the headers under which the current opcode can be placed
+    ///
+    /// Most opcodes can be placed under a region, or under any header
+    /// whose opcodes are inherited by regions.
+    pub fn allowed_scopes(&self) -> &'static [Header] {
+        match self {
+            Opcode::default_path(..)
+            | Opcode::note_offset(..)
+            | Opcode::octave_offset(..)
+            | Opcode::set_ccN(..)
+            | Opcode::set_hdccN(..)
+            | Opcode::label_ccN(..)
+            | Opcode::param_offset(..)
+            | Opcode::hint_(..) => &[Header::Control],
+            Opcode::global_label(..) | Opcode::global_amplitude(..) | Opcode::global_volume(..) => {
+                &[Header::Global]
+            }
+            Opcode::master_label(..) | Opcode::master_amplitude(..) | Opcode::master_volume(..) => {
+                &[Header::Master]
+            }
+            Opcode::group_label(..) | Opcode::group_amplitude(..) | Opcode::group_volume(..) => {
+                &[Header::Group]
+            }
+            Opcode::region_label(..) => &[Header::Region],
+            Opcode::curve_index(..) | Opcode::vN(..) => &[Header::Curve],
+            Opcode::bus(..)
+            | Opcode::r#type(..)
+            | Opcode::dsp_order(..)
+            | Opcode::apan_depth(..)
+            | Opcode::apan_dry(..)
+            | Opcode::apan_freq(..)
+            | Opcode::apan_phase(..)
+            | Opcode::apan_waveform(..)
+            | Opcode::apan_wet(..)
+            | Opcode::bitred(..)
+            | Opcode::bitred_onccN(..)
+            | Opcode::bitred_curveccN(..)
+            | Opcode::bitred_smoothccN(..)
+            | Opcode::bitred_stepccN(..)
+            | Opcode::comp_attack(..)
+            | Opcode::comp_gain(..)
+            | Opcode::comp_ratio(..)
+            | Opcode::comp_release(..)
+            | Opcode::comp_stlink(..)
+            | Opcode::comp_threshold(..)
+            | Opcode::decim(..)
+            | Opcode::decim_onccN(..)
+            | Opcode::decim_curveccN(..)
+            | Opcode::decim_smoothccN(..)
+            | Opcode::decim_stepccN(..)
+            | Opcode::delay_cutoff(..)
+            | Opcode::delay_damphi(..)
+            | Opcode::delay_damplo(..)
+            | Opcode::delay_dry(..)
+            | Opcode::delay_feedback(..)
+            | Opcode::delay_filter(..)
+            | Opcode::delay_input(..)
+            | Opcode::delay_levelc(..)
+            | Opcode::delay_levell(..)
+            | Opcode::delay_levelr(..)
+            | Opcode::delay_lfofreq(..)
+            | Opcode::delay_moddepth(..)
+            | Opcode::delay_mode(..)
+            | Opcode::delay_panc(..)
+            | Opcode::delay_panl(..)
+            | Opcode::delay_panr(..)
+            | Opcode::delay_resonance(..)
+            | Opcode::delay_spread(..)
+            | Opcode::delay_syncc_onccN(..)
+            | Opcode::delay_syncl_onccN(..)
+            | Opcode::delay_syncr_onccN(..)
+            | Opcode::delay_time_tap(..)
+            | Opcode::delay_timec(..)
+            | Opcode::delay_timel(..)
+            | Opcode::delay_timer(..)
+            | Opcode::delay_wet(..)
+            | Opcode::directtomain(..)
+            | Opcode::disto_depth(..)
+            | Opcode::disto_dry(..)
+            | Opcode::disto_stages(..)
+            | Opcode::disto_tone(..)
+            | Opcode::disto_wet(..)
+            | Opcode::eq_bw(..)
+            | Opcode::eq_freq(..)
+            | Opcode::eq_gain(..)
+            | Opcode::eq_type(..)
+            | Opcode::filter_cutoff(..)
+            | Opcode::filter_resonance(..)
+            | Opcode::filter_type(..)
+            | Opcode::fxNtomain(..)
+            | Opcode::gate_onccN(..)
+            | Opcode::gate_attack(..)
+            | Opcode::gate_release(..)
+            | Opcode::gate_stlink(..)
+            | Opcode::gate_threshold(..)
+            | Opcode::phaser_depth(..)
+            | Opcode::phaser_feedback(..)
+            | Opcode::phaser_freq(..)
+            | Opcode::phaser_phase_onccN(..)
+            | Opcode::phaser_stages(..)
+            | Opcode::phaser_waveform(..)
+            | Opcode::phaser_wet(..)
+            | Opcode::reverb_damp(..)
+            | Opcode::reverb_dry(..)
+            | Opcode::reverb_input(..)
+            | Opcode::reverb_predelay(..)
+            | Opcode::reverb_size(..)
+            | Opcode::reverb_tone(..)
+            | Opcode::reverb_type(..)
+            | Opcode::reverb_wet(..)
+            | Opcode::static_cyclic_level(..)
+            | Opcode::static_cyclic_time(..)
+            | Opcode::static_filter(..)
+            | Opcode::static_level(..)
+            | Opcode::static_random_level(..)
+            | Opcode::static_random_maxtime(..)
+            | Opcode::static_random_mintime(..)
+            | Opcode::static_stereo(..)
+            | Opcode::static_tone(..)
+            | Opcode::strings_number(..)
+            | Opcode::strings_wet_onccN(..)
+            | Opcode::tdfir_dry(..)
+            | Opcode::tdfir_gain(..)
+            | Opcode::tdfir_impulse(..)
+            | Opcode::tdfir_wet(..) => &[Header::Effect],
+            _ => &[
+                Header::Global,
+                Header::Master,
+                Header::Group,
+                Header::Region,
+            ],
+        }
+    }
+
+    /// Returns true if the current opcode can be placed under the provided header
+    pub fn is_allowed_in(&self, header: Header) -> bool {
+        self.allowed_scopes().contains(&header)
+    }
+
+    /// Returns true if the current opcode can only be placed under a region
+    pub fn is_region_only(&self) -> bool {
+        self.allowed_scopes() == [Header::Region]
+    }
+
+    /// Returns true if the current opcode can be placed under the global header
+    pub fn is_global_allowed(&self) -> bool {
+        self.is_allowed_in(Header::Global)
+    }
+
+    /// Returns the name of the current opcode as it's written in SFZ code,
+    /// with its numeric parameters in place of the N, X and Y letters.
+    ///
+    /// The NN parameter is written with two digits, like in `var01_mod`.
+    pub fn full_name(&self) -> String {
+        let name = self.name();
+        let mut params = self.params().into_iter();
+        let mut full_name = String::with_capacity(name.len());
+        let mut chars = name.chars().peekable();
+        while let Some(c) = chars.next() {
+            // the parameter letters are the only uppercase chars in a name,
+            // and a double NN stands for a single parameter of two digits
+            if c.is_ascii_uppercase() {
+                if let Some(p) = params.next() {
+                    if c == 'N' && chars.next_if_eq(&'N').is_some() {
+                        full_name += &format!("{:02}", p);
+                    } else {
+                        full_name += &p.to_string();
+                    }
+                }
+            } else {
+                full_name.push(c);
+            }
+        }
+        full_name
     }
 
     // const stati version of str_name
@@ -4834,3 +5277,48 @@ impl Opcode {
     //     OPCODE_DEFAULT.get(name).cloned()
     // }
 }
+
+#[cfg(test)]
+mod tests_opcode {
+    use super::*;
+
+    /// The name as it was obtained before, from the `Debug` output.
+    fn debug_name(opcode: &Opcode) -> String {
+        format!("{:?}", opcode).split('(').collect::<Vec<&str>>()[0].to_string()
+    }
+
+    #[test]
+    fn test_opcode_fields() {
+        let opcode = Opcode::egN_eqXgain_onccY(1, 2, 3, "6".into());
+        assert_eq!(opcode.params(), [1, 2, 3]);
+        assert_eq!(opcode.value::<String>(), Some(&"6".to_string()));
+
+        // the parameters that are the only field are not the value
+        let opcode = Opcode::varNN_target(3);
+        assert_eq!(opcode.params(), [3]);
+        assert!(opcode.value_field().is_none());
+        assert_eq!(opcode.to_string(), "var03_target=");
+
+        assert!(Opcode::hint_().params().is_empty());
+        assert!(Opcode::hint_().value_field().is_none());
+        assert!(Opcode::hikey(60).params().is_empty());
+    }
+
+    #[test]
+    fn test_opcode_name() {
+        let opcodes = [
+            Opcode::cutoff(500.),
+            Opcode::hiccN(64, 100),
+            Opcode::egN_eqXgain_onccY(1, 2, 3, "6".into()),
+            Opcode::varNN_mod(1, "add".into()),
+            Opcode::r#type(effect_type::reverb),
+            Opcode::sample("a.wav".into()),
+            Opcode::fil_type(fil_type::lpf_2p),
+        ];
+        for opcode in &opcodes {
+            assert_eq!(opcode.name(), debug_name(opcode));
+            assert_eq!(opcode.str_name(), opcode.name());
+        }
+        assert_eq!(Opcode::r#type(effect_type::reverb).name(), "type");
+    }
+}
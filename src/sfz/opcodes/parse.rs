@@ -1,11 +1,30 @@
 use logos::{Lexer, Logos};
+use once_cell::sync::Lazy;
 use regex::Regex;
 
-use log::trace;
-
-use crate::sfz::types::{fil_type, loop_mode, trigger, MAX_SAMPLE_RATE};
-use crate::sfz::{Header, Opcode};
-use crate::utils;
+use log::{trace, warn};
+
+use crate::error::Error;
+use crate::sfz::opcodes::aliases;
+use crate::sfz::types::{
+    direction, effect_filter, effect_type, fil_type, loop_mode, loop_type, off_mode, on_off, phase,
+    sw_vel, trigger, waveform, xf_curve, MAX_SAMPLE_RATE,
+};
+use crate::sfz::{Header, Opcode, Warning, WarningKind};
+use crate::utils::{self, ValueError};
+
+/// Matches the opcodes with an "NN" parameter, whose names start with `var`.
+static IS_VAR: Lazy<Regex> = Lazy::new(|| Regex::new(r"^var").unwrap());
+
+/// Matches the letters of the parameters, from the last one to the first one.
+static PARAMETER_LETTERS: Lazy<[(Regex, &str); 4]> = Lazy::new(|| {
+    [
+        (Regex::new(r"Y").unwrap(), "33"),
+        (Regex::new(r"X").unwrap(), "22"),
+        (Regex::new(r"NN").unwrap(), "11"),
+        (Regex::new(r"N").unwrap(), "11"),
+    ]
+});
 
 impl Opcode {
     /// Receives an opcode name with numeric parameters, and returns the canonical
@@ -35,11 +54,20 @@ impl Opcode {
         let mut previous_span_end = 0;
         let mut remainder = String::new(); // the remainder opcode name after the current parameter
 
+        // 4 opcodes have an "NN" parameter:
+        // (varNN_mod, varNN_onccX, varNN_curveccX, varNN_target)
+        let is_var = IS_VAR.is_match(name);
+
         // Tries to find numeric parameters embedded in the name
         let lex_numbers = OpcodeParameter::lexer(name);
         for (n, span) in lex_numbers.spanned() {
             // If a parameter is found
             if let OpcodeParameter::Parameter(p) = &n {
+                // no opcode has more than 3 parameters
+                if par_num == par_char.len() {
+                    return (name.to_string(), Vec::new());
+                }
+
                 // constructs the new name of the opcode,
                 // with the numeric parameters being substituted by
                 // the N, X, Y chars, in that order.
@@ -49,9 +77,8 @@ impl Opcode {
                 let (first, last) = name.split_at(split_idx);
                 remainder = last[span.end - span.start..].to_string();
 
-                // first handle the special case of 4 opcodes with an "NN" parameter:
-                // (varNN_mod, varNN_onccX, varNN_curveccX, varNN_target)
-                if par_num == 0 && Regex::new(r"^var").unwrap().is_match(name) {
+                // first handle the special case of the opcodes with an "NN" parameter
+                if par_num == 0 && is_var {
                     new_name += &format!("{}NN", &first[previous_span_end..]);
 
                 // then handle the rest of the cases
@@ -84,7 +111,7 @@ impl Opcode {
                 //   eqN_vel2gain
 
                 // Stores the numeric parameter
-                params.push(*p as u8);
+                params.push(*p);
 
                 par_num += 1;
                 previous_span_end = span.end;
@@ -108,13 +135,10 @@ impl Opcode {
     /// preparing them for testing the parsing.
     #[allow(dead_code)]
     pub(crate) fn numerize_pars(opcode: &str) -> String {
-        let mut o_new;
-
-        o_new = Regex::new(r"Y").unwrap().replace(opcode, "33").to_string(); //  Y > 33
-        o_new = Regex::new(r"X").unwrap().replace(&o_new, "22").to_string(); //  X > 22
-        o_new = Regex::new(r"NN").unwrap().replace(&o_new, "11").to_string(); // NN > 11
-        o_new = Regex::new(r"N").unwrap().replace(&o_new, "11").to_string(); //  N > 11
-
+        let mut o_new = opcode.to_string();
+        for (letter, number) in PARAMETER_LETTERS.iter() {
+            o_new = letter.replace(&o_new, *number).to_string();
+        }
         o_new
     }
 
@@ -135,109 +159,756 @@ impl Opcode {
     // (thos opcodes can even be of a different different type in each version)
 
     pub(crate) fn parse_opcode(lex: &mut Lexer<SfzToken>) -> Option<Opcode> {
-        // TODO: return also the opcode name parameters
-        // pub(crate) fn parse_opcode(lex: &mut Lexer<SfzToken>)
-        //     -> (Option<Opcode>, Option<vec![u8]>) {
-
         let slice = lex.slice();
 
-        let kv: Vec<&str> = slice.splitn(2, '=').collect();
-        let (opcode, value) = (kv[0], kv[1]);
+        let (opcode, value) = slice.split_once('=')?;
         let value = value.trim(); // remove possible remaining CRLF chars
+        if opcode.is_empty() || value.is_empty() {
+            warn!("`{}` is missing the opcode name or its value", slice);
+            return None;
+        }
 
-        let (opcode, values) = Opcode::parse_name(opcode);
-
-        match (opcode.as_str(), values) {
-            // TODO: test returning opcode name parameters
-            ("eqN_bwccX", _) => utils::check_f32_between(value, -4., 4.).map(Opcode::eqN_bwccX),
+        let name = opcode;
+        let (mut opcode, params) = Opcode::parse_name(name);
+        if let Some(canonical) = aliases::canonical_name(&opcode) {
+            opcode = canonical.to_string();
+        }
 
+        // the numeric parameters found in the name are matched by the slice pattern
+        let parsed = match (opcode.as_str(), params.as_slice()) {
             // v1
+            ("amp_keycenter", _) => utils::try_check_midi_note(value).map(Opcode::amp_keycenter),
+            ("amp_keytrack", _) => {
+                utils::try_check_f32_between(value, -96., 12.).map(Opcode::amp_keytrack)
+            }
             ("amp_veltrack", _) => {
-                utils::check_f32_between(value, -100., 100.).map(Opcode::amp_veltrack)
+                utils::try_check_f32_between(value, -100., 100.).map(Opcode::amp_veltrack)
+            }
+            ("amp_velcurve_N", &[n]) => {
+                utils::try_check_f32_between(value, 0., 1.).map(|v| Opcode::amp_velcurve_N(n, v))
+            }
+            ("amp_random", _) => {
+                utils::try_check_f32_between(value, 0., 24.).map(Opcode::amp_random)
             }
-            ("amp_random", _) => utils::check_f32_between(value, 0., 24.).map(Opcode::amp_random),
             ("ampeg_attack", _) => {
-                utils::check_f32_between(value, 0., 100.).map(Opcode::ampeg_attack)
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::ampeg_attack)
             }
             // NOTE: VPO: TODO: ampeg_attackcc1 (needs special parsing)
-            ("ampeg_attackccN", _) => {
-                utils::check_f32_between(value, 0., 100.).map(Opcode::ampeg_attackccN)
-            }
+            ("ampeg_attackccN", &[n]) => utils::try_check_f32_between(value, -100., 100.)
+                .map(|v| Opcode::ampeg_attackccN(n, v)),
             ("ampeg_decay", _) => {
-                utils::check_f32_between(value, 0., 100.).map(Opcode::ampeg_decay)
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::ampeg_decay)
+            }
+            ("ampeg_decayccN", &[n]) => utils::try_check_f32_between(value, -100., 100.)
+                .map(|v| Opcode::ampeg_decayccN(n, v)),
+            ("ampeg_delay", _) => {
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::ampeg_delay)
             }
-            ("ampeg_hold", _) => utils::check_f32_between(value, 0., 100.).map(Opcode::ampeg_hold),
+            ("ampeg_delayccN", &[n]) => utils::try_check_f32_between(value, -100., 100.)
+                .map(|v| Opcode::ampeg_delayccN(n, v)),
+            ("ampeg_hold", _) => {
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::ampeg_hold)
+            }
+            ("ampeg_holdccN", &[n]) => utils::try_check_f32_between(value, -100., 100.)
+                .map(|v| Opcode::ampeg_holdccN(n, v)),
             ("ampeg_release", _) => {
-                utils::check_f32_between(value, 0., 100.).map(Opcode::ampeg_release)
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::ampeg_release)
+            }
+            ("ampeg_releaseccN", &[n]) => utils::try_check_f32_between(value, -100., 100.)
+                .map(|v| Opcode::ampeg_releaseccN(n, v)),
+            ("ampeg_start", _) => {
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::ampeg_start)
             }
+            ("ampeg_startccN", &[n]) => utils::try_check_f32_between(value, -100., 100.)
+                .map(|v| Opcode::ampeg_startccN(n, v)),
             ("ampeg_sustain", _) => {
-                utils::check_f32_between(value, 0., 100.).map(Opcode::ampeg_sustain)
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::ampeg_sustain)
+            }
+            ("ampeg_sustainccN", &[n]) => utils::try_check_f32_between(value, -100., 100.)
+                .map(|v| Opcode::ampeg_sustainccN(n, v)),
+            ("ampeg_vel2attack", _) => {
+                utils::try_check_f32_between(value, -100., 100.).map(Opcode::ampeg_vel2attack)
+            }
+            ("ampeg_vel2decay", _) => {
+                utils::try_check_f32_between(value, -100., 100.).map(Opcode::ampeg_vel2decay)
+            }
+            ("ampeg_vel2delay", _) => {
+                utils::try_check_f32_between(value, -100., 100.).map(Opcode::ampeg_vel2delay)
+            }
+            ("ampeg_vel2hold", _) => {
+                utils::try_check_f32_between(value, -100., 100.).map(Opcode::ampeg_vel2hold)
+            }
+            ("ampeg_vel2release", _) => {
+                utils::try_check_f32_between(value, -100., 100.).map(Opcode::ampeg_vel2release)
+            }
+            ("ampeg_vel2sustain", _) => {
+                utils::try_check_f32_between(value, -100., 100.).map(Opcode::ampeg_vel2sustain)
             }
-            ("bend_down", _) => utils::check_i16_between(value, -9600, 9600).map(Opcode::bend_down),
-            ("bend_up", _) => utils::check_i16_between(value, -9600, 9600).map(Opcode::bend_up),
+            ("bend_down", _) => {
+                utils::try_check_i16_between(value, -9600, 9600).map(Opcode::bend_down)
+            }
+            ("bend_up", _) => utils::try_check_i16_between(value, -9600, 9600).map(Opcode::bend_up),
+            ("bend_step", _) => utils::try_check_u16_between(value, 1, 1200).map(Opcode::bend_step),
             // NOTE: upper range is SampleRate/2 (it should be checked when sample rate is known)
             ("cutoff", _) => {
-                utils::check_f32_between(value, 0., MAX_SAMPLE_RATE).map(Opcode::cutoff)
+                utils::try_check_f32_between(value, 0., MAX_SAMPLE_RATE).map(Opcode::cutoff)
+            }
+            ("cutoff_ccN", &[n]) => {
+                utils::try_check_i16_between(value, -9600, 9600).map(|v| Opcode::cutoff_ccN(n, v))
+            }
+            ("cutoff_chanaft", _) => {
+                utils::try_check_i16_between(value, -9600, 9600).map(Opcode::cutoff_chanaft)
+            }
+            ("cutoff_polyaft", _) => {
+                utils::try_check_i16_between(value, -9600, 9600).map(Opcode::cutoff_polyaft)
             }
-            ("fil_type", _) => fil_type::from_name(value).map(Opcode::fil_type),
+            ("fil_keycenter", _) => utils::try_check_midi_note(value).map(Opcode::fil_keycenter),
+            ("fil_keytrack", _) => {
+                utils::try_check_i16_between(value, 0, 1200).map(Opcode::fil_keytrack)
+            }
+            ("fil_type", _) => fil_type::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::fil_type),
             ("fil_veltrack", _) => {
-                utils::check_i16_between(value, -9600, 9600).map(Opcode::fil_veltrack)
+                utils::try_check_i16_between(value, -9600, 9600).map(Opcode::fil_veltrack)
+            }
+            ("hibend", _) => utils::try_check_i16_between(value, -8192, 8192).map(Opcode::hibend),
+            ("hiccN", &[n]) => {
+                utils::try_check_u8_between(value, 0, 127).map(|v| Opcode::hiccN(n, v))
             }
+            ("hichan", _) => utils::try_check_u8_between(value, 1, 16).map(Opcode::hichan),
+            ("hichanaft", _) => utils::try_check_u8_between(value, 0, 127).map(Opcode::hichanaft),
+            ("group", _) => utils::try_check_u32_between(value, 0, u32::MAX).map(Opcode::group),
             // NOTE: hikey v2 accepts i8, from -1:
-            ("hikey", _) => utils::check_midi_note(value).map(Opcode::hikey),
-            ("hivel", _) => utils::check_u8_between(value, 0, 127).map(Opcode::hivel),
-            ("hirand", _) => utils::check_f32_between(value, 0., 1.).map(Opcode::hirand),
+            ("hikey", _) => utils::try_check_midi_note(value).map(Opcode::hikey),
+            ("hivel", _) => utils::try_check_u8_between(value, 0, 127).map(Opcode::hivel),
+            ("hirand", _) => utils::try_check_f32_between(value, 0., 1.).map(Opcode::hirand),
+            ("key", _) => utils::try_check_midi_note(value).map(Opcode::key),
+            ("lobend", _) => utils::try_check_i16_between(value, -8192, 8192).map(Opcode::lobend),
+            ("loccN", &[n]) => {
+                utils::try_check_u8_between(value, 0, 127).map(|v| Opcode::loccN(n, v))
+            }
+            ("lochan", _) => utils::try_check_u8_between(value, 1, 16).map(Opcode::lochan),
+            ("lochanaft", _) => utils::try_check_u8_between(value, 0, 127).map(Opcode::lochanaft),
             // NOTE: lokey v2 accepts i8, from -1:
-            ("lokey", _) => utils::check_midi_note(value).map(Opcode::lokey),
-            ("lovel", _) => utils::check_u8_between(value, 0, 127).map(Opcode::lovel),
-            ("loop_mode", _) => loop_mode::from_name(value).map(Opcode::loop_mode),
-            ("lorand", _) => utils::check_f32_between(value, 0., 1.).map(Opcode::lorand),
-            ("off_by", _) => utils::check_u32_between(value, 0, u32::MAX).map(Opcode::off_by),
-            ("offset", _) => utils::check_u32_between(value, 0, u32::MAX).map(Opcode::offset),
-            ("on_loccN", _) => utils::check_i8_between(value, 0, 127).map(Opcode::on_loccN),
-            ("on_hiccN", _) => utils::check_i8_between(value, 0, 127).map(Opcode::on_hiccN),
-            ("pan", _) => utils::check_f32_between(value, 0., 100.).map(Opcode::pan),
-            ("pitch_keycenter", _) => utils::check_midi_note(value).map(Opcode::pitch_keycenter),
+            ("lokey", _) => utils::try_check_midi_note(value).map(Opcode::lokey),
+            ("lovel", _) => utils::try_check_u8_between(value, 0, 127).map(Opcode::lovel),
+            ("loop_mode", _) => loop_mode::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::loop_mode),
+            ("lorand", _) => utils::try_check_f32_between(value, 0., 1.).map(Opcode::lorand),
+            ("off_mode", _) => off_mode::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::off_mode),
+            ("sw_vel", _) => sw_vel::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::sw_vel),
+            ("direction", _) => direction::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::direction),
+            ("loop_type", _) => loop_type::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::loop_type),
+            ("phase", _) => phase::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::phase),
+            ("oscillator", _) => on_off::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::oscillator),
+            ("waveguide", _) => on_off::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::waveguide),
+            ("sostenuto_sw", _) => on_off::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::sostenuto_sw),
+            ("sustain_sw", _) => on_off::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::sustain_sw),
+            ("rt_dead", _) => on_off::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::rt_dead),
+            ("note_selfmask", _) => on_off::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::note_selfmask),
+            ("off_by", _) => utils::try_check_u32_between(value, 0, u32::MAX).map(Opcode::off_by),
+            ("offset", _) => utils::try_check_u32_between(value, 0, u32::MAX).map(Opcode::offset),
+            ("delay_ccN", &[n]) => {
+                utils::try_check_f32_between(value, 0., 100.).map(|v| Opcode::delay_ccN(n, v))
+            }
+            ("offset_ccN", &[n]) => {
+                utils::try_check_u32_between(value, 0, u32::MAX).map(|v| Opcode::offset_ccN(n, v))
+            }
+            ("end", _) => utils::try_check_u32_between(value, 0, u32::MAX).map(Opcode::end),
+            ("loop_start", _) => {
+                utils::try_check_u32_between(value, 0, u32::MAX).map(Opcode::loop_start)
+            }
+            ("loop_end", _) => {
+                utils::try_check_u32_between(value, 0, u32::MAX).map(Opcode::loop_end)
+            }
+            ("loop_crossfade", _) => {
+                utils::try_check_f32_between(value, 0., f32::MAX).map(Opcode::loop_crossfade)
+            }
+            ("loop_count", _) => utils::try_check_u32(value).map(Opcode::loop_count),
+            ("on_loccN", &[n]) => {
+                utils::try_check_i8_between(value, 0, 127).map(|v| Opcode::on_loccN(n, v))
+            }
+            ("on_hiccN", &[n]) => {
+                utils::try_check_i8_between(value, 0, 127).map(|v| Opcode::on_hiccN(n, v))
+            }
+            ("output", _) => utils::try_check_u16_between(value, 0, 1024).map(Opcode::output),
+            ("pan", _) => utils::try_check_f32_between(value, -100., 100.).map(Opcode::pan),
+            ("pitch_keycenter", _) => {
+                utils::try_check_midi_note(value).map(Opcode::pitch_keycenter)
+            }
             ("pitch_keytrack", _) => {
-                utils::check_i16_between(value, -1200, 1200).map(Opcode::pitch_keytrack)
+                utils::try_check_i16_between(value, -1200, 1200).map(Opcode::pitch_keytrack)
             }
             ("pitch_random", _) => {
-                utils::check_u16_between(value, 0, 9600).map(Opcode::pitch_random)
-            }
-            ("rt_decay", _) => utils::check_f32_between(value, 0., 200.).map(Opcode::rt_decay),
-            ("sample", _) => Some(Opcode::sample(utils::fix_path_separators(value))),
-            ("seq_lenght", _) => utils::check_u8_between(value, 1, 100).map(Opcode::seq_length),
-            ("seq_position", _) => utils::check_u8_between(value, 1, 100).map(Opcode::seq_position),
-            ("trigger", _) => trigger::from_name(value).map(Opcode::trigger),
-            ("sw_hikey", _) => utils::check_midi_note(value).map(Opcode::sw_hikey),
-            ("sw_last", _) => utils::check_u8_between(value, 0, 127).map(Opcode::sw_last),
-            ("sw_lokey", _) => utils::check_midi_note(value).map(Opcode::sw_lokey),
-            ("tune", _) => utils::check_i8_between(value, -100, 100).map(Opcode::tune),
-            ("volume", _) => utils::check_f32_between(value, -144., 6.).map(Opcode::volume),
-            ("xfin_hivel", _) => utils::check_u8_between(value, 0, 127).map(Opcode::xfin_hivel),
-            ("xfin_lovel", _) => utils::check_u8_between(value, 0, 127).map(Opcode::xfin_lovel),
-            ("xfout_hivel", _) => utils::check_u8_between(value, 0, 127).map(Opcode::xfout_hivel),
-            ("xfout_lovel", _) => utils::check_u8_between(value, 0, 127).map(Opcode::xfout_lovel),
+                utils::try_check_u16_between(value, 0, 9600).map(Opcode::pitch_random)
+            }
+            ("pitch_veltrack", _) => {
+                utils::try_check_i16_between(value, -9600, 9600).map(Opcode::pitch_veltrack)
+            }
+            ("resonance", _) => utils::try_check_f32_between(value, 0., 40.).map(Opcode::resonance),
+            ("rt_decay", _) => utils::try_check_f32_between(value, 0., 200.).map(Opcode::rt_decay),
+            ("sample", _) => Ok(Opcode::sample(utils::fix_path_separators(value))),
+            ("seq_length", _) => utils::try_check_u8_between(value, 1, 100).map(Opcode::seq_length),
+            ("seq_position", _) => {
+                utils::try_check_u8_between(value, 1, 100).map(Opcode::seq_position)
+            }
+            ("trigger", _) => trigger::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::trigger),
+            ("sw_hikey", _) => utils::try_check_midi_note(value).map(Opcode::sw_hikey),
+            ("sw_last", _) => utils::try_check_u8_between(value, 0, 127).map(Opcode::sw_last),
+            ("sw_lokey", _) => utils::try_check_midi_note(value).map(Opcode::sw_lokey),
+            ("sw_lolast", _) => utils::try_check_u8_between(value, 0, 127).map(Opcode::sw_lolast),
+            ("sw_hilast", _) => utils::try_check_u8_between(value, 0, 127).map(Opcode::sw_hilast),
+            ("transpose", _) => {
+                utils::try_check_i8_between(value, -127, 127).map(Opcode::transpose)
+            }
+            ("tune", _) => utils::try_check_i8_between(value, -100, 100).map(Opcode::tune),
+            ("volume", _) => utils::try_check_f32_between(value, -144., 6.).map(Opcode::volume),
+            ("gain_ccN", &[n]) => {
+                utils::try_check_f32_between(value, -144., 48.).map(|v| Opcode::gain_ccN(n, v))
+            }
+            ("xf_cccurve", _) => xf_curve::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::xf_cccurve),
+            ("xf_keycurve", _) => xf_curve::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::xf_keycurve),
+            ("xf_velcurve", _) => xf_curve::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::xf_velcurve),
+            ("xfin_hivel", _) => utils::try_check_u8_between(value, 0, 127).map(Opcode::xfin_hivel),
+            ("xfin_lovel", _) => utils::try_check_u8_between(value, 0, 127).map(Opcode::xfin_lovel),
+            ("xfout_hivel", _) => {
+                utils::try_check_u8_between(value, 0, 127).map(Opcode::xfout_hivel)
+            }
+            ("xfout_lovel", _) => {
+                utils::try_check_u8_between(value, 0, 127).map(Opcode::xfout_lovel)
+            }
+            ("xfin_lokey", _) => utils::try_check_midi_note(value).map(Opcode::xfin_lokey),
+            ("xfin_hikey", _) => utils::try_check_midi_note(value).map(Opcode::xfin_hikey),
+            ("xfout_lokey", _) => utils::try_check_midi_note(value).map(Opcode::xfout_lokey),
+            ("xfout_hikey", _) => utils::try_check_midi_note(value).map(Opcode::xfout_hikey),
+            ("xfin_loccN", &[n]) => {
+                utils::try_check_u8_between(value, 0, 127).map(|v| Opcode::xfin_loccN(n, v))
+            }
+            ("xfin_hiccN", &[n]) => {
+                utils::try_check_u8_between(value, 0, 127).map(|v| Opcode::xfin_hiccN(n, v))
+            }
+            ("xfout_loccN", &[n]) => {
+                utils::try_check_u8_between(value, 0, 127).map(|v| Opcode::xfout_loccN(n, v))
+            }
+            ("xfout_hiccN", &[n]) => {
+                utils::try_check_u8_between(value, 0, 127).map(|v| Opcode::xfout_hiccN(n, v))
+            }
+            ("effect1", _) => utils::try_check_f32_between(value, 0., 100.).map(Opcode::effect1),
+            ("effect2", _) => utils::try_check_f32_between(value, 0., 100.).map(Opcode::effect2),
 
             // v2
-            ("sw_default", _) => utils::check_u8_between(value, 0, 127).map(Opcode::sw_default),
-            ("default_path", _) => Some(Opcode::default_path(utils::fix_path_separators(value))),
+            ("pitch", _) => utils::try_check_i8_between(value, -100, 100).map(Opcode::pitch),
+            ("bend_stepup", _) => {
+                utils::try_check_u16_between(value, 1, 1200).map(Opcode::bend_stepup)
+            }
+            ("bend_stepdown", _) => {
+                utils::try_check_u16_between(value, 1, 1200).map(Opcode::bend_stepdown)
+            }
+            ("ampeg_attack_shape", _) => {
+                utils::try_check_f32(value).map(Opcode::ampeg_attack_shape)
+            }
+            ("ampeg_decay_shape", _) => utils::try_check_f32(value).map(Opcode::ampeg_decay_shape),
+            ("ampeg_release_shape", _) => {
+                utils::try_check_f32(value).map(Opcode::ampeg_release_shape)
+            }
+            ("ampeg_decay_zero", _) => {
+                utils::try_check_u8_between(value, 0, 1).map(Opcode::ampeg_decay_zero)
+            }
+            ("ampeg_release_zero", _) => {
+                utils::try_check_u8_between(value, 0, 1).map(Opcode::ampeg_release_zero)
+            }
+            ("sw_default", _) => utils::try_check_u8_between(value, 0, 127).map(Opcode::sw_default),
+            ("type", _) => Ok(Opcode::r#type(effect_type::from_name(value))),
+            ("global_amplitude", _) => {
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::global_amplitude)
+            }
+            ("master_amplitude", _) => {
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::master_amplitude)
+            }
+            ("group_amplitude", _) => {
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::group_amplitude)
+            }
+            ("global_volume", _) => {
+                utils::try_check_f32_between(value, -144., 6.).map(Opcode::global_volume)
+            }
+            ("master_volume", _) => {
+                utils::try_check_f32_between(value, -144., 6.).map(Opcode::master_volume)
+            }
+            ("group_volume", _) => {
+                utils::try_check_f32_between(value, -144., 6.).map(Opcode::group_volume)
+            }
+            // NOTE: -1 means the CC doesn't gate the region
+            ("on_lohdccN", &[n]) => utils::try_check_f32_between(value, -1., 1.)
+                .and_then(hdcc_value)
+                .map(|v| Opcode::on_lohdccN(n, v)),
+            ("on_hihdccN", &[n]) => utils::try_check_f32_between(value, -1., 1.)
+                .and_then(hdcc_value)
+                .map(|v| Opcode::on_hihdccN(n, v)),
+            ("start_lohdccN", &[n]) => utils::try_check_f32_between(value, -1., 1.)
+                .and_then(hdcc_value)
+                .map(|v| Opcode::start_lohdccN(n, v)),
+            ("start_hihdccN", &[n]) => utils::try_check_f32_between(value, -1., 1.)
+                .and_then(hdcc_value)
+                .map(|v| Opcode::start_hihdccN(n, v)),
+            ("stop_lohdccN", &[n]) => utils::try_check_f32_between(value, -1., 1.)
+                .and_then(hdcc_value)
+                .map(|v| Opcode::stop_lohdccN(n, v)),
+            ("stop_hihdccN", &[n]) => utils::try_check_f32_between(value, -1., 1.)
+                .and_then(hdcc_value)
+                .map(|v| Opcode::stop_hihdccN(n, v)),
+            ("apan_freq", _) => utils::try_check_f32(value).map(Opcode::apan_freq),
+            ("comp_attack", _) => utils::try_check_f32(value).map(Opcode::comp_attack),
+            ("comp_release", _) => utils::try_check_f32(value).map(Opcode::comp_release),
+            ("delay_cutoff", _) => utils::try_check_f32(value).map(Opcode::delay_cutoff),
+            ("delay_lfofreq", _) => utils::try_check_f32(value).map(Opcode::delay_lfofreq),
+            ("gate_attack", _) => utils::try_check_f32(value).map(Opcode::gate_attack),
+            ("gate_release", _) => utils::try_check_f32(value).map(Opcode::gate_release),
+            ("phaser_freq", _) => utils::try_check_f32(value).map(Opcode::phaser_freq),
+            ("reverb_predelay", _) => utils::try_check_f32(value).map(Opcode::reverb_predelay),
+            ("static_cyclic_time", _) => {
+                utils::try_check_f32(value).map(Opcode::static_cyclic_time)
+            }
+            ("delay_dry", _) => {
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::delay_dry)
+            }
+            ("delay_wet", _) => {
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::delay_wet)
+            }
+            ("disto_dry", _) => {
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::disto_dry)
+            }
+            ("disto_wet", _) => {
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::disto_wet)
+            }
+            ("phaser_wet", _) => {
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::phaser_wet)
+            }
+            ("reverb_dry", _) => {
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::reverb_dry)
+            }
+            ("reverb_wet", _) => {
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::reverb_wet)
+            }
+            ("reverb_type", _) => match value {
+                "chamber" | "large_hall" | "large_room" | "mid_hall" | "mid_room"
+                | "small_hall" | "small_room" => Ok(Opcode::reverb_type(value.to_string())),
+                _ => Err(ValueError::Invalid),
+            },
+            ("tdfir_dry", _) => {
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::tdfir_dry)
+            }
+            ("tdfir_wet", _) => {
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::tdfir_wet)
+            }
+            ("amplitude_curveccN", &[n]) => {
+                utils::try_check_u8_between(value, 0, 255).map(|v| Opcode::amplitude_curveccN(n, v))
+            }
+            ("bitred_curveccN", &[n]) => {
+                utils::try_check_u8_between(value, 0, 255).map(|v| Opcode::bitred_curveccN(n, v))
+            }
+            ("cutoff2_curveccN", &[n]) => {
+                utils::try_check_u8_between(value, 0, 255).map(|v| Opcode::cutoff2_curveccN(n, v))
+            }
+            ("decim_curveccN", &[n]) => {
+                utils::try_check_u8_between(value, 0, 255).map(|v| Opcode::decim_curveccN(n, v))
+            }
+            ("egN_pan_curveccX", &[n, x]) => utils::try_check_u8_between(value, 0, 255)
+                .map(|v| Opcode::egN_pan_curveccX(n, x, v)),
+            ("varNN_curveccX", &[n, x]) => {
+                utils::try_check_u8_between(value, 0, 255).map(|v| Opcode::varNN_curveccX(n, x, v))
+            }
+            ("polyphony_group", _) => {
+                utils::try_check_u32_between(value, 0, u32::MAX).map(Opcode::polyphony_group)
+            }
+            ("curve_index", _) => {
+                utils::try_check_u8_between(value, 0, 255).map(Opcode::curve_index)
+            }
+            ("param_offset", _) => {
+                utils::try_check_i32_between(value, i32::MIN, i32::MAX).map(Opcode::param_offset)
+            }
+            ("vN", &[n]) => utils::try_check_f32_between(value, -1., 1.).map(|v| Opcode::vN(n, v)),
+            ("dsp_order", _) => utils::try_check_u8_between(value, 0, 14).map(Opcode::dsp_order),
+            ("bus", _) => match value {
+                "main" | "aux1" | "aux2" | "aux3" | "aux4" | "aux5" | "aux6" | "aux7" | "aux8"
+                | "fx1" | "fx2" | "fx3" | "fx4" | "midi" => Ok(Opcode::bus(value.to_string())),
+                _ => Err(ValueError::Invalid),
+            },
+            ("directtomain", _) => {
+                utils::try_check_f32_between(value, 0., 100.).map(Opcode::directtomain)
+            }
+            ("fxNtomain", &[n]) => {
+                utils::try_check_f32_between(value, 0., 100.).map(|v| Opcode::fxNtomain(n, v))
+            }
+            ("effect3", _) => utils::try_check_f32_between(value, 0., 100.).map(Opcode::effect3),
+            ("effect4", _) => utils::try_check_f32_between(value, 0., 100.).map(Opcode::effect4),
+            ("comp_threshold", _) => {
+                utils::try_check_f32_between(value, -144., 0.).map(Opcode::comp_threshold)
+            }
+            ("gate_threshold", _) => Ok(Opcode::gate_threshold(value.to_string())),
+            ("bitred_onccN", &[n]) => Ok(Opcode::bitred_onccN(n, value.to_string())),
+            ("decim_onccN", &[n]) => Ok(Opcode::decim_onccN(n, value.to_string())),
+            ("delay_syncc_onccN", &[n]) => Ok(Opcode::delay_syncc_onccN(n, value.to_string())),
+            ("delay_syncl_onccN", &[n]) => Ok(Opcode::delay_syncl_onccN(n, value.to_string())),
+            ("delay_syncr_onccN", &[n]) => Ok(Opcode::delay_syncr_onccN(n, value.to_string())),
+            ("gate_onccN", &[n]) => Ok(Opcode::gate_onccN(n, value.to_string())),
+            ("phaser_phase_onccN", &[n]) => utils::try_check_f32_between(value, 0., 100.)
+                .map(|v| Opcode::phaser_phase_onccN(n, v)),
+            ("strings_wet_onccN", &[n]) => utils::try_check_f32_between(value, 0., 100.)
+                .map(|v| Opcode::strings_wet_onccN(n, v)),
+            ("delay_filter", _) => effect_filter::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::delay_filter),
+            ("filter_cutoff", _) => {
+                utils::try_check_f32_between(value, 0., MAX_SAMPLE_RATE).map(Opcode::filter_cutoff)
+            }
+            ("filter_resonance", _) => {
+                utils::try_check_f32_between(value, 0., 40.).map(Opcode::filter_resonance)
+            }
+            ("filter_type", _) => fil_type::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::filter_type),
+            ("noise_filter", _) => effect_filter::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::noise_filter),
+            ("static_filter", _) => effect_filter::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::static_filter),
+            ("noise_stereo", _) => on_off::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::noise_stereo),
+            ("static_stereo", _) => on_off::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::static_stereo),
+            ("comp_stlink", _) => on_off::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::comp_stlink),
+            ("gate_stlink", _) => on_off::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::gate_stlink),
+            ("apan_waveform", _) => waveform::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::apan_waveform),
+            ("phaser_waveform", _) => waveform::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::phaser_waveform),
+            ("noise_step", _) => utils::try_check_u8_between(value, 0, 100).map(Opcode::noise_step),
+            ("noise_step_onccN", &[n]) => {
+                utils::try_check_u8_between(value, 0, 100).map(|v| Opcode::noise_step_onccN(n, v))
+            }
+            ("noise_tone", _) => utils::try_check_u8_between(value, 0, 100).map(Opcode::noise_tone),
+            ("noise_tone_onccN", &[n]) => {
+                utils::try_check_u8_between(value, 0, 100).map(|v| Opcode::noise_tone_onccN(n, v))
+            }
+            ("default_path", _) => Ok(Opcode::default_path(utils::fix_path_separators(value))),
+            ("lfoN_freq", &[n]) => {
+                utils::try_check_f32_between(value, 0., f32::MAX).map(|v| Opcode::lfoN_freq(n, v))
+            }
+            ("lfoN_delay", &[n]) => {
+                utils::try_check_f32_between(value, 0., f32::MAX).map(|v| Opcode::lfoN_delay(n, v))
+            }
+            ("lfoN_fade", &[n]) => {
+                utils::try_check_f32_between(value, 0., f32::MAX).map(|v| Opcode::lfoN_fade(n, v))
+            }
+            ("lfoN_phase", &[n]) => {
+                utils::try_check_f32_between(value, 0., 1.).map(|v| Opcode::lfoN_phase(n, v))
+            }
+            ("lfoN_wave", &[n]) => {
+                utils::try_check_i32_between(value, 0, i32::MAX).map(|v| Opcode::lfoN_wave(n, v))
+            }
+            ("lfoN_waveX", &[n, x]) => utils::try_check_i32_between(value, 0, i32::MAX)
+                .map(|v| Opcode::lfoN_waveX(n, x, v)),
+            ("lfoN_freq_onccX", &[n, x]) => Ok(Opcode::lfoN_freq_onccX(n, x, value.to_string())),
+            ("lfoN_freq_smoothccX", &[n, x]) => {
+                Ok(Opcode::lfoN_freq_smoothccX(n, x, value.to_string()))
+            }
+            ("lfoN_freq_stepccX", &[n, x]) => {
+                Ok(Opcode::lfoN_freq_stepccX(n, x, value.to_string()))
+            }
+            ("lfoN_steps", &[n]) => {
+                utils::try_check_i32_between(value, 0, i32::MAX).map(|v| Opcode::lfoN_steps(n, v))
+            }
+            ("lfoN_stepX", &[n, x]) => utils::try_check_f32_between(value, -100., 100.)
+                .map(|v| Opcode::lfoN_stepX(n, x, v)),
+            ("lfoN_volume", &[n]) => Ok(Opcode::lfoN_volume(n, value.to_string())),
+            ("lfoN_volume_onccX", &[n, x]) => {
+                Ok(Opcode::lfoN_volume_onccX(n, x, value.to_string()))
+            }
+            ("lfoN_volume_smoothccX", &[n, x]) => {
+                Ok(Opcode::lfoN_volume_smoothccX(n, x, value.to_string()))
+            }
+            ("lfoN_volume_stepccX", &[n, x]) => {
+                Ok(Opcode::lfoN_volume_stepccX(n, x, value.to_string()))
+            }
+            ("lfoN_amplitude", &[n]) => Ok(Opcode::lfoN_amplitude(n, value.to_string())),
+            ("lfoN_amplitude_onccX", &[n, x]) => {
+                Ok(Opcode::lfoN_amplitude_onccX(n, x, value.to_string()))
+            }
+            ("lfoN_amplitude_smoothccX", &[n, x]) => {
+                Ok(Opcode::lfoN_amplitude_smoothccX(n, x, value.to_string()))
+            }
+            ("lfoN_amplitude_stepccX", &[n, x]) => {
+                Ok(Opcode::lfoN_amplitude_stepccX(n, x, value.to_string()))
+            }
+            ("lfoN_pan", &[n]) => Ok(Opcode::lfoN_pan(n, value.to_string())),
+            ("lfoN_pan_onccX", &[n, x]) => Ok(Opcode::lfoN_pan_onccX(n, x, value.to_string())),
+            ("lfoN_pan_smoothccX", &[n, x]) => {
+                Ok(Opcode::lfoN_pan_smoothccX(n, x, value.to_string()))
+            }
+            ("lfoN_pan_stepccX", &[n, x]) => Ok(Opcode::lfoN_pan_stepccX(n, x, value.to_string())),
+            ("lfoN_width", &[n]) => Ok(Opcode::lfoN_width(n, value.to_string())),
+            ("lfoN_width_onccX", &[n, x]) => Ok(Opcode::lfoN_width_onccX(n, x, value.to_string())),
+            ("lfoN_width_smoothccX", &[n, x]) => {
+                Ok(Opcode::lfoN_width_smoothccX(n, x, value.to_string()))
+            }
+            ("lfoN_width_stepccX", &[n, x]) => {
+                Ok(Opcode::lfoN_width_stepccX(n, x, value.to_string()))
+            }
+            ("lfoN_pitch", &[n]) => Ok(Opcode::lfoN_pitch(n, value.to_string())),
+            ("lfoN_pitch_onccX", &[n, x]) => Ok(Opcode::lfoN_pitch_onccX(n, x, value.to_string())),
+            ("lfoN_pitch_smoothccX", &[n, x]) => {
+                Ok(Opcode::lfoN_pitch_smoothccX(n, x, value.to_string()))
+            }
+            ("lfoN_pitch_stepccX", &[n, x]) => {
+                Ok(Opcode::lfoN_pitch_stepccX(n, x, value.to_string()))
+            }
+            ("egN_ampeg", &[n]) => Ok(Opcode::egN_ampeg(n, value.to_string())),
+            ("egN_points", &[n]) => {
+                utils::try_check_u8_between(value, 0, 255).map(|v| Opcode::egN_points(n, v))
+            }
+            ("egN_curveX", &[n, x]) => {
+                utils::try_check_u8_between(value, 0, 255).map(|v| Opcode::egN_curveX(n, x, v))
+            }
+            ("egN_decim", &[n]) => Ok(Opcode::egN_decim(n, value.to_string())),
+            ("egN_decim_onccX", &[n, x]) => Ok(Opcode::egN_decim_onccX(n, x, value.to_string())),
+            ("egN_bitred", &[n]) => Ok(Opcode::egN_bitred(n, value.to_string())),
+            ("egN_bitred_onccX", &[n, x]) => Ok(Opcode::egN_bitred_onccX(n, x, value.to_string())),
+            ("egN_ringmod", &[n]) => Ok(Opcode::egN_ringmod(n, value.to_string())),
+            ("egN_ringmod_onccX", &[n, x]) => {
+                Ok(Opcode::egN_ringmod_onccX(n, x, value.to_string()))
+            }
+            ("egN_rectify", &[n]) => Ok(Opcode::egN_rectify(n, value.to_string())),
+            ("egN_rectify_onccX", &[n, x]) => {
+                Ok(Opcode::egN_rectify_onccX(n, x, value.to_string()))
+            }
+            ("egN_noiselevel", &[n]) => Ok(Opcode::egN_noiselevel(n, value.to_string())),
+            ("egN_noiselevel_onccX", &[n, x]) => {
+                Ok(Opcode::egN_noiselevel_onccX(n, x, value.to_string()))
+            }
+            ("egN_driveshape", &[n]) => Ok(Opcode::egN_driveshape(n, value.to_string())),
+            ("egN_driveshape_onccX", &[n, x]) => {
+                Ok(Opcode::egN_driveshape_onccX(n, x, value.to_string()))
+            }
+            ("egN_freq_lfoX", &[n, x]) => Ok(Opcode::egN_freq_lfoX(n, x, value.to_string())),
+            ("egN_depth_lfoX", &[n, x]) => Ok(Opcode::egN_depth_lfoX(n, x, value.to_string())),
+            ("egN_depthadd_lfoX", &[n, x]) => {
+                Ok(Opcode::egN_depthadd_lfoX(n, x, value.to_string()))
+            }
+            ("egN_eqXfreq", &[n, x]) => Ok(Opcode::egN_eqXfreq(n, x, value.to_string())),
+            ("egN_eqXfreq_onccY", &[n, x, y]) => {
+                Ok(Opcode::egN_eqXfreq_onccY(n, x, y, value.to_string()))
+            }
+            ("egN_eqXbw", &[n, x]) => Ok(Opcode::egN_eqXbw(n, x, value.to_string())),
+            ("egN_eqXbw_onccY", &[n, x, y]) => {
+                Ok(Opcode::egN_eqXbw_onccY(n, x, y, value.to_string()))
+            }
+            ("egN_eqXgain", &[n, x]) => Ok(Opcode::egN_eqXgain(n, x, value.to_string())),
+            ("egN_eqXgain_onccY", &[n, x, y]) => {
+                Ok(Opcode::egN_eqXgain_onccY(n, x, y, value.to_string()))
+            }
+            ("eqN_bwccX", &[n, x]) => {
+                utils::try_check_f32_between(value, -4., 4.).map(|v| Opcode::eqN_bwccX(n, x, v))
+            }
+            ("eqN_freqccX", &[n, x]) => utils::try_check_f32_between(value, -30000., 30000.)
+                .map(|v| Opcode::eqN_freqccX(n, x, v)),
+            ("eqN_gainccX", &[n, x]) => {
+                utils::try_check_f32_between(value, -96., 24.).map(|v| Opcode::eqN_gainccX(n, x, v))
+            }
+            ("eqN_vel2freq", &[n]) => utils::try_check_f32_between(value, -30000., 30000.)
+                .map(|v| Opcode::eqN_vel2freq(n, v)),
+            ("eqN_vel2gain", &[n]) => {
+                utils::try_check_f32_between(value, -96., 24.).map(|v| Opcode::eqN_vel2gain(n, v))
+            }
+            ("cutoff2", _) => {
+                utils::try_check_f32_between(value, 0., MAX_SAMPLE_RATE).map(Opcode::cutoff2)
+            }
+            ("resonance2", _) => {
+                utils::try_check_f32_between(value, 0., 40.).map(Opcode::resonance2)
+            }
+            ("cutoff2_onccN", &[n]) => utils::try_check_i16_between(value, -9600, 9600)
+                .map(|v| Opcode::cutoff2_onccN(n, v)),
+            ("fil2_keycenter", _) => utils::try_check_midi_note(value).map(Opcode::fil2_keycenter),
+            ("fil2_keytrack", _) => {
+                utils::try_check_u16_between(value, 0, 1200).map(Opcode::fil2_keytrack)
+            }
+            ("fil2_type", _) => fil_type::from_name(value)
+                .ok_or(ValueError::Invalid)
+                .map(Opcode::fil2_type),
+            ("fil2_veltrack", _) => {
+                utils::try_check_i16_between(value, -9600, 9600).map(Opcode::fil2_veltrack)
+            }
+            ("resonance2_onccN", &[n]) => {
+                utils::try_check_f32_between(value, 0., 40.).map(|v| Opcode::resonance2_onccN(n, v))
+            }
+            ("resonance2_curveccN", &[n]) => utils::try_check_u8_between(value, 0, 255)
+                .map(|v| Opcode::resonance2_curveccN(n, v)),
+            ("resonance2_smoothccN", &[n]) => utils::try_check_f32_between(value, 0., f32::MAX)
+                .map(|v| Opcode::resonance2_smoothccN(n, v)),
+            ("bend_smooth", _) => {
+                utils::try_check_f32_between(value, 0., f32::MAX).map(Opcode::bend_smooth)
+            }
+            ("amplitude_smoothccN", &[n]) => utils::try_check_f32_between(value, 0., f32::MAX)
+                .map(|v| Opcode::amplitude_smoothccN(n, v)),
+            ("cutoff2_smoothccN", &[n]) => utils::try_check_f32_between(value, 0., f32::MAX)
+                .map(|v| Opcode::cutoff2_smoothccN(n, v)),
+            ("noise_level_smoothccN", &[n]) => utils::try_check_f32_between(value, 0., f32::MAX)
+                .map(|v| Opcode::noise_level_smoothccN(n, v)),
+            ("bitred_smoothccN", &[n]) => utils::try_check_f32_between(value, 0., f32::MAX)
+                .map(|v| Opcode::bitred_smoothccN(n, v)),
+            ("decim_smoothccN", &[n]) => utils::try_check_f32_between(value, 0., f32::MAX)
+                .map(|v| Opcode::decim_smoothccN(n, v)),
+            ("oscillator_mod_smoothccN", &[n]) => utils::try_check_f32_between(value, 0., f32::MAX)
+                .map(|v| Opcode::oscillator_mod_smoothccN(n, v)),
+            ("lfoN_smooth", &[n]) => {
+                utils::try_check_f32_between(value, 0., f32::MAX).map(|v| Opcode::lfoN_smooth(n, v))
+            }
+            ("lfoN_cutoff_smoothccX", &[n, x]) => utils::try_check_f32_between(value, 0., f32::MAX)
+                .map(|v| Opcode::lfoN_cutoff_smoothccX(n, x, v)),
+            ("lfoN_cutoff2_smoothccX", &[n, x]) => {
+                utils::try_check_f32_between(value, 0., f32::MAX)
+                    .map(|v| Opcode::lfoN_cutoff2_smoothccX(n, x, v))
+            }
+            ("lfoN_resonance_smoothccX", &[n, x]) => {
+                utils::try_check_f32_between(value, 0., f32::MAX)
+                    .map(|v| Opcode::lfoN_resonance_smoothccX(n, x, v))
+            }
+            ("lfoN_resonance2_smoothccX", &[n, x]) => {
+                utils::try_check_f32_between(value, 0., f32::MAX)
+                    .map(|v| Opcode::lfoN_resonance2_smoothccX(n, x, v))
+            }
+            ("lfoN_decim_smoothccX", &[n, x]) => utils::try_check_f32_between(value, 0., f32::MAX)
+                .map(|v| Opcode::lfoN_decim_smoothccX(n, x, v)),
+            ("lfoN_bitred_smoothccX", &[n, x]) => utils::try_check_f32_between(value, 0., f32::MAX)
+                .map(|v| Opcode::lfoN_bitred_smoothccX(n, x, v)),
+            ("lfoN_noiselevel_smoothccX", &[n, x]) => {
+                utils::try_check_f32_between(value, 0., f32::MAX)
+                    .map(|v| Opcode::lfoN_noiselevel_smoothccX(n, x, v))
+            }
+            ("lfoN_noisestep_smoothccX", &[n, x]) => {
+                utils::try_check_f32_between(value, 0., f32::MAX)
+                    .map(|v| Opcode::lfoN_noisestep_smoothccX(n, x, v))
+            }
+            ("lfoN_noisetone_smoothccX", &[n, x]) => {
+                utils::try_check_f32_between(value, 0., f32::MAX)
+                    .map(|v| Opcode::lfoN_noisetone_smoothccX(n, x, v))
+            }
+            ("lfoN_drive_smoothccX", &[n, x]) => utils::try_check_f32_between(value, 0., f32::MAX)
+                .map(|v| Opcode::lfoN_drive_smoothccX(n, x, v)),
+            ("lfoN_eqXfreq_smoothccY", &[n, x, y]) => {
+                utils::try_check_f32_between(value, 0., f32::MAX)
+                    .map(|v| Opcode::lfoN_eqXfreq_smoothccY(n, x, y, v))
+            }
+            ("lfoN_eqXbw_smoothccY", &[n, x, y]) => {
+                utils::try_check_f32_between(value, 0., f32::MAX)
+                    .map(|v| Opcode::lfoN_eqXbw_smoothccY(n, x, y, v))
+            }
+            ("lfoN_eqXgain_smoothccY", &[n, x, y]) => {
+                utils::try_check_f32_between(value, 0., f32::MAX)
+                    .map(|v| Opcode::lfoN_eqXgain_smoothccY(n, x, y, v))
+            }
+            ("resonance2_stepccN", &[n]) => {
+                utils::try_check_u32(value).map(|v| Opcode::resonance2_stepccN(n, v))
+            }
 
             // aria
             ("ampeg_dynamic", _) => {
-                utils::check_u8_between(value, 0, 127).map(Opcode::ampeg_dynamic)
+                utils::try_check_u8_between(value, 0, 1).map(Opcode::ampeg_dynamic)
+            }
+            ("global_label", _) => Ok(Opcode::global_label(value.to_string())),
+            ("group_label", _) => Ok(Opcode::group_label(value.to_string())),
+            ("master_label", _) => Ok(Opcode::master_label(value.to_string())),
+            ("region_label", _) => Ok(Opcode::region_label(value.to_string())),
+            ("sw_label", _) => Ok(Opcode::sw_label(value.to_string())),
+            ("vendor_specific", _) => Ok(Opcode::vendor_specific(value.to_string())),
+
+            _ => {
+                trace!("`{}` is not a supported opcode", opcode);
+                lex.extras.warning = Some(Warning::new(name, value, WarningKind::UnknownOpcode));
+                return None;
+            }
+        };
+
+        match parsed {
+            Ok(opcode) => Some(opcode),
+            Err(ValueError::Invalid) => {
+                lex.extras.error = Some(Error::ParseValue {
+                    opcode: name.to_string(),
+                    value: value.to_string(),
+                });
+                None
+            }
+            Err(ValueError::OutOfRange) => {
+                lex.extras.warning = Some(Warning::new(name, value, WarningKind::OutOfRange));
+                None
             }
-            ("group_label", _) => Some(Opcode::group_label(value.to_string())),
-            ("sw_label", _) => Some(Opcode::sw_label(value.to_string())),
-
-            _ => None,
         }
     }
 }
 
+/// Accepts the values of the HD CC gating opcodes, which are either
+/// between 0 and 1, or -1 for when the CC doesn't gate the region
+fn hdcc_value(value: f32) -> Result<f32, ValueError> {
+    if value >= 0. || value == -1. {
+        Ok(value)
+    } else {
+        Err(ValueError::OutOfRange)
+    }
+}
+
+/// The state of the lexer, kept between tokens
+#[derive(Debug, Default)]
+pub(crate) struct LexerState {
+    /// The error found in the value of the last opcode, if any.
+    pub(crate) error: Option<Error>,
+    /// The reason the last opcode was ignored, if any.
+    pub(crate) warning: Option<Warning>,
+}
+
 /// Token for parsing SFZ format elements like headers and tokens
 ///
 #[derive(Logos, Clone, Debug, PartialEq)]
+#[logos(extras = LexerState)]
 pub(crate) enum SfzToken {
     /// Parses a Header
     ///
@@ -250,15 +921,22 @@ pub(crate) enum SfzToken {
     /// `=`, without spaces between the opcode and the sign.
     ///
     /// All opcodes can be in the same line, separated by spaces.
-    /// **sample** uses a special regex to support filenames with spaces.
+    /// **sample** uses a special regex to support filenames with spaces,
+    /// **default_path** takes any path without spaces,
+    /// and **vendor_specific** takes the rest of the line verbatim.
     #[regex("sample=[^.]+\\.\\S+", Opcode::parse_opcode)]
-    #[regex("[a-zA-Z0-9_]+=[\\w.]+", Opcode::parse_opcode)]
+    #[regex("default_path=\\S+", Opcode::parse_opcode)]
+    #[regex("vendor_specific=[^\\n]+", Opcode::parse_opcode)]
+    #[regex("[a-zA-Z0-9_]+=-?[\\w.]+", Opcode::parse_opcode)]
     Opcode(Opcode),
 
     #[regex(r"[ \t\n\f]+", logos::skip)]
     WhiteSpace,
 
-    #[regex(r"[//.*$]", logos::skip)]
+    /// Skips the line comments, from `//` till the end of the line,
+    /// and the block comments, between `/*` and `*/`, even multi-line.
+    #[regex(r"//[^\n]*", logos::skip)]
+    #[regex(r"/\*[^*]*\*+([^/*][^*]*\*+)*/", logos::skip)]
     Comments,
 
     #[error]
@@ -293,8 +971,9 @@ pub(crate) enum OpcodeParameter {
 #[cfg(test)]
 mod tests_opcodes {
     use super::*;
+    use crate::Instrument;
     use logos::Logos;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
 
     #[test]
     fn test_opcode_ampeg_attack() {
@@ -305,6 +984,53 @@ mod tests_opcodes {
         );
     }
 
+    #[test]
+    fn test_opcode_ampeg_family() {
+        let i = Instrument::from_sfz(
+            "<region> sample=a.wav ampeg_delay=0.5 ampeg_vel2attack=-20 ampeg_attack_shape=2
+            ampeg_sustaincc64=-50 ampeg_release_zero=1",
+            Path::new(""),
+        )
+        .unwrap();
+        let opcodes: Vec<&Opcode> = i.regions[0].opcodes.values().skip(1).collect();
+        assert_eq!(
+            opcodes,
+            [
+                &Opcode::ampeg_delay(0.5),
+                &Opcode::ampeg_vel2attack(-20.),
+                &Opcode::ampeg_attack_shape(2.),
+                &Opcode::ampeg_sustainccN(64, -50.),
+                &Opcode::ampeg_release_zero(1),
+            ]
+        );
+
+        // out of range
+        let mut lex = SfzToken::lexer("ampeg_vel2decay=-200");
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+    }
+
+    #[test]
+    fn test_opcode_amp_tracking() {
+        let mut lex = SfzToken::lexer("amp_keytrack=3 amp_keycenter=48 amp_veltrack=100");
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::amp_keytrack(3.))));
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::amp_keycenter(48)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::amp_veltrack(100.)))
+        );
+
+        let mut lex = SfzToken::lexer("amp_keycenter=c4 amp_keytrack=13 amp_veltrack=101");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::amp_keycenter(60)))
+        );
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+    }
+
     #[test]
     fn test_opcode_pan() {
         let mut lex = SfzToken::lexer("pan=100");
@@ -316,92 +1042,959 @@ mod tests_opcodes {
         let mut lex = SfzToken::lexer("pan=67.353");
         assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::pan(67.353))));
 
+        let mut lex = SfzToken::lexer("pan=-100");
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::pan(-100.0))));
+
         let mut lex = SfzToken::lexer("pan=101.0");
         assert_eq!(lex.next(), Some(SfzToken::Error));
-        //
-        // let mut lex = SfzToken::lexer("pan=-1.0");
-        // assert_eq!(lex.next(), Some(SfzToken::Error));
+
+        let mut lex = SfzToken::lexer("pan=150");
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+
+        let mut lex = SfzToken::lexer("pan=-1.0");
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::pan(-1.0))));
     }
 
     #[test]
-    fn test_opcode_sample() {
-        let mut lex = SfzToken::lexer("sample=MOHorn mute_A#1_v1_1.wav");
+    fn test_opcode_resonance() {
+        let mut lex = SfzToken::lexer("resonance=12");
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::resonance(12.0))));
+
+        let mut lex = SfzToken::lexer("resonance=41");
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+
+        let mut lex = SfzToken::lexer("resonance2=3.5");
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::resonance2(3.5))));
+
+        // the CC index is retained
+        let mut lex = SfzToken::lexer("resonance2_oncc1=6 resonance2_curvecc74=2");
         assert_eq!(
             lex.next(),
-            Some(SfzToken::Opcode(Opcode::sample(PathBuf::from(
-                "MOHorn mute_A#1_v1_1.wav"
-            ))))
+            Some(SfzToken::Opcode(Opcode::resonance2_onccN(1, 6.0)))
         );
-
-        // The equal sign is also supported in the filename
-        let mut lex = SfzToken::lexer("sample=equal_sign_=_doesn't_fail.wav");
         assert_eq!(
             lex.next(),
-            Some(SfzToken::Opcode(Opcode::sample(PathBuf::from(
-                "equal_sign_=_doesn't_fail.wav"
-            ))))
+            Some(SfzToken::Opcode(Opcode::resonance2_curveccN(74, 2)))
+        );
+        assert_eq!(
+            Opcode::resonance2_onccN(1, 6.0).full_name(),
+            "resonance2_oncc1"
         );
     }
-}
-
-#[cfg(test)]
-mod tests_token {
-    use super::*;
-
-    use std::path::PathBuf;
 
     #[test]
-    fn test_sfz_simple() {
+    fn test_opcode_filter_family() {
         let mut lex = SfzToken::lexer(
-            "<region>
-sample=with space/MOHorn_mute_A#1_v1_1.wav lokey=46 hikey=48
-pitch_keycenter=46
-lovel=0 hivel=62
-volume=2.0",
+            "cutoff_cc1=2400 cutoff_chanaft=-1200 cutoff2=8000 cutoff2_oncc74=600 fil2_type=hpf_2p",
         );
-
-        assert_eq!(lex.next(), Some(SfzToken::Header(Header::Region)));
-        assert_eq!(lex.span(), 0..8);
-
         assert_eq!(
             lex.next(),
-            Some(SfzToken::Opcode(Opcode::sample(PathBuf::from(
-                "with space/MOHorn_mute_A#1_v1_1.wav"
-            ))))
+            Some(SfzToken::Opcode(Opcode::cutoff_ccN(1, 2400)))
         );
-        // recognize multiple opcodes in the same line
-        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::lokey(46))));
-        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::hikey(48))));
         assert_eq!(
             lex.next(),
-            Some(SfzToken::Opcode(Opcode::pitch_keycenter(46)))
+            Some(SfzToken::Opcode(Opcode::cutoff_chanaft(-1200)))
         );
-        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::lovel(0))));
-        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::hivel(62))));
-        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::volume(2.0))));
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::cutoff2(8000.))));
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::cutoff2_onccN(74, 600)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::fil2_type(fil_type::hpf_2p)))
+        );
+        assert_eq!(
+            Opcode::fil2_type(fil_type::hpf_2p).to_string(),
+            "fil2_type=hpf_2p"
+        );
+
+        let mut lex = SfzToken::lexer("cutoff_cc1=9700 fil2_type=lpf_3p");
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+        assert_eq!(lex.next(), Some(SfzToken::Error));
     }
-}
 
-#[cfg(test)]
-mod tests_parameters {
+    #[test]
+    fn test_opcode_pitch_family() {
+        let mut lex = SfzToken::lexer(
+            "bend_up=1200 bend_down=-1200 pitch=-50 pitch_veltrack=300 transpose=-12 bend_stepup=100",
+        );
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::bend_up(1200))));
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::bend_down(-1200))));
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::pitch(-50))));
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::pitch_veltrack(300)))
+        );
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::transpose(-12))));
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::bend_stepup(100))));
 
-    use super::{Opcode, Regex};
+        let mut lex = SfzToken::lexer("pitch=101 bend_step=0");
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+    }
 
     #[test]
-    fn test_parse_all_opcodes() {
-        // DATA
-        //
-        // [999] indicates the current number opcodes at 2020-09-11
-        // N and NN gets treated equally as being 1 parameter.
-
-        // file names are suffixed with the number of opcodes contained
-        static OPCODES_SFZ_V1: &str = include_str!("data/opcodes-v1_184.txt");
-        static OPCODES_SFZ_V2: &str = include_str!("data/opcodes-v2_166.txt");
-        static OPCODES_SFZ_ARIA_EXT: &str = include_str!("data/opcodes-aria-extension_78.txt");
-        static OPCODES_SFZ_CAKEWALK_V2: &str = include_str!("data/opcodes-cakewalk-v2_162.txt");
-        let opcodes_total = format!(
-            "{}{}{}{}", // the list of all the opcodes
-            OPCODES_SFZ_V1, OPCODES_SFZ_V2, OPCODES_SFZ_ARIA_EXT, OPCODES_SFZ_CAKEWALK_V2,
+    fn test_opcode_crossfade_family() {
+        let i = Instrument::from_sfz(
+            "<region> sample=a.wav
+            xfin_lokey=c4 xfin_hikey=e4 xfout_lokey=g4 xfout_hikey=b4
+            xfin_lovel=1 xfin_hivel=63 xfout_lovel=64 xfout_hivel=127
+            xfin_locc1=0 xfin_hicc1=63 xfout_locc1=64 xfout_hicc1=127
+            xf_keycurve=power xf_velcurve=gain xf_cccurve=power",
+            Path::new(""),
+        )
+        .unwrap();
+        let opcodes: Vec<&Opcode> = i.regions[0].opcodes.values().skip(1).collect();
+        assert_eq!(
+            opcodes,
+            [
+                &Opcode::xfin_lokey(60),
+                &Opcode::xfin_hikey(64),
+                &Opcode::xfout_lokey(67),
+                &Opcode::xfout_hikey(71),
+                &Opcode::xfin_lovel(1),
+                &Opcode::xfin_hivel(63),
+                &Opcode::xfout_lovel(64),
+                &Opcode::xfout_hivel(127),
+                &Opcode::xfin_loccN(1, 0),
+                &Opcode::xfin_hiccN(1, 63),
+                &Opcode::xfout_loccN(1, 64),
+                &Opcode::xfout_hiccN(1, 127),
+                &Opcode::xf_keycurve(xf_curve::power),
+                &Opcode::xf_velcurve(xf_curve::gain),
+                &Opcode::xf_cccurve(xf_curve::power),
+            ]
+        );
+        assert_eq!(Opcode::xfin_loccN(1, 0).to_string(), "xfin_locc1=0");
+
+        let mut lex = SfzToken::lexer("xfin_lokey=128");
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+    }
+
+    #[test]
+    fn test_opcode_loop_family() {
+        let i = Instrument::from_sfz(
+            "<region> sample=a.wav loop_mode=loop_continuous loop_start=1000 loop_end=20000
+            loop_type=alternate loop_count=4 loop_crossfade=0.05",
+            Path::new(""),
+        )
+        .unwrap();
+        let opcodes: Vec<&Opcode> = i.regions[0].opcodes.values().skip(1).collect();
+        assert_eq!(
+            opcodes,
+            [
+                &Opcode::loop_mode(loop_mode::loop_continuous),
+                &Opcode::loop_start(1000),
+                &Opcode::loop_end(20000),
+                &Opcode::loop_type(loop_type::alternate),
+                &Opcode::loop_count(4),
+                &Opcode::loop_crossfade(0.05),
+            ]
+        );
+
+        let mut lex = SfzToken::lexer("loop_count=-1 loop_start=-1");
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+    }
+
+    #[test]
+    fn test_comments() {
+        let tokens = |sfz| SfzToken::lexer(sfz).collect::<Vec<_>>();
+        let region = SfzToken::Header(Header::Region);
+
+        // a full-line comment
+        assert_eq!(
+            tokens("// cutoff=100 <group>\n<region> key=60"),
+            [region.clone(), SfzToken::Opcode(Opcode::key(60))]
+        );
+
+        // a trailing comment
+        assert_eq!(
+            tokens("<region> key=60 // middle C\nvolume=-3//quieter"),
+            [
+                region.clone(),
+                SfzToken::Opcode(Opcode::key(60)),
+                SfzToken::Opcode(Opcode::volume(-3.)),
+            ]
+        );
+
+        // a multi-line block comment
+        assert_eq!(
+            tokens("<region> key=60 /* the next\n<region> key=61\n** is disabled */ <region>"),
+            [region.clone(), SfzToken::Opcode(Opcode::key(60)), region]
+        );
+    }
+
+    #[test]
+    fn test_opcode_malformed() {
+        for code in [
+            "cutoff=",
+            "=5",
+            "=",
+            "cutoff=abc",
+            "lokey=z#9",
+            "a1b2c3d4=5",
+        ] {
+            let mut lex = SfzToken::lexer(code);
+            assert!(
+                !matches!(lex.next(), Some(SfzToken::Opcode(_))),
+                "`{}` must not parse",
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn test_opcode_curve_index_and_param_offset() {
+        let mut lex = SfzToken::lexer("curve_index=3 param_offset=100 curve_index=256");
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::curve_index(3))));
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::param_offset(100)))
+        );
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+    }
+
+    #[test]
+    fn test_opcode_curvecc() {
+        let mut lex =
+            SfzToken::lexer("amplitude_curvecc11=7 var02_curvecc1=200 eg1_pan_curvecc4=3");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::amplitude_curveccN(11, 7)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::varNN_curveccX(2, 1, 200)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::egN_pan_curveccX(1, 4, 3)))
+        );
+        assert_eq!(
+            Opcode::varNN_curveccX(2, 1, 200).to_string(),
+            "var02_curvecc1=200"
+        );
+    }
+
+    #[test]
+    fn test_opcode_eg_crossmodulation() {
+        let mut lex = SfzToken::lexer("eg1_eq2freq=500 eg1_freq_lfo1=2 eg3_eq1gain_oncc7=6");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::egN_eqXfreq(1, 2, "500".into())))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::egN_freq_lfoX(1, 1, "2".into())))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::egN_eqXgain_onccY(
+                3,
+                1,
+                7,
+                "6".into()
+            )))
+        );
+    }
+
+    #[test]
+    fn test_opcode_eg_ampeg() {
+        let mut lex = SfzToken::lexer("eg1_ampeg=1 eg12_ampeg=0");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::egN_ampeg(1, "1".into())))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::egN_ampeg(12, "0".into())))
+        );
+        assert_eq!(Opcode::egN_ampeg(1, "1".into()).to_string(), "eg1_ampeg=1");
+    }
+
+    #[test]
+    fn test_opcode_eg_flex() {
+        let mut lex = SfzToken::lexer("eg1_points=4 eg1_curve2=3");
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::egN_points(1, 4))));
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::egN_curveX(1, 2, 3)))
+        );
+        assert_eq!(Opcode::egN_curveX(1, 2, 3).to_string(), "eg1_curve2=3");
+        assert_eq!(Opcode::egN_curveX(1, 2, 3).params(), [1, 2]);
+    }
+
+    #[test]
+    fn test_opcode_eg_lofi() {
+        let mut lex = SfzToken::lexer("eg1_bitred=8 eg1_decim_oncc1=50");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::egN_bitred(1, "8".into())))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::egN_decim_onccX(1, 1, "50".into())))
+        );
+        assert_eq!(
+            Opcode::egN_decim_onccX(1, 1, "50".into()).to_string(),
+            "eg1_decim_oncc1=50"
+        );
+    }
+
+    #[test]
+    fn test_opcode_lfo() {
+        let mut lex = SfzToken::lexer("lfo1_freq=2.5 lfo1_phase=0.25 lfo2_wave=3 lfo1_phase=2");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::lfoN_freq(1, 2.5)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::lfoN_phase(1, 0.25)))
+        );
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::lfoN_wave(2, 3))));
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+    }
+
+    #[test]
+    fn test_opcode_lfo_modulation() {
+        let mut lex = SfzToken::lexer("lfo1_pitch=50 lfo1_volume_oncc11=3");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::lfoN_pitch(1, "50".into())))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::lfoN_volume_onccX(
+                1,
+                11,
+                "3".into()
+            )))
+        );
+        assert_eq!(
+            Opcode::lfoN_volume_onccX(1, 11, "3".into()).to_string(),
+            "lfo1_volume_oncc11=3"
+        );
+    }
+
+    #[test]
+    fn test_opcode_playback_names() {
+        let mut lex = SfzToken::lexer(
+            "off_mode=normal sw_vel=previous direction=reverse loop_type=alternate phase=invert",
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::off_mode(off_mode::normal)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::sw_vel(sw_vel::previous)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::direction(direction::reverse)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::loop_type(loop_type::alternate)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::phase(phase::invert)))
+        );
+        assert_eq!(
+            Opcode::loop_type(loop_type::alternate).to_string(),
+            "loop_type=alternate"
+        );
+        assert!(Opcode::off_mode(off_mode::fast).is_default());
+
+        assert_eq!(direction::from_name("sideways"), None);
+        assert_eq!(loop_type::from_name("reverse"), None);
+        let mut lex = SfzToken::lexer("direction=sideways");
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+    }
+
+    #[test]
+    fn test_opcode_on_off() {
+        let mut lex = SfzToken::lexer("comp_stlink=on note_selfmask=off static_stereo=yes");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::comp_stlink(on_off::on)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::note_selfmask(on_off::off)))
+        );
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+        assert_eq!(
+            Opcode::comp_stlink(on_off::on).to_string(),
+            "comp_stlink=on"
+        );
+        assert!(Opcode::rt_dead(on_off::off).is_default());
+    }
+
+    #[test]
+    fn test_opcode_aliases() {
+        let mut lex = SfzToken::lexer("loopmode=one_shot loopstart=1000 resonance2_cc1=6");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::loop_mode(loop_mode::one_shot)))
+        );
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::loop_start(1000))));
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::resonance2_onccN(1, 6.)))
+        );
+    }
+
+    #[test]
+    fn test_opcode_aliases_oncc() {
+        let lex =
+            SfzToken::lexer("cutoff_oncc74=1200 gain_oncc7=-6 delay_oncc1=0.5 offset_oncc2=100");
+        assert_eq!(
+            lex.collect::<Vec<_>>(),
+            [
+                Opcode::cutoff_ccN(74, 1200),
+                Opcode::gain_ccN(7, -6.),
+                Opcode::delay_ccN(1, 0.5),
+                Opcode::offset_ccN(2, 100),
+            ]
+            .map(SfzToken::Opcode)
+        );
+
+        let lex = SfzToken::lexer(
+            "ampeg_attack_oncc1=1 ampeg_delay_oncc2=2 ampeg_start_oncc3=3 \
+            ampeg_hold_oncc4=4 ampeg_decay_oncc5=5 ampeg_sustain_oncc6=6 ampeg_release_oncc7=7",
+        );
+        assert_eq!(
+            lex.collect::<Vec<_>>(),
+            [
+                Opcode::ampeg_attackccN(1, 1.),
+                Opcode::ampeg_delayccN(2, 2.),
+                Opcode::ampeg_startccN(3, 3.),
+                Opcode::ampeg_holdccN(4, 4.),
+                Opcode::ampeg_decayccN(5, 5.),
+                Opcode::ampeg_sustainccN(6, 6.),
+                Opcode::ampeg_releaseccN(7, 7.),
+            ]
+            .map(SfzToken::Opcode)
+        );
+
+        // the `_onccN` opcodes of their own are not aliases
+        let mut lex = SfzToken::lexer("cutoff2_oncc1=100");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::cutoff2_onccN(1, 100)))
+        );
+    }
+
+    #[test]
+    fn test_opcode_eq_modulation() {
+        let mut lex =
+            SfzToken::lexer("eq1_freqcc11=500 eq2_gaincc11=3 eq3_vel2freq=-200 eq1_vel2gain=6");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::eqN_freqccX(1, 11, 500.)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::eqN_gainccX(2, 11, 3.)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::eqN_vel2freq(3, -200.)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::eqN_vel2gain(1, 6.)))
+        );
+        assert_eq!(Opcode::eqN_gainccX(2, 11, 3.).to_string(), "eq2_gaincc11=3");
+
+        // out of range
+        let mut lex = SfzToken::lexer("eq2_gaincc11=30");
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+    }
+
+    #[test]
+    fn test_opcode_xf_curve() {
+        let mut lex = SfzToken::lexer("xf_velcurve=gain xf_keycurve=power xf_cccurve=linear");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::xf_velcurve(xf_curve::gain)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::xf_keycurve(xf_curve::power)))
+        );
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+        assert!(Opcode::xf_keycurve(xf_curve::power).is_default());
+    }
+
+    #[test]
+    fn test_opcode_lfo_freq_modulation() {
+        let mut lex = SfzToken::lexer("lfo1_freq_oncc11=2 lfo2_freq_smoothcc1=10");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::lfoN_freq_onccX(1, 11, "2".into())))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::lfoN_freq_smoothccX(
+                2,
+                1,
+                "10".into()
+            )))
+        );
+    }
+
+    #[test]
+    fn test_opcode_hdcc_gating() {
+        let mut lex =
+            SfzToken::lexer("on_lohdcc1=0.2 on_hihdcc1=0.8 stop_lohdcc64=-1 start_hihdcc2=-0.5");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::on_lohdccN(1, 0.2)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::on_hihdccN(1, 0.8)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::stop_lohdccN(64, -1.)))
+        );
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+    }
+
+    #[test]
+    fn test_opcode_lfo_steps() {
+        let mut lex = SfzToken::lexer("lfo1_steps=4 lfo1_step2=50 lfo3_step1=-100");
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::lfoN_steps(1, 4))));
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::lfoN_stepX(1, 2, 50.)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::lfoN_stepX(3, 1, -100.)))
+        );
+        assert_eq!(Opcode::lfoN_stepX(1, 2, 50.).to_string(), "lfo1_step2=50");
+    }
+
+    #[test]
+    fn test_opcode_effect_mix() {
+        let mut lex = SfzToken::lexer("reverb_wet=30 reverb_dry=70 delay_wet=101");
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::reverb_wet(30.))));
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::reverb_dry(70.))));
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+    }
+
+    #[test]
+    fn test_opcode_effect_filter() {
+        let mut lex = SfzToken::lexer("filter_type=lpf_2p filter_cutoff=1000 filter_resonance=3");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::filter_type(fil_type::lpf_2p)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::filter_cutoff(1000.)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::filter_resonance(3.)))
+        );
+    }
+
+    #[test]
+    fn test_opcode_effect_cc_modulation() {
+        let mut lex = SfzToken::lexer("gate_oncc1=1 strings_wet_oncc11=50 comp_threshold=-12");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::gate_onccN(1, "1".into())))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::strings_wet_onccN(11, 50.)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::comp_threshold(-12.)))
+        );
+        assert_eq!(
+            Opcode::strings_wet_onccN(11, 50.).to_string(),
+            "strings_wet_oncc11=50"
+        );
+    }
+
+    #[test]
+    fn test_opcode_effect_times_and_frequencies() {
+        let mut lex = SfzToken::lexer("phaser_freq=0.3 reverb_predelay=20 comp_attack=0.005");
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::phaser_freq(0.3))));
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::reverb_predelay(20.)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::comp_attack(0.005)))
+        );
+
+        let mut lex = SfzToken::lexer("gate_release=fast");
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+    }
+
+    #[test]
+    fn test_opcode_effect_waveform() {
+        let mut lex =
+            SfzToken::lexer("apan_waveform=sine phaser_waveform=triangle apan_waveform=6");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::apan_waveform(waveform::sine)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::phaser_waveform(
+                waveform::triangle
+            )))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::apan_waveform(waveform::saw_up)))
+        );
+
+        let mut lex = SfzToken::lexer("phaser_waveform=noise");
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+        assert_eq!(
+            Opcode::phaser_waveform(waveform::saw_down).to_string(),
+            "phaser_waveform=7"
+        );
+    }
+
+    #[test]
+    fn test_opcode_smoothing() {
+        let mut lex = SfzToken::lexer(
+            "amplitude_smoothcc11=20 cutoff2_smoothcc74=15 lfo2_smooth=3.5 lfo1_eq3gain_smoothcc4=1",
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::amplitude_smoothccN(11, 20.)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::cutoff2_smoothccN(74, 15.)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::lfoN_smooth(2, 3.5)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::lfoN_eqXgain_smoothccY(
+                1, 3, 4, 1.
+            )))
+        );
+
+        let mut lex = SfzToken::lexer("bend_smooth=-1");
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+        assert_eq!(
+            Opcode::amplitude_smoothccN(11, 20.).to_string(),
+            "amplitude_smoothcc11=20"
+        );
+    }
+
+    #[test]
+    fn test_opcode_noise_step_tone() {
+        let mut lex = SfzToken::lexer("noise_step=10 noise_tone=50 noise_tone_oncc1=25");
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::noise_step(10))));
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::noise_tone(50))));
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::noise_tone_onccN(1, 25)))
+        );
+
+        let mut lex = SfzToken::lexer("noise_step_oncc7=101");
+        assert_eq!(lex.next(), Some(SfzToken::Error));
+        assert_eq!(
+            Opcode::noise_step_onccN(7, 100).to_string(),
+            "noise_step_oncc7=100"
+        );
+    }
+
+    #[test]
+    fn test_opcode_effect_filter_type() {
+        let mut lex = SfzToken::lexer(
+            "noise_filter=lpf_2p delay_filter=hpf_1p static_filter=off delay_filter=hpf_2p",
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::noise_filter(
+                effect_filter::Filter(fil_type::lpf_2p)
+            )))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::delay_filter(
+                effect_filter::Filter(fil_type::hpf_1p)
+            )))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::static_filter(effect_filter::off)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::delay_filter(
+                effect_filter::Filter(fil_type::hpf_2p)
+            )))
+        );
+        assert_eq!(
+            Opcode::delay_filter(effect_filter::Filter(fil_type::hpf_1p)).to_string(),
+            "delay_filter=hpf_1p"
+        );
+    }
+
+    #[test]
+    fn test_opcode_type() {
+        let mut lex = SfzToken::lexer("type=reverb type=static type=com.mda.Leslie");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::r#type(effect_type::reverb)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::r#type(effect_type::r#static)))
+        );
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::r#type(effect_type::Other(
+                "com.mda.Leslie".into()
+            ))))
+        );
+        assert_eq!(
+            Opcode::r#type(effect_type::r#static).to_string(),
+            "type=static"
+        );
+    }
+
+    #[test]
+    fn test_opcode_sample() {
+        let mut lex = SfzToken::lexer("sample=MOHorn mute_A#1_v1_1.wav");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::sample(PathBuf::from(
+                "MOHorn mute_A#1_v1_1.wav"
+            ))))
+        );
+
+        // The equal sign is also supported in the filename
+        let mut lex = SfzToken::lexer("sample=equal_sign_=_doesn't_fail.wav");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::sample(PathBuf::from(
+                "equal_sign_=_doesn't_fail.wav"
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_opcode_vendor_specific() {
+        let line = "vendor_specific=<x> a=1, b=\"two\" // {3}";
+        let mut lex = SfzToken::lexer(line);
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::vendor_specific(
+                "<x> a=1, b=\"two\" // {3}".to_string()
+            )))
+        );
+        assert_eq!(lex.next(), None);
+
+        // it only takes until the end of the line
+        let mut lex = SfzToken::lexer("vendor_specific=a b\r\nvolume=1");
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::vendor_specific("a b".to_string())))
+        );
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::volume(1.))));
+
+        let i =
+            Instrument::from_sfz(&format!("<region> sample=a.wav {line}"), Path::new("")).unwrap();
+        assert!(i.to_sfz().ends_with(&format!("\n{line}\n")));
+    }
+}
+
+#[cfg(test)]
+mod tests_ranges {
+    use super::*;
+
+    /// Opcodes whose parsing range doesn't match their documentation yet.
+    static KNOWN_MISMATCHES: &[&str] = &[];
+
+    /// Returns the opcodes with a numeric range in their documentation,
+    /// as tuples of (name, value type, min, max).
+    fn documented_ranges() -> Vec<(String, String, f64, f64)> {
+        let doc_range =
+            Regex::new(r"^\s*/// - range: (-?\d+(?:\.\d+)?) to (-?\d+(?:\.\d+)?)\b").unwrap();
+        let variant = Regex::new(r"^    (\w+)\((.*)\),").unwrap();
+
+        let mut ranges = Vec::new();
+        let mut range = None;
+        for line in include_str!("opcode.rs").lines() {
+            if let Some(c) = doc_range.captures(line) {
+                range = Some((c[1].parse().unwrap(), c[2].parse().unwrap()));
+            } else if let Some(c) = variant.captures(line) {
+                let value_type = c[2].rsplit(", ").next().unwrap().to_string();
+                if let Some((min, max)) = range.take() {
+                    ranges.push((c[1].to_string(), value_type, min, max));
+                }
+            }
+        }
+        ranges
+    }
+
+    /// Returns the names of the opcodes that have a parsing arm.
+    pub(super) fn handled_opcodes() -> Vec<String> {
+        let arm = Regex::new(r#"^\s*\("(\w+)", "#).unwrap();
+        include_str!("parse.rs")
+            .lines()
+            .filter_map(|line| arm.captures(line).map(|c| c[1].to_string()))
+            .collect()
+    }
+
+    /// The bounds of the values representable by each type.
+    fn type_bounds(value_type: &str) -> (f64, f64) {
+        match value_type {
+            "u8" => (u8::MIN.into(), u8::MAX.into()),
+            "i8" => (i8::MIN.into(), i8::MAX.into()),
+            "u16" => (u16::MIN.into(), u16::MAX.into()),
+            "i16" => (i16::MIN.into(), i16::MAX.into()),
+            "u32" | "UndefinedUnsignedInteger" => (u32::MIN.into(), u32::MAX.into()),
+            "UndefinedInteger" => (i32::MIN.into(), i32::MAX.into()),
+            _ => (f64::MIN, f64::MAX),
+        }
+    }
+
+    fn parses(name: &str, value: f64) -> bool {
+        let code = format!("{}={}", Opcode::numerize_pars(name), value);
+        matches![
+            SfzToken::lexer(&code).next(),
+            Some(SfzToken::Opcode(o)) if o.str_name() == name
+        ]
+    }
+
+    #[test]
+    fn test_parse_ranges_match_docs() {
+        let handled = handled_opcodes();
+        let mut checked = 0;
+        let mut mismatches = Vec::new();
+
+        for (name, value_type, min, max) in documented_ranges() {
+            if !handled.contains(&name) || KNOWN_MISMATCHES.contains(&name.as_str()) {
+                continue;
+            }
+            let (type_min, type_max) = type_bounds(&value_type);
+            let step = if type_max == f64::MAX { 0.1 } else { 1. };
+
+            let mut expected = vec![(min, true), (max, true)];
+            if min - step >= type_min {
+                expected.push((min - step, false));
+            }
+            if max + step <= type_max {
+                expected.push((max + step, false));
+            }
+            for (value, valid) in expected {
+                if parses(&name, value) != valid {
+                    mismatches.push(format!("{}={}", name, value));
+                }
+            }
+            checked += 1;
+        }
+        assert!(checked > 0);
+        assert!(mismatches.is_empty(), "mismatched ranges: {:?}", mismatches);
+    }
+}
+
+#[cfg(test)]
+mod tests_token {
+    use super::*;
+
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_sfz_simple() {
+        let mut lex = SfzToken::lexer(
+            "<region>
+sample=with space/MOHorn_mute_A#1_v1_1.wav lokey=46 hikey=48
+pitch_keycenter=46
+lovel=0 hivel=62
+volume=2.0",
+        );
+
+        assert_eq!(lex.next(), Some(SfzToken::Header(Header::Region)));
+        assert_eq!(lex.span(), 0..8);
+
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::sample(PathBuf::from(
+                "with space/MOHorn_mute_A#1_v1_1.wav"
+            ))))
+        );
+        // recognize multiple opcodes in the same line
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::lokey(46))));
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::hikey(48))));
+        assert_eq!(
+            lex.next(),
+            Some(SfzToken::Opcode(Opcode::pitch_keycenter(46)))
+        );
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::lovel(0))));
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::hivel(62))));
+        assert_eq!(lex.next(), Some(SfzToken::Opcode(Opcode::volume(2.0))));
+    }
+}
+
+#[cfg(test)]
+mod tests_parameters {
+
+    use super::{Opcode, Regex};
+    use crate::sfz::opcodes::aliases::{canonical_name, OPCODE_ALIASES};
+    use log::trace;
+
+    #[test]
+    fn test_parse_name() {
+        assert_eq!(Opcode::parse_name("cutoff"), ("cutoff".into(), vec![]));
+        assert_eq!(Opcode::parse_name("hicc88"), ("hiccN".into(), vec![88]));
+        assert_eq!(
+            Opcode::parse_name("var22_oncc44"),
+            ("varNN_onccX".into(), vec![22, 44])
+        );
+        assert_eq!(
+            Opcode::parse_name("lfo111_eq44gain_oncc55"),
+            ("lfoN_eqXgain_onccY".into(), vec![111, 44, 55])
+        );
+        assert_eq!(
+            Opcode::numerize_pars("lfoN_eqXgain_onccY"),
+            "lfo11_eq22gain_oncc33"
+        );
+        assert_eq!(Opcode::numerize_pars("varNN_onccX"), "var11_oncc22");
+    }
+
+    #[test]
+    fn test_parse_all_opcodes() {
+        // DATA
+        //
+        // [999] indicates the current number opcodes at 2020-09-11
+        // N and NN gets treated equally as being 1 parameter.
+
+        // file names are suffixed with the number of opcodes contained
+        static OPCODES_SFZ_V1: &str = include_str!("data/opcodes-v1_184.txt");
+        static OPCODES_SFZ_V2: &str = include_str!("data/opcodes-v2_166.txt");
+        static OPCODES_SFZ_ARIA_EXT: &str = include_str!("data/opcodes-aria-extension_78.txt");
+        static OPCODES_SFZ_CAKEWALK_V2: &str = include_str!("data/opcodes-cakewalk-v2_162.txt");
+        let opcodes_total = format!(
+            "{}{}{}{}", // the list of all the opcodes
+            OPCODES_SFZ_V1, OPCODES_SFZ_V2, OPCODES_SFZ_ARIA_EXT, OPCODES_SFZ_CAKEWALK_V2,
         );
         let mut opcodes_params = String::new(); // …with any parameters
         let mut opcodes_params_3 = String::new(); // …with 3 parameters
@@ -488,7 +2081,7 @@ mod tests_parameters {
 
         // Test parsing the opcodes with 3 parameters
         for o in opcodes_params_3.lines() {
-            let o_new = Opcode::numerize_pars(&o);
+            let o_new = Opcode::numerize_pars(o);
             let (o_parsed, params) = Opcode::parse_name(&o_new);
             assert_eq!(&o, &o_parsed);
             assert_eq!(params, vec![11, 22, 33]);
@@ -496,7 +2089,7 @@ mod tests_parameters {
 
         // Test parsing the opcodes with 2 parameters
         for o in opcodes_params_2.lines() {
-            let o_new = Opcode::numerize_pars(&o);
+            let o_new = Opcode::numerize_pars(o);
             let (o_parsed, params) = Opcode::parse_name(&o_new);
             assert_eq!(&o, &o_parsed);
             assert_eq!(params, vec![11, 22]);
@@ -504,7 +2097,7 @@ mod tests_parameters {
 
         // Test parsing the opcodes with 1 parameters
         for o in opcodes_params_1.lines() {
-            let o_new = Opcode::numerize_pars(&o);
+            let o_new = Opcode::numerize_pars(o);
             let (o_parsed, params) = Opcode::parse_name(&o_new);
             assert_eq!(&o, &o_parsed);
             assert_eq!(params, vec![11]);
@@ -512,10 +2105,70 @@ mod tests_parameters {
 
         // Test parsing the opcodes with no parameters
         for o in opcodes_no_params.lines() {
-            let o_new = Opcode::numerize_pars(&o);
+            let o_new = Opcode::numerize_pars(o);
             let (o_parsed, params) = Opcode::parse_name(&o_new);
             assert_eq!(&o, &o_parsed);
-            assert_eq!(params, vec![]);
+            assert_eq!(params, Vec::<u8>::new());
+        }
+    }
+
+    /// The number of opcodes without a parsing arm.
+    ///
+    /// Lower it each time new parsing arms are added.
+    const UNHANDLED_OPCODES: usize = 294;
+
+    #[test]
+    fn test_parse_aliases() {
+        let handled = super::tests_ranges::handled_opcodes();
+        for (alias, canonical) in OPCODE_ALIASES.entries() {
+            assert!(
+                !handled.iter().any(|h| h == alias),
+                "{alias} has its own arm"
+            );
+            assert!(
+                handled.iter().any(|h| h == canonical),
+                "{canonical} has no parsing arm"
+            );
         }
     }
+
+    #[test]
+    fn test_parse_canonical_names() {
+        assert_eq!(canonical_name("benddown"), Some("bend_down"));
+        assert_eq!(canonical_name("cutoff_onccN"), Some("cutoff_ccN"));
+        assert_eq!(
+            canonical_name("ampeg_attack_onccN"),
+            Some("ampeg_attackccN")
+        );
+        assert_eq!(canonical_name("resonance2_onccN"), None);
+        assert_eq!(canonical_name("cutoff_ccN"), None);
+        assert_eq!(canonical_name("foo_onccN"), None);
+    }
+
+    #[test]
+    fn test_parse_coverage() {
+        let handled = super::tests_ranges::handled_opcodes();
+        let opcodes = [
+            include_str!("data/opcodes-v1_184.txt"),
+            include_str!("data/opcodes-v2_166.txt"),
+            include_str!("data/opcodes-aria-extension_78.txt"),
+            include_str!("data/opcodes-cakewalk-v2_162.txt"),
+        ]
+        .concat();
+
+        let mut unhandled = Vec::new();
+        for o in opcodes.lines() {
+            let (name, _) = Opcode::parse_name(&Opcode::numerize_pars(o));
+            if !handled.contains(&name) && !unhandled.contains(&name) {
+                unhandled.push(name);
+            }
+        }
+        unhandled.sort();
+
+        assert_eq!(
+            unhandled.len(),
+            UNHANDLED_OPCODES,
+            "opcodes without a parsing arm: {unhandled:?}"
+        );
+    }
 }
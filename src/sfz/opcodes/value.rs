@@ -0,0 +1,38 @@
+//! Generic access to the values of the opcodes.
+
+use std::any::Any;
+
+use crate::sfz::Opcode;
+
+impl Opcode {
+    /// Get the value of the opcode, if it's of type `T`
+    ///
+    /// For example `Opcode::cutoff(500.).value::<f32>()` returns `Some(&500.)`,
+    /// while `Opcode::cutoff(500.).value::<u8>()` returns `None`.
+    /// The numeric parameters in the name are not part of the value.
+    pub fn value<T: Any>(&self) -> Option<&T> {
+        self.value_field()?.as_any().downcast_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests_value {
+    use crate::sfz::{types::loop_mode, Opcode};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_opcode_value() {
+        assert_eq!(Opcode::cutoff(500.).value::<f32>(), Some(&500.));
+        assert_eq!(Opcode::cutoff(500.).value::<u8>(), None);
+        assert_eq!(Opcode::loccN(1, 64).value::<u8>(), Some(&64));
+        assert_eq!(
+            Opcode::loop_mode(loop_mode::one_shot).value(),
+            Some(&loop_mode::one_shot)
+        );
+        assert_eq!(
+            Opcode::sample(PathBuf::from("a.wav")).value(),
+            Some(&PathBuf::from("a.wav"))
+        );
+        assert_eq!(Opcode::hint_().value::<f32>(), None);
+    }
+}
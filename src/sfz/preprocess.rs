@@ -0,0 +1,308 @@
+//! Processes the SFZ directives, like `#define`, before parsing the opcodes.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+use crate::error::{Error, Result};
+use crate::utils::fix_path_separators;
+
+/// The maximum depth of nested `#include` directives,
+/// for detecting files that include themselves.
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Matches an `#include "path"` line, capturing the path.
+static INCLUDE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^\s*#include\s+"([^"]+)""#).unwrap());
+
+/// Matches a `#define $NAME value` line, capturing the name and the value.
+static DEFINE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*#define\s+\$(\w+)\s+(\S+)").unwrap());
+
+/// Matches a `$NAME` variable, capturing the name.
+static VARIABLE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\$(\w+)").unwrap());
+
+/// The SFZ code after processing the directives, and the directives found.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Preprocessed {
+    /// The code, with the included files inlined and the variables substituted.
+    pub(crate) code: String,
+
+    /// For each line of the code, where it comes from.
+    pub(crate) lines: Vec<LineOrigin>,
+
+    /// The variables defined, with their values.
+    pub(crate) defines: IndexMap<String, String>,
+
+    /// The paths of the included files, as written.
+    pub(crate) includes: Vec<PathBuf>,
+}
+
+/// Where a line of the preprocessed code comes from.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct LineOrigin {
+    /// The line of the original code, counting from 1.
+    pub(crate) line: usize,
+
+    /// The index of the include it comes from, if any, and its line
+    /// in the included file, counting from 1.
+    ///
+    /// The lines of the nested includes have the line of their
+    /// `#include` directive in the included file.
+    pub(crate) include: Option<(usize, usize)>,
+
+    /// The variables substituted in the line, in order.
+    pub(crate) substitutions: Vec<Substitution>,
+
+    /// The variables used in the line without being defined before,
+    /// which are left as written.
+    pub(crate) undefined: Vec<String>,
+}
+
+/// A variable substituted by its value in a line of the code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Substitution {
+    /// The column where the value starts in the substituted line, counting from 0.
+    pub(crate) col: usize,
+
+    /// The number of characters of the value.
+    pub(crate) value_len: usize,
+
+    /// The number of characters of the variable, including the `$`.
+    pub(crate) variable_len: usize,
+}
+
+impl LineOrigin {
+    /// Returns the column of the line before substituting the variables,
+    /// from a column of the substituted line, both counting from 1
+    ///
+    /// A column inside a substituted value returns the column of its variable.
+    pub(crate) fn unsubstituted_col(&self, col: usize) -> usize {
+        let mut shift = 0;
+        for s in &self.substitutions {
+            if col > s.col + s.value_len {
+                shift += s.variable_len as isize - s.value_len as isize;
+            } else if col > s.col {
+                return (s.col as isize + shift) as usize + 1;
+            } else {
+                break;
+            }
+        }
+        (col as isize + shift) as usize
+    }
+}
+
+/// Inlines the files included with `#include "path"`, and then substitutes
+/// the variables declared with `#define $NAME value`.
+///
+/// The included paths are relative to `sfz_path`. Only the includes of the
+/// original code are listed, the ones nested inside them are inlined with them.
+///
+pub(crate) fn preprocess(sfz: &str, sfz_path: &Path) -> Result<Preprocessed> {
+    let mut includes = Vec::new();
+    let mut code = String::with_capacity(sfz.len());
+    let mut lines = Vec::new();
+
+    for (n, line) in sfz.split_inclusive('\n').enumerate() {
+        match INCLUDE.captures(line) {
+            Some(captures) => {
+                let (included, included_lines) =
+                    expand_includes(&read_include(sfz_path, &captures[1])?, sfz_path, 1)?;
+                let index = includes.len();
+                includes.push(PathBuf::from(&captures[1]));
+                lines.extend(included_lines.into_iter().map(|l| LineOrigin {
+                    line: n + 1,
+                    include: Some((index, l)),
+                    ..LineOrigin::default()
+                }));
+                push_lines(&mut code, &included);
+            }
+            None => {
+                lines.push(LineOrigin {
+                    line: n + 1,
+                    ..LineOrigin::default()
+                });
+                push_lines(&mut code, line);
+            }
+        }
+    }
+
+    let (code, defines) = substitute_defines(&code, &mut lines);
+    Ok(Preprocessed {
+        code,
+        lines,
+        defines,
+        includes,
+    })
+}
+
+/// Reads an included file.
+fn read_include(sfz_path: &Path, path: &str) -> Result<String> {
+    Ok(fs::read_to_string(
+        sfz_path.join(fix_path_separators(path)),
+    )?)
+}
+
+/// Inlines the included files recursively.
+///
+/// Returns the code, and the line of `sfz` where each of its lines comes from.
+fn expand_includes(sfz: &str, sfz_path: &Path, depth: usize) -> Result<(String, Vec<usize>)> {
+    if depth > MAX_INCLUDE_DEPTH {
+        return Err(Error::OutOfBounds(format!(
+            "The includes are nested deeper than {MAX_INCLUDE_DEPTH} levels"
+        )));
+    }
+    let mut code = String::with_capacity(sfz.len());
+    let mut lines = Vec::new();
+    for (n, line) in sfz.split_inclusive('\n').enumerate() {
+        match INCLUDE.captures(line) {
+            Some(captures) => {
+                let included = read_include(sfz_path, &captures[1])?;
+                let (included, included_lines) = expand_includes(&included, sfz_path, depth + 1)?;
+                lines.extend(included_lines.iter().map(|_| n + 1));
+                push_lines(&mut code, &included);
+            }
+            None => {
+                lines.push(n + 1);
+                push_lines(&mut code, line);
+            }
+        }
+    }
+    Ok((code, lines))
+}
+
+/// Appends some lines, ending them with a newline if they don't.
+fn push_lines(code: &mut String, lines: &str) {
+    code.push_str(lines);
+    if !lines.is_empty() && !lines.ends_with('\n') {
+        code.push('\n');
+    }
+}
+
+/// Substitutes the variables declared with `#define $NAME value`
+/// with their values, in the code that follows each definition.
+///
+/// The lines with the definitions are left empty, so that the lines of
+/// the resulting code correspond to the original ones. Comments are left
+/// untouched. The substitutions made in each line are recorded in the
+/// corresponding one of `lines`, if there is one.
+///
+/// The variables used without being defined before are left as written,
+/// since a `$` can also be part of a file name or of a block comment,
+/// and are recorded in the corresponding one of `lines`.
+///
+pub(crate) fn substitute_defines(
+    sfz: &str,
+    lines: &mut [LineOrigin],
+) -> (String, IndexMap<String, String>) {
+    let mut defines = IndexMap::new();
+    let mut output = String::with_capacity(sfz.len());
+    for (n, line) in sfz.split_inclusive('\n').enumerate() {
+        if let Some(captures) = DEFINE.captures(line) {
+            defines.insert(captures[1].to_string(), captures[2].to_string());
+            if line.ends_with('\n') {
+                output.push('\n');
+            }
+            continue;
+        }
+
+        let (code, comment) = match line.find("//") {
+            Some(i) => line.split_at(i),
+            None => (line, ""),
+        };
+        let mut undefined = Vec::new();
+        let mut substitutions = Vec::new();
+        // the difference in characters between the substituted and the original code
+        let mut shift = 0isize;
+        let code = VARIABLE.replace_all(code, |c: &Captures| match defines.get(&c[1]) {
+            Some(value) => {
+                let variable = c.get(0).unwrap();
+                let substitution = Substitution {
+                    col: (code[..variable.start()].chars().count() as isize + shift) as usize,
+                    value_len: value.chars().count(),
+                    variable_len: variable.as_str().chars().count(),
+                };
+                shift += substitution.value_len as isize - substitution.variable_len as isize;
+                substitutions.push(substitution);
+                value.clone()
+            }
+            None => {
+                undefined.push(c[1].to_string());
+                c[0].to_string()
+            }
+        });
+        if let Some(origin) = lines.get_mut(n) {
+            origin.substitutions = substitutions;
+            origin.undefined = undefined;
+        }
+        output.push_str(&code);
+        output.push_str(comment);
+    }
+    (output, defines)
+}
+
+#[cfg(test)]
+mod tests_preprocess {
+    use super::*;
+
+    #[test]
+    fn test_substitute_defines() {
+        let sfz = "#define $KEY 60\n<region> lokey=$KEY // $KEY\n";
+        let (code, defines) = substitute_defines(sfz, &mut []);
+        assert_eq!(code, "\n<region> lokey=60 // $KEY\n");
+        assert_eq!(defines["KEY"], "60");
+    }
+
+    #[test]
+    fn test_substitute_defines_columns() {
+        let sfz = "#define $F 1000\n#define $LONG_NAME 5\ncutoff=$F pan=$LONG_NAME x=1\n";
+        let mut lines = vec![LineOrigin::default(); 3];
+        let (code, _) = substitute_defines(sfz, &mut lines);
+        assert_eq!(code.lines().nth(2), Some("cutoff=1000 pan=5 x=1"));
+
+        let origin = &lines[2];
+        assert_eq!(origin.substitutions.len(), 2);
+        // before, inside and after the substituted values
+        assert_eq!(origin.unsubstituted_col(1), 1);
+        assert_eq!(origin.unsubstituted_col(9), 8);
+        assert_eq!(origin.unsubstituted_col(13), 11);
+        assert_eq!(origin.unsubstituted_col(17), 15);
+        assert_eq!(origin.unsubstituted_col(19), 26);
+    }
+
+    #[test]
+    fn test_substitute_defines_undefined() {
+        let sfz = "<region> lokey=$KEY\n<region> sample=a$b.wav /* cost $5 */\n";
+        let mut lines = vec![LineOrigin::default(); 2];
+        let (code, _) = substitute_defines(sfz, &mut lines);
+        assert_eq!(code, sfz);
+        assert_eq!(lines[0].undefined, ["KEY"]);
+        assert_eq!(lines[1].undefined, ["b", "5"]);
+        assert!(lines[1].substitutions.is_empty());
+    }
+
+    #[test]
+    fn test_preprocess_includes() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+        let pre = preprocess(
+            "#define $KEY 1\n#include \"directives_regions.sfz\"\n<region> sample=c.wav",
+            &fixtures,
+        )
+        .unwrap();
+        assert_eq!(pre.includes, [PathBuf::from("directives_regions.sfz")]);
+        assert_eq!(pre.code.lines().count(), pre.lines.len());
+        assert_eq!((pre.lines[0].line, pre.lines[0].include), (1, None));
+        assert_eq!((pre.lines[1].line, pre.lines[1].include), (2, Some((0, 1))));
+        assert_eq!((pre.lines[2].line, pre.lines[2].include), (2, Some((0, 2))));
+        let last = pre.lines.last().unwrap();
+        assert_eq!((last.line, last.include), (3, None));
+
+        assert!(matches!(
+            preprocess("#include \"missing.sfz\"", &fixtures),
+            Err(Error::IOError(_))
+        ));
+    }
+}
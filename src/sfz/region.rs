@@ -1,7 +1,9 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fmt::Debug;
+use std::ops::RangeInclusive;
 
-use crate::sfz::{Opcode, OpcodeMap};
+use crate::sfz::{types::loop_mode, Instrument, MidiContext, Opcode, OpcodeMap};
+use crate::utils;
 
 /// A Region is the basic component of an instrument.
 /// An instrument is defined by one or more regions.
@@ -21,12 +23,51 @@ use crate::sfz::{Opcode, OpcodeMap};
 /// Consequently, all conditions must be matched for the region to play.
 ///
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Region {
     /// The opcodes of this group are applied and will override the defaults.
     pub group: Option<usize>,
 
+    /// The opcodes of this master are applied after the group ones.
+    pub master: Option<usize>,
+
     /// This list of opcodes will override both the default and inherited opcodes.
     pub opcodes: OpcodeMap,
+
+    /// The line of the SFZ code where this region's header is, if parsed.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) source_line: Option<usize>,
+
+    /// The index of the included file where this region's header is, if any.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) include: Option<usize>,
+}
+
+// The source line and the include are not part of the region's definition.
+impl PartialEq for Region {
+    fn eq(&self, other: &Self) -> bool {
+        self.group == other.group && self.master == other.master && self.opcodes == other.opcodes
+    }
+}
+
+/// Returns true if the value is within the inherited range of a region,
+/// using the provided defaults for the bounds that aren't defined.
+macro_rules! within {
+    ($region:expr, $instrument:expr, $value:expr, $lo:ident, $hi:ident, $default_lo:expr, $default_hi:expr) => {{
+        let lo = $region
+            .inherited_value($instrument, |o| match o {
+                Opcode::$lo(v) => Some(*v),
+                _ => None,
+            })
+            .unwrap_or($default_lo);
+        let hi = $region
+            .inherited_value($instrument, |o| match o {
+                Opcode::$hi(v) => Some(*v),
+                _ => None,
+            })
+            .unwrap_or($default_hi);
+        (lo..=hi).contains(&$value)
+    }};
 }
 
 impl Region {
@@ -40,13 +81,15 @@ impl Region {
     pub fn with_group(group: usize) -> Self {
         Self {
             group: Some(group),
-            opcodes: HashMap::new(),
+            ..Self::default()
         }
     }
 
     /// Add an opcode to this Region
+    ///
+    /// An opcode with the same full name is replaced, keeping its position.
     pub fn add_opcode(&mut self, opcode: &Opcode) {
-        self.opcodes.insert(opcode.str_name(), opcode.clone());
+        self.opcodes.insert(opcode.full_name(), opcode.clone());
     }
 
     /// Set the group of this Region
@@ -58,4 +101,700 @@ impl Region {
     pub fn group(&self) -> Option<usize> {
         self.group
     }
+
+    /// Get the master of this Region
+    pub fn master(&self) -> Option<usize> {
+        self.master
+    }
+
+    /// Get the line of the SFZ code where this region starts, counting from 1
+    ///
+    /// Only regions parsed from SFZ code have it.
+    pub fn source_line(&self) -> Option<usize> {
+        self.source_line
+    }
+
+    /// Get an opcode of this region by its full name, like `locc1`
+    pub fn opcode(&self, name: &str) -> Option<&Opcode> {
+        self.opcodes.get(name)
+    }
+
+    /// Get the value of an opcode of this region, if it's of type `T`
+    pub fn value<T: std::any::Any + Copy>(&self, name: &str) -> Option<T> {
+        self.opcode(name)?.value().copied()
+    }
+
+    /// Get the value of an `f32` opcode of this region
+    pub fn f32(&self, name: &str) -> Option<f32> {
+        self.value(name)
+    }
+
+    /// Get the value of a `u8` opcode of this region
+    pub fn u8(&self, name: &str) -> Option<u8> {
+        self.value(name)
+    }
+
+    /// Get the index of the [`Instrument::includes`][crate::Instrument::includes]
+    /// file where this region starts, if it was parsed from an included file.
+    pub fn include(&self) -> Option<usize> {
+        self.include
+    }
+
+    /// Returns the opcode maps this region inherits from, in priority order:
+    /// its own opcodes, its group's, its master's, and the instrument's global ones.
+    pub(crate) fn inheritance<'a>(
+        &'a self,
+        instrument: &'a Instrument,
+    ) -> impl Iterator<Item = &'a OpcodeMap> {
+        let group = self.group.and_then(|g| instrument.groups.get(g));
+        let master = self.master.and_then(|m| instrument.masters.get(m));
+        std::iter::once(&self.opcodes)
+            .chain(group.map(|g| &g.opcodes))
+            .chain(master.map(|m| &m.opcodes))
+            .chain(std::iter::once(&instrument.global))
+    }
+
+    /// Get an opcode by name, from this region, or else inherited from
+    /// its group, its master, or else from the global header of the instrument.
+    pub fn inherited_opcode<'a>(
+        &'a self,
+        name: &str,
+        instrument: &'a Instrument,
+    ) -> Option<&'a Opcode> {
+        self.inheritance(instrument)
+            .find_map(|opcodes| opcodes.get(name))
+    }
+
+    /// Finds the first opcode accepted by `f`, from this region, or else
+    /// inherited from its group, its master, or else from the global header.
+    pub(crate) fn inherited_value<T>(
+        &self,
+        instrument: &Instrument,
+        f: impl Fn(&Opcode) -> Option<T>,
+    ) -> Option<T> {
+        self.inheritance(instrument)
+            .find_map(|opcodes| opcodes.values().find_map(&f))
+    }
+
+    /// Returns true if this region must play for the provided MIDI context,
+    /// taking inheritance into account.
+    ///
+    /// All the input controls must match: key, velocity, channel, CC values,
+    /// pitch bend, aftertouch and the last keyswitch.
+    pub fn matches(&self, ctx: &MidiContext, instrument: &Instrument) -> bool {
+        if !self.matches_note(ctx.key, ctx.velocity, ctx.channel, instrument)
+            || !within!(self, instrument, ctx.bend, lobend, hibend, -8192, 8192)
+            || !within!(
+                self,
+                instrument,
+                ctx.channel_aftertouch,
+                lochanaft,
+                hichanaft,
+                0,
+                127
+            )
+            || !within!(
+                self,
+                instrument,
+                ctx.poly_aftertouch,
+                lopolyaft,
+                hipolyaft,
+                0,
+                127
+            )
+        {
+            return false;
+        }
+
+        for (cc, &value) in ctx.cc.iter().enumerate() {
+            let cc = cc as u8;
+            let lo = self
+                .inherited_value(instrument, |o| match o {
+                    Opcode::loccN(n, v) if *n == cc => Some(*v),
+                    _ => None,
+                })
+                .unwrap_or(0);
+            let hi = self
+                .inherited_value(instrument, |o| match o {
+                    Opcode::hiccN(n, v) if *n == cc => Some(*v),
+                    _ => None,
+                })
+                .unwrap_or(127);
+            if !(lo..=hi).contains(&value) {
+                return false;
+            }
+        }
+
+        if let Some(last) = self.inherited_value(instrument, |o| match o {
+            Opcode::sw_last(v) => Some(*v),
+            _ => None,
+        }) {
+            let keyswitch = ctx.keyswitch.or_else(|| {
+                self.inherited_value(instrument, |o| match o {
+                    Opcode::sw_default(v) => Some(*v),
+                    _ => None,
+                })
+            });
+            if keyswitch != Some(last) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Returns true if a note with the provided key, velocity and channel
+    /// falls within the ranges of this region, taking inheritance into account.
+    ///
+    /// Unlike [`matches`][Self::matches], the rest of the MIDI input is ignored.
+    pub fn matches_note(
+        &self,
+        key: u8,
+        velocity: u8,
+        channel: u8,
+        instrument: &Instrument,
+    ) -> bool {
+        self.key_range(instrument).contains(&key)
+            && within!(self, instrument, velocity, lovel, hivel, 0, 127)
+            && within!(self, instrument, channel, lochan, hichan, 1, 16)
+    }
+
+    /// Get the filter cutoff in Hz for a note played with the provided
+    /// key and velocity, taking inheritance into account.
+    ///
+    /// The base `cutoff` is shifted by `fil_keytrack` cents per key away from
+    /// `fil_keycenter`, and by `fil_veltrack` cents scaled by the velocity.
+    ///
+    /// Returns `None` if the filter is disabled, because no cutoff is defined.
+    pub fn effective_cutoff(&self, key: u8, vel: u8, instrument: &Instrument) -> Option<f32> {
+        let cutoff = self.inherited_value(instrument, |o| match o {
+            Opcode::cutoff(v) => Some(*v),
+            _ => None,
+        })?;
+        let keytrack = self
+            .inherited_value(instrument, |o| match o {
+                Opcode::fil_keytrack(v) => Some(*v),
+                _ => None,
+            })
+            .unwrap_or(0);
+        let keycenter = self
+            .inherited_value(instrument, |o| match o {
+                Opcode::fil_keycenter(v) => Some(*v),
+                _ => None,
+            })
+            .unwrap_or(60);
+        let veltrack = self
+            .inherited_value(instrument, |o| match o {
+                Opcode::fil_veltrack(v) => Some(*v),
+                _ => None,
+            })
+            .unwrap_or(0);
+
+        let cents = f32::from(keytrack) * (f32::from(key) - f32::from(keycenter))
+            + f32::from(veltrack) * f32::from(vel) / 127.;
+        Some(cutoff * 2f32.powf(cents / 1200.))
+    }
+
+    /// Get the amplitude gain, from 0 to 1, for a note played with
+    /// the provided velocity, taking inheritance into account.
+    ///
+    /// When some `amp_velcurve_N` points are defined, the gain is linearly
+    /// interpolated between the two closest points, the undefined ends of the
+    /// curve being 0 at velocity 0, and 1 at velocity 127. Each point can be
+    /// inherited separately.
+    ///
+    /// Otherwise the standard curve is used, which is quadratic on the
+    /// velocity, scaled by `amp_veltrack` (100% by default). A negative
+    /// `amp_veltrack` inverts the curve, so that lower velocities play louder.
+    pub fn velocity_gain(&self, vel: u8, instrument: &Instrument) -> f32 {
+        let mut points = BTreeMap::new();
+        for opcodes in self.inheritance(instrument) {
+            for opcode in opcodes.values() {
+                if let Opcode::amp_velcurve_N(n, v) = opcode {
+                    points.entry(*n).or_insert(*v);
+                }
+            }
+        }
+
+        if points.is_empty() {
+            let veltrack = self
+                .inherited_value(instrument, |o| match o {
+                    Opcode::amp_veltrack(v) => Some(*v),
+                    _ => None,
+                })
+                .unwrap_or(100.)
+                / 100.;
+            let vel = if veltrack < 0. { 127 - vel } else { vel };
+            let curve = (f32::from(vel) / 127.).powi(2);
+            return 1. - veltrack.abs() * (1. - curve);
+        }
+
+        points.entry(0).or_insert(0.);
+        points.entry(127).or_insert(1.);
+        let (&lo_vel, &lo_gain) = points.range(..=vel).next_back().unwrap_or((&0, &0.));
+        match points.range(vel..).next() {
+            Some((&hi_vel, &hi_gain)) if hi_vel > lo_vel => {
+                let t = f32::from(vel - lo_vel) / f32::from(hi_vel - lo_vel);
+                lo_gain + t * (hi_gain - lo_gain)
+            }
+            _ => lo_gain,
+        }
+    }
+
+    /// Infers the pitch keycenter from the note name in the sample's filename,
+    /// like `A#1` in `Horn_A#1_v1.wav`
+    ///
+    /// The note name must be delimited by spaces, underscores, dots or the
+    /// ends of the filename. Returns `None` if the region has no sample, or
+    /// if no note name is found.
+    pub fn infer_keycenter_from_filename(&self) -> Option<u8> {
+        let name = match self.opcodes.get("sample") {
+            Some(Opcode::sample(path)) => path.file_stem()?.to_str()?.to_string(),
+            _ => return None,
+        };
+        name.split(['_', ' ', '.'])
+            .find_map(utils::note_name_to_number)
+    }
+
+    /// Get the audio output bus of this region, taking inheritance into account
+    pub fn output(&self, instrument: &Instrument) -> u16 {
+        self.inherited_value(instrument, |o| match o {
+            Opcode::output(v) => Some(*v),
+            _ => None,
+        })
+        .unwrap_or(0)
+    }
+
+    /// Get the polyphony group of this region, taking inheritance into account
+    ///
+    /// It's defined by either the `polyphony_group` opcode or its older
+    /// `group` alias.
+    pub fn polyphony_group(&self, instrument: &Instrument) -> u32 {
+        self.inherited_value(instrument, |o| match o {
+            Opcode::polyphony_group(v) | Opcode::group(v) => Some(*v),
+            _ => None,
+        })
+        .unwrap_or(0)
+    }
+
+    /// Get the range of keys that play this region, taking inheritance
+    /// into account, and falling back to the default range of 0 to 127.
+    ///
+    /// The `key` opcode sets both ends of the range, unless `lokey` or `hikey`
+    /// are also defined in the same header.
+    pub fn key_range(&self, instrument: &Instrument) -> RangeInclusive<u8> {
+        let (mut lokey, mut hikey) = (None, None);
+        for opcodes in self.inheritance(instrument) {
+            let key = match opcodes.get("key") {
+                Some(Opcode::key(k)) => Some(*k),
+                _ => None,
+            };
+            if lokey.is_none() {
+                lokey = match opcodes.get("lokey") {
+                    Some(Opcode::lokey(k)) => Some(*k),
+                    _ => key,
+                };
+            }
+            if hikey.is_none() {
+                hikey = match opcodes.get("hikey") {
+                    Some(Opcode::hikey(k)) => Some(*k),
+                    _ => key,
+                };
+            }
+        }
+        lokey.unwrap_or(0)..=hikey.unwrap_or(127)
+    }
+
+    /// Get the loop settings of this region, taking inheritance into account
+    pub fn loop_settings(&self, instrument: &Instrument) -> LoopSettings {
+        LoopSettings {
+            mode: self.inherited_value(instrument, |o| match o {
+                Opcode::loop_mode(v) => Some(*v),
+                _ => None,
+            }),
+            start: self
+                .inherited_value(instrument, |o| match o {
+                    Opcode::loop_start(v) => Some(*v),
+                    _ => None,
+                })
+                .unwrap_or(0),
+            end: self.inherited_value(instrument, |o| match o {
+                Opcode::loop_end(v) => Some(*v),
+                _ => None,
+            }),
+            crossfade: self
+                .inherited_value(instrument, |o| match o {
+                    Opcode::loop_crossfade(v) => Some(*v),
+                    _ => None,
+                })
+                .unwrap_or(0.),
+        }
+    }
+
+    /// Get the configuration of the LFOs used by this region, taking inheritance into account
+    ///
+    /// There is one for each LFO index that appears in any `lfoN_*` opcode,
+    /// sorted by index.
+    pub fn lfos(&self, instrument: &Instrument) -> Vec<LfoConfig> {
+        let mut indices: Vec<u8> = self
+            .inheritance(instrument)
+            .flat_map(|opcodes| opcodes.values())
+            .filter(|o| o.name().starts_with("lfoN_"))
+            .filter_map(|o| o.params().first().copied())
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+
+        indices
+            .into_iter()
+            .map(|n| {
+                let value =
+                    |f: &dyn Fn(&Opcode) -> Option<f32>| self.inherited_value(instrument, f);
+                LfoConfig {
+                    index: n,
+                    freq: value(&|o| match o {
+                        Opcode::lfoN_freq(i, v) if *i == n => Some(*v),
+                        _ => None,
+                    })
+                    .unwrap_or(0.),
+                    delay: value(&|o| match o {
+                        Opcode::lfoN_delay(i, v) if *i == n => Some(*v),
+                        _ => None,
+                    })
+                    .unwrap_or(0.),
+                    fade: value(&|o| match o {
+                        Opcode::lfoN_fade(i, v) if *i == n => Some(*v),
+                        _ => None,
+                    })
+                    .unwrap_or(0.),
+                    phase: value(&|o| match o {
+                        Opcode::lfoN_phase(i, v) if *i == n => Some(*v),
+                        _ => None,
+                    })
+                    .unwrap_or(0.),
+                    wave: self
+                        .inherited_value(instrument, |o| match o {
+                            Opcode::lfoN_wave(i, v) if *i == n => Some(*v),
+                            _ => None,
+                        })
+                        .unwrap_or(1),
+                }
+            })
+            .collect()
+    }
+
+    /// Get the range of keys that play this region as note names, like `C4–E4`
+    ///
+    /// A region played by a single key returns just its name, like `C4`.
+    pub fn key_range_string(&self, instrument: &Instrument) -> String {
+        let range = self.key_range(instrument);
+        if range.start() == range.end() {
+            utils::note_number_to_name(*range.start())
+        } else {
+            format!(
+                "{}–{}",
+                utils::note_number_to_name(*range.start()),
+                utils::note_number_to_name(*range.end())
+            )
+        }
+    }
+}
+
+/// The loop settings of a region, as returned by [`Region::loop_settings`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LoopSettings {
+    /// The loop mode, if defined.
+    ///
+    /// Otherwise it depends on the sample, looping it only if it has a loop defined.
+    pub mode: Option<loop_mode>,
+
+    /// The first frame of the loop.
+    pub start: u32,
+
+    /// The last frame of the loop, if defined.
+    ///
+    /// Otherwise it's the loop defined in the sample, or its last frame.
+    pub end: Option<u32>,
+
+    /// The duration of the crossfade at the loop point, in seconds.
+    pub crossfade: f32,
+}
+
+/// The configuration of an LFO of a region, as returned by [`Region::lfos`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LfoConfig {
+    /// The index N of the LFO in the `lfoN_*` opcodes.
+    pub index: u8,
+
+    /// The frequency, in Hz.
+    pub freq: f32,
+
+    /// The time before the LFO starts, in seconds.
+    pub delay: f32,
+
+    /// The time the LFO takes to reach its full depth, in seconds.
+    pub fade: f32,
+
+    /// The starting phase, from 0 to 1.
+    pub phase: f32,
+
+    /// The index of the waveform, 1 being a sine wave, the default.
+    pub wave: i32,
+}
+
+#[cfg(test)]
+mod tests_region {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_region_lfos() {
+        let i = Instrument::from_sfz(
+            "<global> lfo2_freq=5
+            <group> lfo1_freq=0.5 lfo1_wave=3
+            <region> sample=a.wav lfo1_phase=0.25 lfo1_freq=2 lfo1_pitch=100",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(
+            i.regions[0].lfos(&i),
+            [
+                LfoConfig {
+                    index: 1,
+                    freq: 2.,
+                    delay: 0.,
+                    fade: 0.,
+                    phase: 0.25,
+                    wave: 3,
+                },
+                LfoConfig {
+                    index: 2,
+                    freq: 5.,
+                    delay: 0.,
+                    fade: 0.,
+                    phase: 0.,
+                    wave: 1,
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_region_opcode_order() {
+        let mut r = Region::new();
+        r.add_opcode(&Opcode::sample("a.wav".into()));
+        r.add_opcode(&Opcode::volume(-3.));
+        r.add_opcode(&Opcode::hiccN(64, 100));
+        r.add_opcode(&Opcode::lokey(40));
+        r.add_opcode(&Opcode::hiccN(1, 10));
+        r.add_opcode(&Opcode::volume(2.));
+
+        let names: Vec<&String> = r.opcodes.keys().collect();
+        assert_eq!(names, ["sample", "volume", "hicc64", "lokey", "hicc1"]);
+        assert_eq!(r.opcodes["volume"], Opcode::volume(2.));
+    }
+
+    #[test]
+    fn test_region_key_range() {
+        let mut i = Instrument::new();
+        i.add_opcode(&Opcode::hikey(100)).unwrap();
+        i.new_group();
+        i.add_opcode(&Opcode::lokey(20)).unwrap();
+
+        // inherits lokey from the group and hikey from global
+        i.new_region();
+        assert_eq!(i.regions[0].key_range(&i), 20..=100);
+
+        // the region's key overrides both
+        i.add_opcode(&Opcode::key(41)).unwrap();
+        assert_eq!(i.regions[0].key_range(&i), 41..=41);
+
+        // the defaults apply when nothing is defined
+        assert_eq!(Region::new().key_range(&Instrument::new()), 0..=127);
+    }
+
+    #[test]
+    fn test_region_key_range_string() {
+        let mut i = Instrument::new();
+        i.new_region();
+        i.add_opcode(&Opcode::lokey(60)).unwrap();
+        i.add_opcode(&Opcode::hikey(64)).unwrap();
+        assert_eq!(i.regions[0].key_range_string(&i), "C4–E4");
+
+        i.add_opcode(&Opcode::key(61)).unwrap();
+        i.regions[0].opcodes.shift_remove("lokey");
+        i.regions[0].opcodes.shift_remove("hikey");
+        assert_eq!(i.regions[0].key_range_string(&i), "C#4");
+    }
+
+    #[test]
+    fn test_region_matches() {
+        let i = Instrument::from_sfz(
+            "<group> locc64=64 hibend=0
+            <region> sample=a.wav lokey=60 hikey=72 hicc1=63
+            <region> sample=b.wav sw_last=24 sw_default=24",
+            Path::new(""),
+        )
+        .unwrap();
+        let (a, b) = (&i.regions[0], &i.regions[1]);
+
+        let mut ctx = MidiContext::new(64, 100);
+        ctx.cc[64] = 127;
+        assert!(a.matches(&ctx, &i));
+
+        // CC gating, inherited from the group and from the region
+        ctx.cc[64] = 0;
+        assert!(!a.matches(&ctx, &i));
+        ctx.cc[64] = 127;
+        ctx.cc[1] = 64;
+        assert!(!a.matches(&ctx, &i));
+        ctx.cc[1] = 0;
+
+        // bend gating
+        ctx.bend = 100;
+        assert!(!a.matches(&ctx, &i));
+        ctx.bend = -100;
+        assert!(a.matches(&ctx, &i));
+
+        // key gating
+        ctx.key = 80;
+        assert!(!a.matches(&ctx, &i));
+
+        // keyswitch gating, falling back to sw_default
+        assert!(b.matches(&ctx, &i));
+        ctx.keyswitch = Some(25);
+        assert!(!b.matches(&ctx, &i));
+    }
+
+    #[test]
+    fn test_region_effective_cutoff() {
+        let i = Instrument::from_sfz(
+            "<group> cutoff=1000 fil_keycenter=60
+            <region> sample=a.wav fil_keytrack=100
+            <region> sample=b.wav fil_veltrack=1200",
+            Path::new(""),
+        )
+        .unwrap();
+        let (a, b) = (&i.regions[0], &i.regions[1]);
+
+        assert_eq!(a.effective_cutoff(60, 100, &i), Some(1000.));
+        assert_eq!(a.effective_cutoff(72, 100, &i), Some(2000.));
+        assert_eq!(a.effective_cutoff(48, 100, &i), Some(500.));
+
+        assert_eq!(b.effective_cutoff(72, 0, &i), Some(1000.));
+        assert_eq!(b.effective_cutoff(72, 127, &i), Some(2000.));
+
+        // the filter is disabled without a cutoff
+        assert_eq!(Region::new().effective_cutoff(60, 100, &i), None);
+    }
+
+    #[test]
+    fn test_region_infer_keycenter_from_filename() {
+        let mut region = Region::new();
+        assert_eq!(region.infer_keycenter_from_filename(), None);
+
+        region.add_opcode(&Opcode::sample(
+            "with space/MOHorn_mute_A#1_v1_1.wav".into(),
+        ));
+        assert_eq!(region.infer_keycenter_from_filename(), Some(34));
+
+        region.add_opcode(&Opcode::sample("MOHorn mute_v1_1.wav".into()));
+        assert_eq!(region.infer_keycenter_from_filename(), None);
+    }
+
+    #[test]
+    fn test_region_output_and_polyphony_group() {
+        let i = Instrument::from_sfz(
+            "<group> output=1 group=3
+            <region> sample=a.wav output=2 polyphony_group=5
+            <region> sample=b.wav",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(i.regions[0].output(&i), 2);
+        assert_eq!(i.regions[0].polyphony_group(&i), 5);
+        assert_eq!(i.regions[1].output(&i), 1);
+        assert_eq!(i.regions[1].polyphony_group(&i), 3);
+        assert_eq!(Region::new().output(&i), 0);
+        assert_eq!(Region::new().polyphony_group(&i), 0);
+    }
+
+    #[test]
+    fn test_region_velocity_gain() {
+        let i = Instrument::from_sfz(
+            "<group> amp_velcurve_0=0.2
+            <region> sample=a.wav amp_velcurve_127=0.6
+            <region> sample=b.wav amp_velcurve_64=1",
+            Path::new(""),
+        )
+        .unwrap();
+        let gain = |r: usize, vel| i.regions[r].velocity_gain(vel, &i);
+
+        // sparse points, with v0 inherited from the group
+        assert_eq!(gain(0, 0), 0.2);
+        assert_eq!(gain(0, 127), 0.6);
+        assert!((gain(0, 64) - (0.2 + 0.4 * 64. / 127.)).abs() < 1e-6);
+
+        // the undefined end of the curve goes up to 1
+        assert_eq!(gain(1, 0), 0.2);
+        assert_eq!(gain(1, 64), 1.);
+        assert_eq!(gain(1, 100), 1.);
+
+        // the standard curve
+        let i = Instrument::from_sfz(
+            "<region> sample=a.wav
+            <region> sample=b.wav amp_veltrack=0
+            <region> sample=c.wav amp_veltrack=-100",
+            Path::new(""),
+        )
+        .unwrap();
+        let gain = |r: usize, vel| i.regions[r].velocity_gain(vel, &i);
+        assert_eq!(gain(0, 127), 1.);
+        assert_eq!(gain(0, 0), 0.);
+        assert!((gain(0, 64) - (64f32 / 127.).powi(2)).abs() < 1e-6);
+        assert_eq!(gain(1, 0), 1.);
+        assert_eq!(gain(2, 0), 1.);
+        assert_eq!(gain(2, 127), 0.);
+    }
+
+    #[test]
+    fn test_region_typed_getters() {
+        let i = Instrument::from_sfz("<region> sample=a.wav cutoff=500 lokey=36", Path::new(""))
+            .unwrap();
+        let region = &i.regions[0];
+        assert_eq!(region.opcode("lokey"), Some(&Opcode::lokey(36)));
+        assert_eq!(region.f32("cutoff"), Some(500.));
+        assert_eq!(region.u8("lokey"), Some(36));
+        assert_eq!(region.f32("lokey"), None);
+        assert_eq!(region.f32("resonance"), None);
+        assert_eq!(region.opcode("resonance"), None);
+    }
+
+    #[test]
+    fn test_region_loop_settings() {
+        let i = Instrument::from_sfz(
+            "<group> loop_mode=loop_sustain loop_crossfade=0.05
+            <region> sample=a.wav loop_start=100 loop_end=4000
+            <region> sample=b.wav loop_mode=no_loop",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(
+            i.regions[0].loop_settings(&i),
+            LoopSettings {
+                mode: Some(loop_mode::loop_sustain),
+                start: 100,
+                end: Some(4000),
+                crossfade: 0.05,
+            }
+        );
+        assert_eq!(
+            i.regions[1].loop_settings(&i),
+            LoopSettings {
+                mode: Some(loop_mode::no_loop),
+                start: 0,
+                end: None,
+                crossfade: 0.05,
+            }
+        );
+    }
 }
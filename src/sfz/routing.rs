@@ -0,0 +1,119 @@
+//! Summarizes the signal flow of an instrument, from its regions to the output.
+
+use indexmap::IndexMap;
+
+use crate::sfz::{Instrument, Opcode};
+
+/// How the audio of an instrument flows from its regions,
+/// through the effect buses, to the main output.
+///
+/// Returned by [`Instrument::routing_matrix`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoutingMatrix {
+    /// The send levels of each region to the fx1 to fx4 buses, in %.
+    pub region_sends: Vec<[f32; 4]>,
+
+    /// The effects processing each bus, as indices of
+    /// [`Instrument::effects`], in processing order.
+    pub bus_effects: IndexMap<String, Vec<usize>>,
+
+    /// The level of the main bus into the output, in %.
+    pub direct_to_main: f32,
+
+    /// The levels of the fx1 to fx4 buses into the output, in %.
+    pub fx_to_main: [f32; 4],
+}
+
+impl Instrument {
+    /// Returns the routing of the regions and buses of the instrument.
+    ///
+    /// The region sends come from `effect1` to `effect4`, taking inheritance
+    /// into account, and each effect processes the bus set by its `bus` opcode
+    /// (`main` by default). The levels into the output come from `directtomain`
+    /// and `fxNtomain`, under any effect header.
+    pub fn routing_matrix(&self) -> RoutingMatrix {
+        let region_sends = self
+            .regions
+            .iter()
+            .map(|region| {
+                let mut sends = [0.; 4];
+                for (n, send) in sends.iter_mut().enumerate() {
+                    if let Some(level) = region.inherited_value(self, |o| match (n, o) {
+                        (0, Opcode::effect1(l))
+                        | (1, Opcode::effect2(l))
+                        | (2, Opcode::effect3(l))
+                        | (3, Opcode::effect4(l)) => Some(*l),
+                        _ => None,
+                    }) {
+                        *send = level;
+                    }
+                }
+                sends
+            })
+            .collect();
+
+        let mut bus_effects: IndexMap<String, Vec<usize>> = IndexMap::new();
+        for e in self.effect_indices_in_order() {
+            let bus = self.effects[e].bus.as_deref().unwrap_or("main").to_string();
+            bus_effects.entry(bus).or_default().push(e);
+        }
+
+        let mut direct_to_main = 100.;
+        let mut fx_to_main = [0.; 4];
+        for opcode in self.effects.iter().flat_map(|e| e.opcodes.values()) {
+            match opcode {
+                Opcode::directtomain(level) => direct_to_main = *level,
+                Opcode::fxNtomain(n @ 1..=4, level) => fx_to_main[*n as usize - 1] = *level,
+                _ => (),
+            }
+        }
+
+        RoutingMatrix {
+            region_sends,
+            bus_effects,
+            direct_to_main,
+            fx_to_main,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_routing {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_routing_matrix() {
+        let i = Instrument::from_sfz(
+            "<effect> bus=fx1 type=fverb fx1tomain=80
+            <group> effect1=50
+            <region> sample=a.wav
+            <region> sample=b.wav effect1=0 effect2=10",
+            Path::new(""),
+        )
+        .unwrap();
+        let routing = i.routing_matrix();
+        assert_eq!(
+            routing.region_sends,
+            vec![[50., 0., 0., 0.], [0., 10., 0., 0.]]
+        );
+        assert_eq!(routing.bus_effects["fx1"], vec![0]);
+        assert_eq!(routing.bus_effects.get("main"), None);
+        assert_eq!(routing.direct_to_main, 100.);
+        assert_eq!(routing.fx_to_main, [80., 0., 0., 0.]);
+    }
+
+    #[test]
+    fn test_routing_matrix_order() {
+        let mut i = Instrument::from_sfz(
+            "<effect> type=gate
+            <effect> type=fverb
+            <effect> type=comp dsp_order=1",
+            Path::new(""),
+        )
+        .unwrap();
+        // out of the parsed range, but it must still go before the unordered ones
+        i.effects[1].dsp_order = Some(255);
+        assert_eq!(i.routing_matrix().bus_effects["main"], vec![2, 1, 0]);
+    }
+}
@@ -4,17 +4,29 @@
 //!
 //! - loop_mode
 //! - trigger
+//! - xf_curve
 //! - fil_type
+//! - off_mode
+//! - sw_vel
+//! - direction
+//! - loop_type
+//! - phase
+//! - on_off
+//! - effect_filter
+//! - effect_type
+//! - waveform
 //!
 //! Debug types:
 //!
+//! - RawValue
 //! - UndefinedInteger
 //! - UndefinedUnsignedInteger
 //! - UnknownType
 
-use std::collections::HashMap;
 use std::path::PathBuf;
 
+use indexmap::IndexMap;
+
 use crate::sfz::Opcode;
 
 /// Theoretical maximum sample rate possible, used for type range checks
@@ -33,23 +45,38 @@ pub enum OpcodeType {
     fil_type(Option<fil_type>),
     loop_mode(Option<loop_mode>),
     trigger(Option<trigger>),
+    xf_curve(Option<xf_curve>),
+    off_mode(Option<off_mode>),
+    sw_vel(Option<sw_vel>),
+    direction(Option<direction>),
+    loop_type(Option<loop_type>),
+    phase(Option<phase>),
+    on_off(Option<on_off>),
     PathBuf(Option<PathBuf>),
     String(Option<&'static str>),
 }
 
-/// A Hashmap of opcodes, in which the key is the opcode's name
-pub type OpcodeMap = HashMap<String, Opcode>;
+/// A map of opcodes, in which the key is the opcode's full name
+///
+/// The full name includes the numeric parameters, like `locc1`, so that opcodes
+/// with different parameters don't collide. It preserves the order in which
+/// the opcodes were inserted.
+pub type OpcodeMap = IndexMap<String, Opcode>;
 
 /// Allows playing samples with loops defined in the unlooped mode.
 ///
 /// - info: [loop_mode](https://sfzformat.com/opcodes/loop_mode)
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(non_camel_case_types)]
 pub enum loop_mode {
     /// no looping will be performed. Sample will play straight from start to end,
     /// or until note off, whatever reaches first.
     ///
     /// This is the default.
+    // IMPROVE: `no_loop` for samples without a loop defined,
+    // `loop_continuous` for samples with defined loop(s).
+    #[default]
     no_loop,
 
     /// sample will play from start to end, ignoring note off. This is commonly
@@ -67,14 +94,6 @@ pub enum loop_mode {
     loop_sustain,
 }
 
-// IMPROVE: `no_loop` for samples without a loop defined,
-// `loop_continuous` for samples with defined loop(s).
-impl Default for loop_mode {
-    fn default() -> loop_mode {
-        Self::no_loop
-    }
-}
-
 impl loop_mode {
     /// Constructor from the variant name, as a string
     pub fn from_name(name: &str) -> Option<Self> {
@@ -86,15 +105,27 @@ impl loop_mode {
             _ => None,
         }
     }
+
+    /// Returns the name of the variant, as it's written in SFZ code
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::no_loop => "no_loop",
+            Self::one_shot => "one_shot",
+            Self::loop_continuous => "loop_continuous",
+            Self::loop_sustain => "loop_sustain",
+        }
+    }
 }
 
 /// Sets the trigger which will be used for the sample to play.
 ///
 /// - info: [trigger](https://sfzformat.com/opcodes/trigger)
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(non_camel_case_types)]
 pub enum trigger {
     /// (Default): Region will play on note-on.
+    #[default]
     attack,
 
     /// Region will play on note-off or sustain pedal off. The velocity used to
@@ -114,12 +145,6 @@ pub enum trigger {
     release_key, // aria
 }
 
-impl Default for trigger {
-    fn default() -> trigger {
-        Self::attack
-    }
-}
-
 impl trigger {
     /// Constructor from the variant name, as a string
     pub fn from_name(name: &str) -> Option<Self> {
@@ -128,16 +153,275 @@ impl trigger {
             "release" => Some(Self::release),
             "first" => Some(Self::first),
             "legato" => Some(Self::legato),
-            "release_key" => Some(Self::legato),
+            "release_key" => Some(Self::release_key),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the variant, as it's written in SFZ code
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::attack => "attack",
+            Self::release => "release",
+            Self::first => "first",
+            Self::legato => "legato",
+            Self::release_key => "release_key",
+        }
+    }
+}
+
+/// Sets the curve used when crossfading between regions.
+///
+/// - info: [xf_velcurve](https://sfzformat.com/opcodes/xf_velcurve)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(non_camel_case_types)]
+pub enum xf_curve {
+    /// Linear gain crossfade.
+    gain,
+
+    /// (Default): Constant power crossfade.
+    #[default]
+    power,
+}
+
+impl xf_curve {
+    /// Constructor from the variant name, as a string
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "gain" => Some(Self::gain),
+            "power" => Some(Self::power),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the variant, as it's written in SFZ code
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::gain => "gain",
+            Self::power => "power",
+        }
+    }
+}
+
+/// Sets how a region is turned off by another one of the `off_by` group.
+///
+/// - info: [off_mode](https://sfzformat.com/opcodes/off_mode)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(non_camel_case_types)]
+pub enum off_mode {
+    /// (Default): The voice is killed almost immediately.
+    #[default]
+    fast,
+
+    /// The voice goes into its release phase.
+    normal,
+
+    /// The voice fades out in the time set by `off_time`.
+    time,
+}
+
+impl off_mode {
+    /// Constructor from the variant name, as a string
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "fast" => Some(Self::fast),
+            "normal" => Some(Self::normal),
+            "time" => Some(Self::time),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the variant, as it's written in SFZ code
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::fast => "fast",
+            Self::normal => "normal",
+            Self::time => "time",
+        }
+    }
+}
+
+/// Sets which velocity is used when a keyswitch triggers a region.
+///
+/// - info: [sw_vel](https://sfzformat.com/opcodes/sw_vel)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(non_camel_case_types)]
+pub enum sw_vel {
+    /// (Default): The velocity of the current note.
+    #[default]
+    current,
+
+    /// The velocity of the previous note.
+    previous,
+}
+
+impl sw_vel {
+    /// Constructor from the variant name, as a string
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "current" => Some(Self::current),
+            "previous" => Some(Self::previous),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the variant, as it's written in SFZ code
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::current => "current",
+            Self::previous => "previous",
+        }
+    }
+}
+
+/// Sets the direction in which the sample is played.
+///
+/// - info: [direction](https://sfzformat.com/opcodes/direction)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(non_camel_case_types)]
+pub enum direction {
+    /// (Default): The sample is played forward.
+    #[default]
+    forward,
+
+    /// The sample is played in reverse, from its end to its start.
+    reverse,
+}
+
+impl direction {
+    /// Constructor from the variant name, as a string
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "forward" => Some(Self::forward),
+            "reverse" => Some(Self::reverse),
             _ => None,
         }
     }
+
+    /// Returns the name of the variant, as it's written in SFZ code
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::forward => "forward",
+            Self::reverse => "reverse",
+        }
+    }
+}
+
+/// Sets the way the loop of the sample is played.
+///
+/// - info: [loop_type](https://sfzformat.com/opcodes/loop_type)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(non_camel_case_types)]
+pub enum loop_type {
+    /// (Default): The loop is played forward.
+    #[default]
+    forward,
+
+    /// The loop is played in reverse.
+    backward,
+
+    /// The loop is played forward and then in reverse, alternately.
+    alternate,
+}
+
+impl loop_type {
+    /// Constructor from the variant name, as a string
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "forward" => Some(Self::forward),
+            "backward" => Some(Self::backward),
+            "alternate" => Some(Self::alternate),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the variant, as it's written in SFZ code
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::forward => "forward",
+            Self::backward => "backward",
+            Self::alternate => "alternate",
+        }
+    }
+}
+
+/// Sets the phase of the sample.
+///
+/// - info: [phase](https://sfzformat.com/opcodes/phase)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(non_camel_case_types)]
+pub enum phase {
+    /// (Default): The phase is kept.
+    #[default]
+    normal,
+
+    /// The phase is inverted.
+    invert,
+}
+
+impl phase {
+    /// Constructor from the variant name, as a string
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "normal" => Some(Self::normal),
+            "invert" => Some(Self::invert),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the variant, as it's written in SFZ code
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::normal => "normal",
+            Self::invert => "invert",
+        }
+    }
+}
+
+/// Turns a feature on or off, for the opcodes that act as switches.
+///
+/// - info: [note_selfmask](https://sfzformat.com/opcodes/note_selfmask)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(non_camel_case_types)]
+pub enum on_off {
+    /// The feature is enabled.
+    on,
+
+    /// The feature is disabled.
+    off,
+}
+
+impl on_off {
+    /// Constructor from the variant name, as a string
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "on" => Some(Self::on),
+            "off" => Some(Self::off),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the variant, as it's written in SFZ code
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::on => "on",
+            Self::off => "off",
+        }
+    }
 }
 
 /// Allows you to choose which type of filter you use if not specified
 ///
 /// - info: [fil_type](https://sfzformat.com/opcodes/fil_type)
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(non_camel_case_types)]
 pub enum fil_type {
     /// One-pole low pass filter (6dB/octave)
@@ -155,6 +439,7 @@ pub enum fil_type {
     /// This is the default.
     ///
     /// - version: v1
+    #[default]
     lpf_2p,
 
     /// Two-pole high pass filter (12dB/octave)
@@ -258,12 +543,6 @@ pub enum fil_type {
     peq,
 }
 
-impl Default for fil_type {
-    fn default() -> fil_type {
-        Self::lpf_2p
-    }
-}
-
 impl fil_type {
     /// Constructor from the variant name, as a string
     pub fn from_name(name: &str) -> Option<Self> {
@@ -271,7 +550,7 @@ impl fil_type {
             "lpf_1p" => Some(Self::lpf_1p),
             "hpf_1p" => Some(Self::hpf_1p),
             "lpf_2p" => Some(Self::lpf_2p),
-            "hpf_2p" => Some(Self::bpf_2p),
+            "hpf_2p" => Some(Self::hpf_2p),
             "bpf_2p" => Some(Self::bpf_2p),
             "brf_2p" => Some(Self::brf_2p),
             "bpf_1p" => Some(Self::bpf_1p),
@@ -294,6 +573,221 @@ impl fil_type {
             _ => None,
         }
     }
+
+    /// Returns the name of the variant, as it's written in SFZ code
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::lpf_1p => "lpf_1p",
+            Self::hpf_1p => "hpf_1p",
+            Self::lpf_2p => "lpf_2p",
+            Self::hpf_2p => "hpf_2p",
+            Self::bpf_2p => "bpf_2p",
+            Self::brf_2p => "brf_2p",
+            Self::bpf_1p => "bpf_1p",
+            Self::brf_1p => "brf_1p",
+            Self::apf_1p => "apf_1p",
+            Self::lpf_2p_sv => "lpf_2p_sv",
+            Self::hpf_2p_sv => "hpf_2p_sv",
+            Self::bpf_2p_sv => "bpf_2p_sv",
+            Self::brf_2p_sv => "brf_2p_sv",
+            Self::pkf_2p => "pkf_2p",
+            Self::lpf_4p => "lpf_4p",
+            Self::hpf_4p => "hpf_4p",
+            Self::lpf_6p => "lpf_6p",
+            Self::hpf_6p => "hpf_6p",
+            Self::comb => "comb",
+            Self::pink => "pink",
+            Self::lsh => "lsh",
+            Self::hsh => "hsh",
+            Self::peq => "peq",
+        }
+    }
+}
+
+/// Sets the filter of an effect, like the ones of the noise, static and delay effects.
+///
+/// Accepts the same filter types as [`fil_type`], plus `on` and `off`.
+///
+/// - info: [noise_filter](https://sfzformat.com/opcodes/noise_filter)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(non_camel_case_types)]
+pub enum effect_filter {
+    /// The filter is enabled, with its default type
+    on,
+    /// The filter is disabled
+    off,
+    /// The filter is enabled, with the given type
+    Filter(fil_type),
+}
+
+impl effect_filter {
+    /// Constructor from the variant name, as a string
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "on" => Some(Self::on),
+            "off" => Some(Self::off),
+            _ => fil_type::from_name(name).map(Self::Filter),
+        }
+    }
+}
+
+/// Sets the waveform of the LFO of an effect, like the ones of the auto-pan and phaser effects.
+///
+/// Accepts the waveform names, and the waveform numbers used by [`lfoN_wave`].
+///
+/// - info: [apan_waveform](https://sfzformat.com/opcodes/apan_waveform)
+///
+/// [`lfoN_wave`]: https://sfzformat.com/opcodes/lfoN_wave
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(non_camel_case_types)]
+pub enum waveform {
+    /// Triangle wave (0).
+    triangle,
+    /// Sine wave (1).
+    sine,
+    /// Pulse wave, with a 75% duty cycle (2).
+    pulse75,
+    /// Square wave (3).
+    square,
+    /// Pulse wave, with a 25% duty cycle (4).
+    pulse25,
+    /// Pulse wave, with a 12.5% duty cycle (5).
+    pulse12_5,
+    /// Ascending sawtooth wave (6).
+    saw_up,
+    /// Descending sawtooth wave (7).
+    saw_down,
+}
+
+impl waveform {
+    /// Constructor from the variant name or number, as a string
+    ///
+    /// A plain `saw` stands for `saw_up`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "triangle" | "0" => Some(Self::triangle),
+            "sine" | "1" => Some(Self::sine),
+            "pulse75" | "2" => Some(Self::pulse75),
+            "square" | "3" => Some(Self::square),
+            "pulse25" | "4" => Some(Self::pulse25),
+            "pulse12_5" | "5" => Some(Self::pulse12_5),
+            "saw_up" | "saw" | "6" => Some(Self::saw_up),
+            "saw_down" | "7" => Some(Self::saw_down),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of the waveform, as it's written in SFZ code
+    ///
+    /// The numbers are understood by every opcode taking a waveform,
+    /// unlike the names.
+    pub fn as_number(&self) -> u8 {
+        match self {
+            Self::triangle => 0,
+            Self::sine => 1,
+            Self::pulse75 => 2,
+            Self::square => 3,
+            Self::pulse25 => 4,
+            Self::pulse12_5 => 5,
+            Self::saw_up => 6,
+            Self::saw_down => 7,
+        }
+    }
+}
+
+/// Sets the type of effect of an <effect> header.
+///
+/// - info: [type](https://sfzformat.com/opcodes/type)
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(non_camel_case_types)]
+pub enum effect_type {
+    /// Auto-pan
+    apan,
+    /// Compressor
+    comp,
+    /// Delay
+    delay,
+    /// Distortion
+    disto,
+    /// Equalizer
+    eq,
+    /// Filter
+    filter,
+    /// Reverb (ARIA)
+    fverb,
+    /// Gate
+    gate,
+    /// Limiter
+    limiter,
+    /// Bit reduction and decimation
+    lofi,
+    /// Reverb (ARIA)
+    mverb,
+    /// Phaser
+    phaser,
+    /// Generic reverb
+    reverb,
+    /// Static noise
+    r#static,
+    /// Sympathetic string resonance
+    strings,
+    /// Impulse response convolution
+    tdfir,
+    /// Any other effect type, like the vendor specific `com.mda.*` ones
+    Other(String),
+}
+
+impl effect_type {
+    /// Constructor from the variant name, as a string
+    ///
+    /// Unknown names are kept in the `Other` variant.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "apan" => Self::apan,
+            "comp" => Self::comp,
+            "delay" => Self::delay,
+            "disto" => Self::disto,
+            "eq" => Self::eq,
+            "filter" => Self::filter,
+            "fverb" => Self::fverb,
+            "gate" => Self::gate,
+            "limiter" => Self::limiter,
+            "lofi" => Self::lofi,
+            "mverb" => Self::mverb,
+            "phaser" => Self::phaser,
+            "reverb" => Self::reverb,
+            "static" => Self::r#static,
+            "strings" => Self::strings,
+            "tdfir" => Self::tdfir,
+            _ => Self::Other(name.to_string()),
+        }
+    }
+
+    /// Returns the name of the effect type, as it's written in SFZ code
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::apan => "apan",
+            Self::comp => "comp",
+            Self::delay => "delay",
+            Self::disto => "disto",
+            Self::eq => "eq",
+            Self::filter => "filter",
+            Self::fverb => "fverb",
+            Self::gate => "gate",
+            Self::limiter => "limiter",
+            Self::lofi => "lofi",
+            Self::mverb => "mverb",
+            Self::phaser => "phaser",
+            Self::reverb => "reverb",
+            Self::r#static => "static",
+            Self::strings => "strings",
+            Self::tdfir => "tdfir",
+            Self::Other(name) => name,
+        }
+    }
 }
 
 /// This type is used for Opcodes with an unknown range and vague specification.
@@ -311,6 +805,14 @@ pub type UndefinedInteger = i32;
 ///
 pub type UndefinedUnsignedInteger = u32;
 
+/// This type is used for Opcodes with a complex or unclear specification,
+/// whose value is kept verbatim, as it's written in the SFZ code.
+///
+/// Opcodes using this type should be considered not stable, and its type
+/// should be changed to a more defined one, eventually.
+///
+pub type RawValue = String;
+
 /// This type is used for Opcodes with an unknown type.
 ///
 /// Opcodes using this type should be considered unimplemented, and its type
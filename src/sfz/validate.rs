@@ -0,0 +1,534 @@
+//! Checks an instrument for problems that the parser can't detect on its own.
+
+use std::{
+    fmt::Debug,
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+
+use crate::sfz::{Header, Instrument, Opcode};
+
+/// A problem found while validating an instrument.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    /// The header where the problem was found.
+    pub header: Header,
+
+    /// The index of the group or region, if the problem was found in one.
+    pub index: Option<usize>,
+
+    /// The kind of problem found.
+    pub kind: DiagnosticKind,
+}
+
+/// All the kinds of problems found by [`Instrument::validate`].
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum DiagnosticKind {
+    /// A filter cutoff frequency is above the Nyquist frequency (half the sample rate).
+    #[error("`{opcode}` of {cutoff} Hz is above the Nyquist frequency of {nyquist} Hz")]
+    CutoffAboveNyquist {
+        opcode: String,
+        cutoff: f32,
+        nyquist: f32,
+    },
+
+    /// An opcode is placed under a header where it's not allowed.
+    #[error("`{opcode}` is not allowed under the {header} header")]
+    MisplacedOpcode { opcode: String, header: Header },
+
+    /// A sample position is beyond the last frame of the sample.
+    #[error("`{opcode}` of {position} is beyond the {frames} frames of the sample")]
+    BeyondSampleEnd {
+        opcode: String,
+        position: u32,
+        frames: u64,
+    },
+
+    /// A region belongs to a group that doesn't exist.
+    #[error("the group {group} doesn't exist, there are only {groups} groups")]
+    MissingGroup { group: usize, groups: usize },
+
+    /// A region with opcodes has no sample.
+    #[error("the region has no sample")]
+    NoSample,
+
+    /// The sample of a region can't be read.
+    #[error("the sample `{}` can't be read", path.display())]
+    SampleNotFound { path: PathBuf },
+
+    /// A range can't contain any value, because its low end is above its high end.
+    #[error("`{low}` of {lo} is above `{high}` of {hi}")]
+    EmptyRange {
+        low: String,
+        lo: u8,
+        high: String,
+        hi: u8,
+    },
+}
+
+/// Reads the metadata of the samples used by an instrument.
+///
+/// Used by the checks that need to know about the samples, like
+/// [`Instrument::validate_loops`].
+pub trait SampleResolver {
+    /// Returns the number of frames of the sample at `path`,
+    /// or `None` if it can't be read.
+    fn frames(&self, path: &Path) -> Option<u64>;
+
+    /// Returns the sample rate in Hz of the sample at `path`,
+    /// or `None` if it can't be read.
+    fn sample_rate(&self, _path: &Path) -> Option<u32> {
+        None
+    }
+}
+
+impl Instrument {
+    /// Set the sample rate the instrument is going to be played at, in Hz
+    ///
+    /// Some opcode ranges depend on it, and they are checked by [`validate`][Self::validate].
+    pub fn set_sample_rate(&mut self, hz: f32) {
+        self.sample_rate = Some(hz);
+    }
+
+    /// Get the sample rate the instrument is going to be played at, if known
+    pub fn sample_rate(&self) -> Option<f32> {
+        self.sample_rate
+    }
+
+    /// Checks the instrument for problems, returning the list of them found
+    ///
+    /// The checks that depend on the sample rate are only made
+    /// after setting it with [`set_sample_rate`][Self::set_sample_rate].
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for (header, index, opcodes) in self.blocks() {
+            let mut push = |kind| {
+                diagnostics.push(Diagnostic {
+                    header,
+                    index,
+                    kind,
+                })
+            };
+
+            for opcode in opcodes.values() {
+                if !opcode.is_allowed_in(header) {
+                    push(DiagnosticKind::MisplacedOpcode {
+                        opcode: opcode.full_name(),
+                        header,
+                    });
+                }
+            }
+
+            if let Some(nyquist) = self.sample_rate.map(|hz| hz / 2.) {
+                for opcode in opcodes.values() {
+                    if let Opcode::cutoff(cutoff)
+                    | Opcode::cutoff2(cutoff)
+                    | Opcode::filter_cutoff(cutoff) = opcode
+                    {
+                        if *cutoff > nyquist {
+                            push(DiagnosticKind::CutoffAboveNyquist {
+                                opcode: opcode.str_name(),
+                                cutoff: *cutoff,
+                                nyquist,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        diagnostics.extend(self.validate_region_groups());
+        diagnostics
+    }
+
+    /// Checks that the regions only belong to groups that exist
+    ///
+    /// Also made while parsing with [`from_sfz_strict`][Self::from_sfz_strict].
+    pub(crate) fn validate_region_groups(&self) -> Vec<Diagnostic> {
+        self.regions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, region)| {
+                let group = region.group().filter(|&g| g >= self.groups())?;
+                Some(Diagnostic {
+                    header: Header::Region,
+                    index: Some(index),
+                    kind: DiagnosticKind::MissingGroup {
+                        group,
+                        groups: self.groups(),
+                    },
+                })
+            })
+            .collect()
+    }
+
+    /// Checks whether the instrument can be loaded and played,
+    /// returning the list of problems found otherwise
+    ///
+    /// Besides the checks of [`validate`][Self::validate], every region with opcodes
+    /// must have a sample that the `resolver` can read, unless it's a generator
+    /// like `*sine`, and its key and velocity ranges must not be empty.
+    pub fn is_playable(&self, resolver: &impl SampleResolver) -> Result<(), Vec<Diagnostic>> {
+        let mut diagnostics = self.validate();
+
+        for (index, region) in self.regions.iter().enumerate() {
+            if region.opcodes.is_empty() {
+                continue;
+            }
+            let mut push = |kind| {
+                diagnostics.push(Diagnostic {
+                    header: Header::Region,
+                    index: Some(index),
+                    kind,
+                })
+            };
+
+            match region.inherited_opcode("sample", self) {
+                None => push(DiagnosticKind::NoSample),
+                Some(Opcode::sample(sample)) if sample.to_string_lossy().starts_with('*') => (),
+                Some(_) => {
+                    if let Some(path) = self.sample_path(index) {
+                        if resolver.frames(&path).is_none() {
+                            push(DiagnosticKind::SampleNotFound { path });
+                        }
+                    }
+                }
+            }
+
+            let keys = region.key_range(self);
+            if keys.is_empty() {
+                push(DiagnosticKind::EmptyRange {
+                    low: "lokey".into(),
+                    lo: *keys.start(),
+                    high: "hikey".into(),
+                    hi: *keys.end(),
+                });
+            }
+            let lovel = region.inherited_value(self, |o| match o {
+                Opcode::lovel(v) => Some(*v),
+                _ => None,
+            });
+            let hivel = region.inherited_value(self, |o| match o {
+                Opcode::hivel(v) => Some(*v),
+                _ => None,
+            });
+            let (lo, hi) = (lovel.unwrap_or(0), hivel.unwrap_or(127));
+            if lo > hi {
+                push(DiagnosticKind::EmptyRange {
+                    low: "lovel".into(),
+                    lo,
+                    high: "hivel".into(),
+                    hi,
+                });
+            }
+        }
+
+        if diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(diagnostics)
+        }
+    }
+
+    /// Checks the sample positions of the regions against the length of their samples,
+    /// returning the list of problems found
+    ///
+    /// The `loop_end` and `end` opcodes must not be beyond the last frame.
+    /// The samples are looked for in the default path, and the regions
+    /// whose sample can't be read by the `resolver` are not checked.
+    pub fn validate_loops(&self, resolver: &impl SampleResolver) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for (index, region) in self.regions.iter().enumerate() {
+            let Some(frames) = self.sample_path(index).and_then(|p| resolver.frames(&p)) else {
+                continue;
+            };
+
+            for name in ["loop_end", "end"] {
+                if let Some(Opcode::loop_end(position) | Opcode::end(position)) =
+                    region.inherited_opcode(name, self)
+                {
+                    if u64::from(*position) >= frames {
+                        diagnostics.push(Diagnostic {
+                            header: Header::Region,
+                            index: Some(index),
+                            kind: DiagnosticKind::BeyondSampleEnd {
+                                opcode: name.into(),
+                                position: *position,
+                                frames,
+                            },
+                        });
+                    }
+                }
+            }
+        }
+        diagnostics
+    }
+
+    /// Checks the regions against the metadata of their samples,
+    /// returning the list of problems found
+    ///
+    /// Besides the checks of [`validate_loops`][Self::validate_loops], the filter
+    /// cutoffs must not be above the Nyquist frequency of the sample rate of the
+    /// sample, when the `resolver` knows it.
+    pub fn validate_samples(&self, resolver: &impl SampleResolver) -> Vec<Diagnostic> {
+        let mut diagnostics = self.validate_loops(resolver);
+
+        for (index, region) in self.regions.iter().enumerate() {
+            let Some(hz) = self
+                .sample_path(index)
+                .and_then(|p| resolver.sample_rate(&p))
+            else {
+                continue;
+            };
+            let nyquist = hz as f32 / 2.;
+
+            for name in ["cutoff", "cutoff2"] {
+                if let Some(Opcode::cutoff(cutoff) | Opcode::cutoff2(cutoff)) =
+                    region.inherited_opcode(name, self)
+                {
+                    if *cutoff > nyquist {
+                        diagnostics.push(Diagnostic {
+                            header: Header::Region,
+                            index: Some(index),
+                            kind: DiagnosticKind::CutoffAboveNyquist {
+                                opcode: name.into(),
+                                cutoff: *cutoff,
+                                nyquist,
+                            },
+                        });
+                    }
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests_validate {
+    use super::*;
+    use crate::sfz::Region;
+    use std::collections::HashMap;
+
+    /// Knows the length of some samples, without reading them.
+    struct Samples(HashMap<PathBuf, u64>);
+
+    impl SampleResolver for Samples {
+        fn frames(&self, path: &Path) -> Option<u64> {
+            self.0.get(path).copied()
+        }
+    }
+
+    #[test]
+    fn test_validate_misplaced_opcode() {
+        let i = Instrument::from_sfz(
+            "<control> default_path=samples
+            <region> sample=a.wav default_path=other",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(
+            i.validate(),
+            [Diagnostic {
+                header: Header::Region,
+                index: Some(0),
+                kind: DiagnosticKind::MisplacedOpcode {
+                    opcode: "default_path".into(),
+                    header: Header::Region,
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validate_cutoff_nyquist() {
+        let mut i = Instrument::from_sfz(
+            "<group> cutoff=20000
+            <region> sample=a.wav cutoff=30000",
+            Path::new(""),
+        )
+        .unwrap();
+
+        // unknown sample rate
+        assert!(i.validate().is_empty());
+
+        i.set_sample_rate(44100.);
+        assert_eq!(
+            i.validate(),
+            [Diagnostic {
+                header: Header::Region,
+                index: Some(0),
+                kind: DiagnosticKind::CutoffAboveNyquist {
+                    opcode: "cutoff".into(),
+                    cutoff: 30000.,
+                    nyquist: 22050.,
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validate_missing_group() {
+        let mut i = Instrument::from_sfz(
+            "<group> volume=1 <region> sample=a.wav
+            <group> volume=2 <region> sample=b.wav",
+            Path::new(""),
+        )
+        .unwrap();
+        assert!(i.validate().is_empty());
+
+        i.groups.pop();
+        assert_eq!(
+            i.validate(),
+            [Diagnostic {
+                header: Header::Region,
+                index: Some(1),
+                kind: DiagnosticKind::MissingGroup {
+                    group: 1,
+                    groups: 1,
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validate_is_playable() {
+        let resolver = Samples(HashMap::from([
+            (PathBuf::from("lib/a.wav"), 1000),
+            (PathBuf::from("lib/b.wav"), 1000),
+        ]));
+
+        let mut i = Instrument::from_sfz(
+            "<region> sample=a.wav lokey=60 hikey=64
+            <region> sample=b.wav lovel=64
+            <region>",
+            Path::new("lib"),
+        )
+        .unwrap();
+        i.add_region(Region::new());
+        i.add_opcode(&Opcode::sample(PathBuf::from("*sine")))
+            .unwrap();
+        assert_eq!(i.is_playable(&resolver), Ok(()));
+
+        let i = Instrument::from_sfz(
+            "<region> sample=a.wav lokey=64 hikey=60
+            <region> sample=missing.wav
+            <region> lovel=100 hivel=10",
+            Path::new("lib"),
+        )
+        .unwrap();
+        let kinds: Vec<DiagnosticKind> = i
+            .is_playable(&resolver)
+            .unwrap_err()
+            .into_iter()
+            .map(|d| d.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                DiagnosticKind::EmptyRange {
+                    low: "lokey".into(),
+                    lo: 64,
+                    high: "hikey".into(),
+                    hi: 60,
+                },
+                DiagnosticKind::SampleNotFound {
+                    path: PathBuf::from("lib/missing.wav"),
+                },
+                DiagnosticKind::NoSample,
+                DiagnosticKind::EmptyRange {
+                    low: "lovel".into(),
+                    lo: 100,
+                    high: "hivel".into(),
+                    hi: 10,
+                },
+            ]
+        );
+        assert_eq!(
+            kinds[1].to_string(),
+            "the sample `lib/missing.wav` can't be read"
+        );
+    }
+
+    #[test]
+    fn test_validate_loops() {
+        let i = Instrument::from_sfz(
+            "<control> default_path=samples/
+            <group> loop_end=999
+            <region> sample=short.wav
+            <region> sample=short.wav loop_end=100 end=1000
+            <region> sample=long.wav
+            <region> sample=missing.wav loop_end=100000",
+            Path::new("lib"),
+        )
+        .unwrap();
+        let resolver = Samples(HashMap::from([
+            (PathBuf::from("lib/samples/short.wav"), 1000),
+            (PathBuf::from("lib/samples/long.wav"), 48000),
+        ]));
+
+        assert_eq!(
+            i.validate_loops(&resolver),
+            [Diagnostic {
+                header: Header::Region,
+                index: Some(1),
+                kind: DiagnosticKind::BeyondSampleEnd {
+                    opcode: "end".into(),
+                    position: 1000,
+                    frames: 1000,
+                },
+            }]
+        );
+
+        let resolver = Samples(HashMap::from([(
+            PathBuf::from("lib/samples/short.wav"),
+            500,
+        )]));
+        let diagnostics = i.validate_loops(&resolver);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].kind.to_string(),
+            "`loop_end` of 999 is beyond the 500 frames of the sample"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "wav")]
+    fn test_validate_samples() {
+        let i = Instrument::from_sfz(
+            "<region> sample=tiny.wav end=9 cutoff=10000
+            <region> sample=tiny.wav end=10 cutoff2=12000",
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures"),
+        )
+        .unwrap();
+        let kinds: Vec<_> = i
+            .validate_samples(&crate::wav::WavResolver)
+            .into_iter()
+            .map(|d| (d.index, d.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (
+                    Some(1),
+                    DiagnosticKind::BeyondSampleEnd {
+                        opcode: "end".into(),
+                        position: 10,
+                        frames: 10,
+                    }
+                ),
+                (
+                    Some(1),
+                    DiagnosticKind::CutoffAboveNyquist {
+                        opcode: "cutoff2".into(),
+                        cutoff: 12000.,
+                        nyquist: 11025.,
+                    }
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//! Problems found while parsing that don't stop the parsing.
+
+use thiserror::Error;
+
+/// Something in the SFZ code that the parser had to ignore.
+///
+/// Returned by [`Instrument::from_sfz_verbose`][crate::Instrument::from_sfz_verbose].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Warning {
+    /// The line where it was found, counting from 1.
+    pub line: usize,
+
+    /// The opcode name as written, the header for a misplaced or unknown header,
+    /// or the variable for an undefined variable.
+    pub opcode: String,
+
+    /// The raw value of the opcode, empty for a header or a variable.
+    pub value: String,
+
+    /// The reason it was ignored.
+    pub kind: WarningKind,
+}
+
+/// All the reasons for a [`Warning`].
+#[derive(Error, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WarningKind {
+    /// The opcode is not supported.
+    #[error("unknown opcode")]
+    UnknownOpcode,
+
+    /// The value is outside the range of the opcode.
+    #[error("value out of range")]
+    OutOfRange,
+
+    /// The header appears after the headers it must precede, or more than once.
+    #[error("misplaced header")]
+    MisplacedHeader,
+
+    /// The header is not supported.
+    #[error("unknown header")]
+    UnknownHeader,
+
+    /// The opcode appears before any header.
+    #[error("opcode outside of any header")]
+    MisplacedOpcode,
+
+    /// The variable is used without having been defined with `#define`.
+    #[error("undefined variable")]
+    UndefinedVariable,
+}
+
+impl Warning {
+    /// A new warning, whose line is filled in later by the parser loop.
+    pub(crate) fn new(opcode: &str, value: &str, kind: WarningKind) -> Self {
+        Self {
+            line: 0,
+            opcode: opcode.to_string(),
+            value: value.to_string(),
+            kind,
+        }
+    }
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.value.is_empty() {
+            write!(f, "line {}: {} `{}`", self.line, self.kind, self.opcode)
+        } else {
+            write!(
+                f,
+                "line {}: {} `{}={}`",
+                self.line, self.kind, self.opcode, self.value
+            )
+        }
+    }
+}
@@ -1,166 +1,323 @@
+use std::{fmt::Display, str::FromStr};
+
+use log::warn;
+use once_cell::sync::Lazy;
 use regex::Regex;
 
-/// Receive a string, try to parse it as f32
+/// The reason why a value can't be parsed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ValueError {
+    /// The value is malformed, like a number with letters in it.
+    Invalid,
+    /// The value is well formed, but out of the allowed range.
+    OutOfRange,
+}
+
+/// Receive a string, try to parse it as a `T` number
+///
+/// A number that can't be represented by `T`, like a negative one
+/// for an unsigned type, is considered out of range.
+fn try_parse<T: FromStr>(value: &str, type_name: &str) -> Result<T, ValueError> {
+    value.parse::<T>().map_err(|_| {
+        warn!("`{}` is not a valid {} number", value, type_name);
+        if value.parse::<f64>().is_ok() {
+            ValueError::OutOfRange
+        } else {
+            ValueError::Invalid
+        }
+    })
+}
+
+/// Receive a string, try to parse it as a `T` number between a given range
+///
+fn try_parse_between<T: FromStr + PartialOrd + Display>(
+    value: &str,
+    type_name: &str,
+    min: T,
+    max: T,
+) -> Result<T, ValueError> {
+    let num = try_parse(value, type_name)?;
+    if num >= min && num <= max {
+        Ok(num)
+    } else {
+        warn!("`{}` is out of the range {} to {}", value, min, max);
+        Err(ValueError::OutOfRange)
+    }
+}
+
+/// Receive a string, try to parse it as a finite f32
 ///
-pub(crate) fn check_f32(value: &str) -> f32 {
-    let num: f32 = value
-        .parse::<f32>()
-        .unwrap_or_else(|_| panic!("ERROR: `{}` is not a valid f32 number", value));
-    num
+/// The infinite and NaN values are considered out of range.
+pub(crate) fn try_check_f32(value: &str) -> Result<f32, ValueError> {
+    let num: f32 = try_parse(value, "f32")?;
+    if num.is_finite() {
+        Ok(num)
+    } else {
+        warn!("`{}` is not a finite number", value);
+        Err(ValueError::OutOfRange)
+    }
+}
+
+/// Receive a string, try to parse it as f32, returning `None` if it's not valid
+///
+pub(crate) fn check_f32(value: &str) -> Option<f32> {
+    try_check_f32(value).ok()
 }
 
 /// Receive a string, try to parse it as f32 between a given range
 ///
-// NOTE: floating-point types cannot be used in patterns
-// https://github.com/rust-lang/rust/issues/41620
-// solution from: https://stackoverflow.com/a/58434531/940200
+pub(crate) fn try_check_f32_between(value: &str, min: f32, max: f32) -> Result<f32, ValueError> {
+    try_parse_between(value, "f32", min, max)
+}
+
+/// Receive a string, try to parse it as f32 between a given range,
+/// returning `None` if it's not valid
 ///
 pub(crate) fn check_f32_between(value: &str, min: f32, max: f32) -> Option<f32> {
-    let num = check_f32(value);
-    match num {
-        num if (min..=max).contains(&num) => Some(num),
-        _ => None,
-    }
+    try_check_f32_between(value, min, max).ok()
 }
 
 /// Receive a string, try to parse it as u8
 ///
-pub(crate) fn check_u8(value: &str) -> u8 {
-    let num: u8 = value
-        .parse::<u8>()
-        .unwrap_or_else(|_| panic!("ERROR: `{}` is not a valid u8 number", value));
-    num
+pub(crate) fn try_check_u8(value: &str) -> Result<u8, ValueError> {
+    try_parse(value, "u8")
+}
+
+/// Receive a string, try to parse it as u8, returning `None` if it's not valid
+///
+pub(crate) fn check_u8(value: &str) -> Option<u8> {
+    try_check_u8(value).ok()
 }
 
 /// Receive a string, try to parse it as u8 between a given range
 ///
+pub(crate) fn try_check_u8_between(value: &str, min: u8, max: u8) -> Result<u8, ValueError> {
+    try_parse_between(value, "u8", min, max)
+}
+
+/// Receive a string, try to parse it as u8 between a given range,
+/// returning `None` if it's not valid
+///
 pub(crate) fn check_u8_between(value: &str, min: u8, max: u8) -> Option<u8> {
-    let num = check_u8(value);
-    if num >= min && num <= max {
-        Some(num)
-    } else {
-        None
-    }
+    try_check_u8_between(value, min, max).ok()
 }
 
 /// Receive a string, try to parse it as i8
 ///
-pub(crate) fn check_i8(value: &str) -> i8 {
-    let num: i8 = value
-        .parse::<i8>()
-        .unwrap_or_else(|_| panic!("ERROR: `{}` is not a valid i8 number", value));
-    num
+pub(crate) fn try_check_i8(value: &str) -> Result<i8, ValueError> {
+    try_parse(value, "i8")
+}
+
+/// Receive a string, try to parse it as i8, returning `None` if it's not valid
+///
+pub(crate) fn check_i8(value: &str) -> Option<i8> {
+    try_check_i8(value).ok()
 }
 
 /// Receive a string, try to parse it as i8 between a given range
 ///
+pub(crate) fn try_check_i8_between(value: &str, min: i8, max: i8) -> Result<i8, ValueError> {
+    try_parse_between(value, "i8", min, max)
+}
+
+/// Receive a string, try to parse it as i8 between a given range,
+/// returning `None` if it's not valid
+///
 pub(crate) fn check_i8_between(value: &str, min: i8, max: i8) -> Option<i8> {
-    let num = check_i8(value);
-    if num >= min && num <= max {
-        Some(num)
-    } else {
-        None
-    }
+    try_check_i8_between(value, min, max).ok()
 }
 
 /// Receive a string, try to parse it as i16
 ///
-pub(crate) fn check_i16(value: &str) -> i16 {
-    let num: i16 = value
-        .parse::<i16>()
-        .unwrap_or_else(|_| panic!("ERROR: `{}` is not a valid i16 number", value));
-    num
+pub(crate) fn try_check_i16(value: &str) -> Result<i16, ValueError> {
+    try_parse(value, "i16")
+}
+
+/// Receive a string, try to parse it as i16, returning `None` if it's not valid
+///
+pub(crate) fn check_i16(value: &str) -> Option<i16> {
+    try_check_i16(value).ok()
 }
 
 /// Receive a string, try to parse it as i16 between a given range
 ///
+pub(crate) fn try_check_i16_between(value: &str, min: i16, max: i16) -> Result<i16, ValueError> {
+    try_parse_between(value, "i16", min, max)
+}
+
+/// Receive a string, try to parse it as i16 between a given range,
+/// returning `None` if it's not valid
+///
 pub(crate) fn check_i16_between(value: &str, min: i16, max: i16) -> Option<i16> {
-    let num = check_i16(value);
-    if num >= min && num <= max {
-        Some(num)
-    } else {
-        None
-    }
+    try_check_i16_between(value, min, max).ok()
+}
+
+/// Receive a string, try to parse it as i32
+///
+pub(crate) fn try_check_i32(value: &str) -> Result<i32, ValueError> {
+    try_parse(value, "i32")
+}
+
+/// Receive a string, try to parse it as i32, returning `None` if it's not valid
+///
+pub(crate) fn check_i32(value: &str) -> Option<i32> {
+    try_check_i32(value).ok()
+}
+
+/// Receive a string, try to parse it as i32 between a given range
+///
+pub(crate) fn try_check_i32_between(value: &str, min: i32, max: i32) -> Result<i32, ValueError> {
+    try_parse_between(value, "i32", min, max)
+}
+
+/// Receive a string, try to parse it as i32 between a given range,
+/// returning `None` if it's not valid
+///
+pub(crate) fn check_i32_between(value: &str, min: i32, max: i32) -> Option<i32> {
+    try_check_i32_between(value, min, max).ok()
 }
 
 /// Receive a string, try to parse it as u16
 ///
-pub(crate) fn check_u16(value: &str) -> u16 {
-    let num: u16 = value
-        .parse::<u16>()
-        .unwrap_or_else(|_| panic!("ERROR: `{}` is not a valid u16 number", value));
-    num
+pub(crate) fn try_check_u16(value: &str) -> Result<u16, ValueError> {
+    try_parse(value, "u16")
+}
+
+/// Receive a string, try to parse it as u16, returning `None` if it's not valid
+///
+pub(crate) fn check_u16(value: &str) -> Option<u16> {
+    try_check_u16(value).ok()
 }
 
 /// Receive a string, try to parse it as u16 between a given range
 ///
+pub(crate) fn try_check_u16_between(value: &str, min: u16, max: u16) -> Result<u16, ValueError> {
+    try_parse_between(value, "u16", min, max)
+}
+
+/// Receive a string, try to parse it as u16 between a given range,
+/// returning `None` if it's not valid
+///
 pub(crate) fn check_u16_between(value: &str, min: u16, max: u16) -> Option<u16> {
-    let num = check_u16(value);
-    if num >= min && num <= max {
-        Some(num)
-    } else {
-        None
-    }
+    try_check_u16_between(value, min, max).ok()
 }
 
 /// Receive a string, try to parse it as u32
 ///
-pub(crate) fn check_u32(value: &str) -> u32 {
-    let num: u32 = value
-        .parse::<u32>()
-        .unwrap_or_else(|_| panic!("ERROR: `{}` is not a valid u32 number", value));
-    num
+pub(crate) fn try_check_u32(value: &str) -> Result<u32, ValueError> {
+    try_parse(value, "u32")
+}
+
+/// Receive a string, try to parse it as u32, returning `None` if it's not valid
+///
+pub(crate) fn check_u32(value: &str) -> Option<u32> {
+    try_check_u32(value).ok()
 }
 
 /// Receive a string, try to parse it as u32 between a given range
 ///
+pub(crate) fn try_check_u32_between(value: &str, min: u32, max: u32) -> Result<u32, ValueError> {
+    try_parse_between(value, "u32", min, max)
+}
+
+/// Receive a string, try to parse it as u32 between a given range,
+/// returning `None` if it's not valid
+///
 pub(crate) fn check_u32_between(value: &str, min: u32, max: u32) -> Option<u32> {
-    let num = check_u32(value);
-    if num >= min && num <= max {
-        Some(num)
-    } else {
-        None
+    try_check_u32_between(value, min, max).ok()
+}
+
+/// The regular expression matching a note name, like `C#4`,
+/// capturing the note and the octave.
+pub(crate) const NOTE_REGEX: &str = r"([a-gA-G][bB#]?)(-?\d+)";
+
+/// Finds a note name anywhere in a value.
+static NOTE: Lazy<Regex> = Lazy::new(|| Regex::new(NOTE_REGEX).unwrap());
+
+/// Matches a value that is just a note name.
+static WHOLE_NOTE: Lazy<Regex> = Lazy::new(|| Regex::new(&format!("^{}$", NOTE_REGEX)).unwrap());
+
+/// Receive a note without octave, like `C#`, and return its semitones above C
+fn note_semitones(note: &str) -> Option<i8> {
+    match note.to_lowercase().as_str() {
+        "c" => Some(0),
+        "c#" => Some(1),
+        "db" => Some(1),
+        "d" => Some(2),
+        "d#" => Some(3),
+        "eb" => Some(3),
+        "e" => Some(4),
+        "f" => Some(5),
+        "f#" => Some(6),
+        "gb" => Some(6),
+        "g" => Some(7),
+        "g#" => Some(8),
+        "ab" => Some(8),
+        "a" => Some(9),
+        "a#" => Some(10),
+        "bb" => Some(10),
+        "b" => Some(11),
+        _ => None,
     }
 }
 
 /// Receive a string, try to parse it as MIDI note (see "key" opcodes)
 ///
-pub(crate) fn check_midi_note(value: &str) -> Option<u8> {
-    let note_regex = Regex::new(r"([a-gA-G][bB#]?)(-?\d+)").unwrap();
-    if let Some(captures) = note_regex.captures(value) {
+pub(crate) fn try_check_midi_note(value: &str) -> Result<u8, ValueError> {
+    if let Some(captures) = NOTE.captures(value) {
         // encoded as text
-        let note_semitones = match captures[1].to_lowercase().as_str() {
-            "c" => 0,
-            "c#" => 1,
-            "db" => 1,
-            "d" => 2,
-            "d#" => 3,
-            "eb" => 3,
-            "e" => 4,
-            "f" => 5,
-            "f#" => 6,
-            "gb" => 6,
-            "g" => 7,
-            "g#" => 8,
-            "ab" => 8,
-            "a" => 9,
-            "a#" => 10,
-            "bb" => 10,
-            "b" => 11,
-            _ => panic!("ERROR: `{}` is not a valid note", &captures[1]),
-        };
+        let note_semitones = note_semitones(&captures[1]).ok_or(ValueError::Invalid);
         let octave = captures[2]
-            .parse::<i8>()
-            .unwrap_or_else(|_| panic!("ERROR: `{}` is not a valid i8 number", &captures[2]));
-        let midi_note = u8::try_from(12 + note_semitones + octave * 12)
-            .unwrap_or_else(|_| panic!("ERROR: `{}` is outside of MIDI note range", value));
-        Some(midi_note)
+            .parse::<i32>()
+            .map_err(|_| ValueError::OutOfRange);
+        let midi_note = note_semitones.and_then(|n| {
+            let note = 12 + i32::from(n) + octave?.saturating_mul(12);
+            u8::try_from(note)
+                .ok()
+                .filter(|note| *note <= 127)
+                .ok_or(ValueError::OutOfRange)
+        });
+        if midi_note.is_err() {
+            warn!("`{}` is not a valid MIDI note", value);
+        }
+        midi_note
     } else {
         // encoded as u8
-        check_u8_between(value, 0, 127)
+        try_check_u8_between(value, 0, 127)
     }
 }
 
+/// Receive a string, try to parse it as MIDI note, returning `None` if it's not valid
+///
+pub(crate) fn check_midi_note(value: &str) -> Option<u8> {
+    try_check_midi_note(value).ok()
+}
+
+/// Receive a string, try to parse it as a note name, like `C#4`,
+/// returning `None` instead of panicking if it's not a valid one
+///
+pub(crate) fn note_name_to_number(value: &str) -> Option<u8> {
+    let captures = WHOLE_NOTE.captures(value)?;
+    let note_semitones = i32::from(note_semitones(&captures[1])?);
+    let octave = captures[2].parse::<i32>().ok()?;
+    u8::try_from(12 + note_semitones + octave.saturating_mul(12))
+        .ok()
+        .filter(|note| *note <= 127)
+}
+
+/// Receive a MIDI note number, and return its name (see "key" opcodes)
+///
+/// Uses sharps for the black keys, and the octave numbering where
+/// the MIDI note number 60 is C4.
+///
+pub(crate) fn note_number_to_name(note: u8) -> String {
+    const NAMES: [&str; 12] = [
+        "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+    ];
+    let octave = i16::from(note) / 12 - 1;
+    format!("{}{}", NAMES[usize::from(note % 12)], octave)
+}
+
 /*
 // IDEA:WIP create wrapper macro
 // check!(value, f32, 0.1, 100.)
@@ -193,7 +350,19 @@ macro_rules! check {
 
 #[cfg(test)]
 mod tests_parse {
-    use super::check_midi_note;
+    use super::{
+        check_midi_note, note_name_to_number, note_number_to_name, try_check_f32, ValueError,
+    };
+
+    #[test]
+    fn test_parse_f32() {
+        assert_eq!(try_check_f32("-2.5"), Ok(-2.5));
+        assert_eq!(try_check_f32("1e3"), Ok(1000.));
+        assert_eq!(try_check_f32("abc"), Err(ValueError::Invalid));
+        for value in ["nan", "NaN", "inf", "-inf", "infinity"] {
+            assert_eq!(try_check_f32(value), Err(ValueError::OutOfRange));
+        }
+    }
 
     #[test]
     fn test_parse_valid_midi_notes() {
@@ -208,10 +377,32 @@ mod tests_parse {
     #[test]
     fn test_parse_invalid_midi_notes() {
         assert_eq!(check_midi_note("128"), None);
-        assert!(std::panic::catch_unwind(|| check_midi_note("-1")).is_err());
-        assert!(std::panic::catch_unwind(|| check_midi_note("c")).is_err());
-        assert!(std::panic::catch_unwind(|| check_midi_note("de#2")).is_err());
-        assert!(std::panic::catch_unwind(|| check_midi_note("d#-9")).is_err());
-        assert!(std::panic::catch_unwind(|| check_midi_note("q#2")).is_err());
+        assert_eq!(check_midi_note("-1"), None);
+        assert_eq!(check_midi_note("c"), None);
+        assert_eq!(check_midi_note("de#2"), None);
+        assert_eq!(check_midi_note("d#-9"), None);
+        assert_eq!(check_midi_note("q#2"), None);
+        assert_eq!(check_midi_note("g#99"), None);
+    }
+
+    #[test]
+    fn test_note_name_to_number() {
+        assert_eq!(note_name_to_number("A#1"), Some(34));
+        assert_eq!(note_name_to_number("G9"), Some(127));
+        assert_eq!(note_name_to_number("G#9"), None);
+        assert_eq!(note_name_to_number("e#2"), None);
+        assert_eq!(note_name_to_number("v1"), None);
+        assert_eq!(note_name_to_number("60"), None);
+    }
+
+    #[test]
+    fn test_note_number_to_name() {
+        assert_eq!(note_number_to_name(0), "C-1");
+        assert_eq!(note_number_to_name(60), "C4");
+        assert_eq!(note_number_to_name(106), "A#7");
+        assert_eq!(note_number_to_name(127), "G9");
+        for note in 0..=127 {
+            assert_eq!(check_midi_note(&note_number_to_name(note)), Some(note));
+        }
     }
 }
@@ -0,0 +1,132 @@
+//! Reads the metadata of WAV samples, without decoding their audio.
+//!
+//! Only the RIFF chunks needed are read, the `fmt ` chunk and the size
+//! of the `data` chunk, so that it's cheap to check many samples.
+
+use std::{
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use crate::{
+    error::{Error, Result},
+    SampleResolver,
+};
+
+/// The basic metadata of a WAV sample.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WavInfo {
+    /// The sample rate, in Hz.
+    pub sample_rate: u32,
+
+    /// The number of channels.
+    pub channels: u16,
+
+    /// The number of frames, each one having a sample for every channel.
+    pub frames: u64,
+}
+
+/// Reads the header of the WAV file at `path`.
+pub fn read_header(path: impl AsRef<Path>) -> Result<WavInfo> {
+    let mut reader = BufReader::new(File::open(path)?);
+
+    let mut riff = [0; 12];
+    reader.read_exact(&mut riff)?;
+    if &riff[0..4] != b"RIFF" || &riff[8..12] != b"WAVE" {
+        return Err(Error::InvalidWav("not a RIFF WAVE file".into()));
+    }
+
+    // (sample_rate, channels, block_align)
+    let mut format: Option<(u32, u16, u16)> = None;
+    loop {
+        let mut chunk = [0; 8];
+        if reader.read_exact(&mut chunk).is_err() {
+            return Err(Error::InvalidWav("missing the `data` chunk".into()));
+        }
+        let size = u32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]);
+
+        match &chunk[0..4] {
+            b"fmt " => {
+                if size < 16 {
+                    return Err(Error::InvalidWav("the `fmt ` chunk is too short".into()));
+                }
+                let mut fmt = [0; 16];
+                reader.read_exact(&mut fmt)?;
+                format = Some((
+                    u32::from_le_bytes([fmt[4], fmt[5], fmt[6], fmt[7]]),
+                    u16::from_le_bytes([fmt[2], fmt[3]]),
+                    u16::from_le_bytes([fmt[12], fmt[13]]),
+                ));
+                skip_chunk(&mut reader, size - 16)?;
+            }
+            b"data" => {
+                let (sample_rate, channels, block_align) =
+                    format.ok_or_else(|| Error::InvalidWav("missing the `fmt ` chunk".into()))?;
+                if block_align == 0 {
+                    return Err(Error::InvalidWav("the block align is zero".into()));
+                }
+                return Ok(WavInfo {
+                    sample_rate,
+                    channels,
+                    frames: u64::from(size) / u64::from(block_align),
+                });
+            }
+            _ => skip_chunk(&mut reader, size)?,
+        }
+    }
+}
+
+/// Skips the rest of a chunk, including its padding byte if its size is odd.
+fn skip_chunk(reader: &mut impl Seek, size: u32) -> Result<()> {
+    reader.seek(SeekFrom::Current(i64::from(size) + i64::from(size % 2)))?;
+    Ok(())
+}
+
+/// Resolves the samples by reading the headers of their WAV files.
+///
+/// Used by [`Instrument::validate_loops`][crate::Instrument::validate_loops]
+/// and [`Instrument::validate_samples`][crate::Instrument::validate_samples].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WavResolver;
+
+impl SampleResolver for WavResolver {
+    fn frames(&self, path: &Path) -> Option<u64> {
+        read_header(path).ok().map(|info| info.frames)
+    }
+
+    fn sample_rate(&self, path: &Path) -> Option<u32> {
+        read_header(path).ok().map(|info| info.sample_rate)
+    }
+}
+
+#[cfg(test)]
+mod tests_wav {
+    use super::*;
+
+    fn fixture(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    #[test]
+    fn test_read_header() {
+        assert_eq!(
+            read_header(fixture("tiny.wav")).unwrap(),
+            WavInfo {
+                sample_rate: 22050,
+                channels: 2,
+                frames: 10,
+            }
+        );
+        assert!(matches!(
+            read_header(fixture("hierarchy.sfz")),
+            Err(Error::InvalidWav(_))
+        ));
+        assert!(matches!(
+            read_header(fixture("missing.wav")),
+            Err(Error::IOError(_))
+        ));
+    }
+}
@@ -0,0 +1,98 @@
+//! Tests that no input, however malformed, makes the parser panic.
+
+use std::path::Path;
+
+use sofiza::Instrument;
+
+/// Pieces of SFZ code, both valid and malformed, to combine into inputs.
+static PIECES: &[&str] = &[
+    "<region>",
+    "<group>",
+    "<global>",
+    "<control>",
+    "<effect>",
+    "<curve>",
+    "<master>",
+    "<",
+    ">",
+    "sample",
+    "cutoff",
+    "lokey",
+    "key",
+    "lfo1_freq",
+    "var01_mod",
+    "v",
+    "eq1_bwcc2",
+    "type",
+    "default_path",
+    "=",
+    "=",
+    "-",
+    ".",
+    "5",
+    "0.5",
+    "-1",
+    "999999",
+    "c4",
+    "z#9",
+    "g#99",
+    "a.wav",
+    "x",
+    " ",
+    " ",
+    "\n",
+    "\t",
+    "/",
+    "//",
+    "\\",
+    "é",
+    "_",
+    "#",
+];
+
+/// A small deterministic pseudo-random number generator.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) as usize
+    }
+}
+
+#[test]
+fn malformed_opcodes() {
+    for sfz in [
+        "cutoff=",
+        "=5",
+        "=",
+        "<region> cutoff=",
+        "<region> =5",
+        "<region> =",
+    ] {
+        assert!(Instrument::from_sfz(sfz, Path::new("")).is_ok());
+    }
+}
+
+#[test]
+fn opcodes_before_any_header() {
+    let i = Instrument::from_sfz("cutoff=500 <region> sample=a.wav", Path::new("")).unwrap();
+    assert!(i.global.is_empty());
+    assert_eq!(i.regions[0].opcodes.len(), 1);
+}
+
+#[test]
+fn random_inputs() {
+    let mut rng = Lcg(0x5f2);
+    for _ in 0..5_000 {
+        let len = rng.next() % 24;
+        let sfz: String = (0..len)
+            .map(|_| PIECES[rng.next() % PIECES.len()])
+            .collect();
+        // it can return an error, but it must not panic
+        let _ = Instrument::from_sfz(&sfz, Path::new(""));
+    }
+}
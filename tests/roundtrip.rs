@@ -0,0 +1,205 @@
+//! Round-trip fidelity tests: parse, serialize with `to_sfz`, and parse again.
+
+use std::path::{Path, PathBuf};
+
+use sofiza::{FormatOptions, Instrument, SfzWriteOptions, WarningKind};
+
+static HIERARCHY: &str = include_str!("fixtures/hierarchy.sfz");
+static HEADERS: &str = include_str!("fixtures/headers.sfz");
+static NYLON_GUITAR: &str = include_str!("../examples/instruments/Nylon Guitar.sfz");
+
+/// Parses the sfz code, serializes it and parses it again,
+/// returning both instruments.
+fn roundtrip(sfz: &str) -> (Instrument, Instrument) {
+    let original = Instrument::from_sfz(sfz, Path::new("")).unwrap();
+    let reparsed = Instrument::from_sfz(&original.to_sfz(), Path::new("")).unwrap();
+    (original, reparsed)
+}
+
+#[test]
+fn roundtrip_hierarchy() {
+    let (original, reparsed) = roundtrip(HIERARCHY);
+    assert_eq!(original.groups(), 2);
+    assert_eq!(original.regions(), 3);
+    assert_eq!(original, reparsed);
+
+    // the serialized output is stable
+    assert_eq!(original.to_sfz(), reparsed.to_sfz());
+}
+
+#[test]
+fn roundtrip_preserves_order() {
+    let (original, reparsed) = roundtrip(HIERARCHY);
+
+    for i in [&original, &reparsed] {
+        let global: Vec<&String> = i.global.keys().collect();
+        assert_eq!(global, ["volume", "fil_type"]);
+
+        let group: Vec<&String> = i.groups[0].opcodes.keys().collect();
+        assert_eq!(group, ["ampeg_release", "ampeg_attack"]);
+
+        let region: Vec<&String> = i.regions[1].opcodes.keys().collect();
+        assert_eq!(
+            region,
+            ["sample", "lokey", "hikey", "pitch_keycenter", "tune"]
+        );
+
+        let groups: Vec<Option<usize>> = i.regions.iter().map(|r| r.group()).collect();
+        assert_eq!(groups, [Some(0), Some(0), Some(1)]);
+    }
+}
+
+#[test]
+fn roundtrip_pretty() {
+    let original = Instrument::from_sfz(HIERARCHY, Path::new("")).unwrap();
+    let format = FormatOptions {
+        indent: 4,
+        blank_lines: false,
+        align_equals: true,
+    };
+    let pretty = original.to_sfz_pretty(format);
+    assert!(pretty.starts_with("<global>\n      volume=3\n    fil_type=lpf_2p\n<group>\n"));
+
+    let reparsed = Instrument::from_sfz(&pretty, Path::new("")).unwrap();
+    assert_eq!(original, reparsed);
+    assert_eq!(pretty, reparsed.to_sfz_pretty(format));
+
+    // the default format is the same as the plain one
+    assert_eq!(
+        original.to_sfz_pretty(FormatOptions::default()),
+        original.to_sfz()
+    );
+}
+
+#[test]
+fn roundtrip_headers() {
+    let original = Instrument::from_sfz(HEADERS, Path::new("root")).unwrap();
+    assert_eq!(original.default_path, Path::new("root/samples"));
+    assert_eq!(original.masters(), 2);
+    assert_eq!(original.groups(), 3);
+    assert_eq!(original.regions(), 4);
+    assert_eq!(original.effects.len(), 1);
+    assert_eq!(original.curves(), 1);
+
+    let reparsed = Instrument::from_sfz(&original.to_sfz(), Path::new("root")).unwrap();
+    assert_eq!(original, reparsed);
+    for r in 0..original.regions() {
+        assert_eq!(original.sample_path(r), reparsed.sample_path(r));
+    }
+}
+
+#[test]
+fn roundtrip_nylon_guitar() {
+    let (original, reparsed) = roundtrip(NYLON_GUITAR);
+    assert_eq!(original.regions(), 267);
+    assert_eq!(original, reparsed);
+}
+
+#[test]
+fn roundtrip_directives() {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let original = Instrument::from_file(&fixtures.join("directives.sfz")).unwrap();
+    assert_eq!(original.defines["KEY"], "60");
+    assert_eq!(original.includes, [PathBuf::from("directives_regions.sfz")]);
+    assert_eq!(original.regions(), 2);
+
+    // inlined
+    let inlined = original.to_sfz();
+    assert!(!inlined.contains('#'));
+    let reparsed = Instrument::from_sfz(&inlined, &fixtures).unwrap();
+    assert_eq!(original.groups, reparsed.groups);
+    assert_eq!(original.regions, reparsed.regions);
+
+    // preserved
+    let options = SfzWriteOptions {
+        inline_includes: false,
+        preserve_defines: true,
+    };
+    let preserved = original.to_sfz_with(options);
+    assert_eq!(
+        preserved,
+        "#define $KEY 60\n\n<group>\npitch_keycenter=60\n\n#include \"directives_regions.sfz\"\n"
+    );
+    let reparsed = Instrument::from_sfz(&preserved, &fixtures).unwrap();
+    assert_eq!(original, reparsed);
+    assert_eq!(preserved, reparsed.to_sfz_with(options));
+}
+
+/// Returns the paths of the SFZ files of the fixtures and of the bundled instruments,
+/// leaving out the ones that are only meant to be included by others.
+fn sfz_files() -> Vec<PathBuf> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut files: Vec<PathBuf> = ["tests/fixtures", "examples/instruments"]
+        .iter()
+        .flat_map(|dir| std::fs::read_dir(root.join(dir)).unwrap())
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "sfz"))
+        .collect();
+    files.sort();
+
+    let included: Vec<String> = files
+        .iter()
+        .flat_map(|path| {
+            std::fs::read_to_string(path)
+                .unwrap()
+                .lines()
+                .map(str::to_owned)
+                .collect::<Vec<_>>()
+        })
+        .filter_map(|line| {
+            Some(
+                line.trim()
+                    .strip_prefix("#include")?
+                    .trim()
+                    .trim_matches('"')
+                    .to_owned(),
+            )
+        })
+        .collect();
+    files.retain(|path| {
+        let name = path.file_name().unwrap().to_str().unwrap();
+        !included.iter().any(|include| include == name)
+    });
+    files
+}
+
+/// Every SFZ file must survive a round trip through `to_sfz`.
+///
+/// The comparison leaves out what `to_sfz` doesn't write back on purpose:
+/// the `#define` and `#include` directives, which are substituted and inlined,
+/// and the opcodes the parser doesn't know, which are dropped with a warning.
+#[test]
+fn roundtrip_every_file() {
+    let files = sfz_files();
+    assert!(files.len() >= 4);
+
+    for path in files {
+        let dir = path.parent().unwrap();
+        let sfz = std::fs::read_to_string(&path).unwrap();
+        let (original, _) = Instrument::from_sfz_verbose(&sfz, dir).unwrap();
+
+        let serialized = original.to_sfz();
+        let (reparsed, warnings) = Instrument::from_sfz_verbose(&serialized, dir).unwrap();
+        assert!(
+            warnings
+                .iter()
+                .all(|w| w.kind != WarningKind::UnknownOpcode),
+            "{}: {warnings:?}",
+            path.display()
+        );
+
+        assert_eq!(original.global, reparsed.global, "{}", path.display());
+        assert_eq!(original.masters, reparsed.masters, "{}", path.display());
+        assert_eq!(original.groups, reparsed.groups, "{}", path.display());
+        assert_eq!(original.regions, reparsed.regions, "{}", path.display());
+        assert_eq!(original.effects, reparsed.effects, "{}", path.display());
+        assert_eq!(original.curves, reparsed.curves, "{}", path.display());
+        assert_eq!(
+            original.default_path,
+            reparsed.default_path,
+            "{}",
+            path.display()
+        );
+        assert_eq!(serialized, reparsed.to_sfz(), "{}", path.display());
+    }
+}
@@ -0,0 +1,33 @@
+//! Serde round-trip tests: parse, serialize to JSON, and deserialize again.
+#![cfg(feature = "serde")]
+
+use std::path::Path;
+
+use sofiza::{Instrument, Opcode};
+
+static HIERARCHY: &str = include_str!("fixtures/hierarchy.sfz");
+
+#[test]
+fn serde_roundtrip_hierarchy() {
+    let original = Instrument::from_sfz(HIERARCHY, Path::new("")).unwrap();
+    let json = serde_json::to_string(&original).unwrap();
+    let deserialized: Instrument = serde_json::from_str(&json).unwrap();
+    assert_eq!(original, deserialized);
+}
+
+#[test]
+fn serde_opcode_form() {
+    // opcodes are externally tagged by their name
+    assert_eq!(
+        serde_json::to_string(&Opcode::cutoff(500.)).unwrap(),
+        r#"{"cutoff":500.0}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&Opcode::loccN(64, 10)).unwrap(),
+        r#"{"loccN":[64,10]}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<Opcode>(r#"{"loop_mode":"loop_continuous"}"#).unwrap(),
+        Opcode::loop_mode(sofiza::loop_mode::loop_continuous)
+    );
+}
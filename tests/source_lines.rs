@@ -0,0 +1,20 @@
+//! Tests for the source location of the parsed headers.
+
+use std::path::Path;
+
+use sofiza::{Instrument, Region};
+
+static HIERARCHY: &str = include_str!("fixtures/hierarchy.sfz");
+
+#[test]
+fn source_lines() {
+    let i = Instrument::from_sfz(HIERARCHY, Path::new("")).unwrap();
+    assert_eq!(i.groups[0].source_line(), Some(6));
+    assert_eq!(i.groups[1].source_line(), Some(21));
+    assert_eq!(i.regions[0].source_line(), Some(9));
+    assert_eq!(i.regions[1].source_line(), Some(14));
+    assert_eq!(i.regions[2].source_line(), Some(23));
+
+    // regions not parsed from SFZ code don't have one
+    assert_eq!(Region::new().source_line(), None);
+}